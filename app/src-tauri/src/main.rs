@@ -2,17 +2,38 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 use creator_core::{
-    __cmd__calculate_timeline_at_curr_frame, __cmd__get_system_families, __cmd__get_system_font,
+    __cmd__bake_animated_float, __cmd__simplify_animated_float,
+    __cmd__calculate_timeline_at_curr_frame, __cmd__calculate_timeline_at_time,
+    __cmd__get_system_families, __cmd__get_system_font,
     __cmd__get_system_fonts, __cmd__get_values_at_frame_range_from_animated_float,
     __cmd__get_values_at_frame_range_from_animated_float_vec2,
     __cmd__get_values_at_frame_range_from_animated_float_vec3,
+    __cmd__get_velocities_at_frame_range_from_animated_float,
+    __cmd__get_velocities_at_frame_range_from_animated_float_vec2,
+    __cmd__get_velocities_at_frame_range_from_animated_float_vec3, __cmd__get_transform_matrix,
+    __cmd__get_spring_settle_time, __cmd__get_spring_from_duration_and_bounce,
+    __cmd__analyze_audio_file, __cmd__validate_timeline_report,
+    __cmd__convert_timeline_frame_rate, __cmd__format_timecode, __cmd__parse_timecode,
+    __cmd__parse_svg_path,
     animation::{
         primitives::values::animated_values::{
+            bake_animated_float, simplify_animated_float,
             get_values_at_frame_range_from_animated_float,
             get_values_at_frame_range_from_animated_float_vec2,
             get_values_at_frame_range_from_animated_float_vec3,
+            get_velocities_at_frame_range_from_animated_float,
+            get_velocities_at_frame_range_from_animated_float_vec2,
+            get_velocities_at_frame_range_from_animated_float_vec3,
+        },
+        primitives::interpolations::{get_spring_from_duration_and_bounce, get_spring_settle_time},
+        primitives::transform::get_transform_matrix,
+        primitives::audio::analyze_audio_file,
+        primitives::path::parse_svg_path,
+        primitives::timecode::{format_timecode, parse_timecode},
+        timeline::{
+            calculate_timeline_at_curr_frame, calculate_timeline_at_time,
+            convert_timeline_frame_rate, validate_timeline_report,
         },
-        timeline::calculate_timeline_at_curr_frame,
     },
     fonts::fonts::{get_system_families, get_system_font, get_system_fonts},
 };
@@ -20,13 +41,28 @@ use creator_core::{
 fn main() {
     tauri::Builder::default()
         .invoke_handler(tauri::generate_handler![
+            bake_animated_float,
+            simplify_animated_float,
             calculate_timeline_at_curr_frame,
+            calculate_timeline_at_time,
             get_system_font,
             get_system_families,
             get_system_fonts,
             get_values_at_frame_range_from_animated_float,
             get_values_at_frame_range_from_animated_float_vec2,
-            get_values_at_frame_range_from_animated_float_vec3
+            get_values_at_frame_range_from_animated_float_vec3,
+            get_velocities_at_frame_range_from_animated_float,
+            get_velocities_at_frame_range_from_animated_float_vec2,
+            get_velocities_at_frame_range_from_animated_float_vec3,
+            get_transform_matrix,
+            get_spring_settle_time,
+            get_spring_from_duration_and_bounce,
+            analyze_audio_file,
+            validate_timeline_report,
+            convert_timeline_frame_rate,
+            format_timecode,
+            parse_timecode,
+            parse_svg_path
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
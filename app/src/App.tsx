@@ -7,6 +7,7 @@ import useKeyControls from "hooks/useKeyControls";
 import { useFontsStore } from "stores/fonts.store";
 import * as ScrollArea from "@radix-ui/react-scroll-area";
 import ScrollBar from "components/ScrollArea";
+import ValidationReport from "components/ValidationReport";
 
 export default function App() {
   const fontsStoreDidInit = useFontsStore((store) => store.didInit);
@@ -29,6 +30,7 @@ export default function App() {
                   </PropertiesContainer>
                 </div>
                 <Timeline />
+                <ValidationReport />
               </div>
             </ScrollArea.Viewport>
             <ScrollBar />
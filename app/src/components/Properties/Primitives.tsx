@@ -5,8 +5,13 @@ import {
   AnimatedRectEntity,
   AnimatedStaggeredTextEntity,
   AnimatedEllipseEntity,
+  AnimatedPathEntity,
 } from "primitives/AnimatedEntities";
-import { Paint, PaintStyle, PaintStyleType } from "primitives/Paint";
+import {
+  AnimatedPaint,
+  AnimatedPaintStyle,
+  PaintStyleType,
+} from "primitives/Paint";
 import { FC } from "react";
 import { z } from "zod";
 import { ColorProperties } from "./Values";
@@ -24,11 +29,12 @@ type TextPropertiesProps = PropertiesProps<z.input<typeof AnimatedTextEntity>>;
 type StaggeredTextPropertiesProps = PropertiesProps<
   z.input<typeof AnimatedStaggeredTextEntity>
 >;
-type PaintPropertiesProps = PropertiesProps<z.input<typeof Paint>>;
+type PaintPropertiesProps = PropertiesProps<z.input<typeof AnimatedPaint>>;
 type RectPropertiesProps = PropertiesProps<z.input<typeof AnimatedRectEntity>>;
 type EllipsePropertiesProps = PropertiesProps<
   z.input<typeof AnimatedEllipseEntity>
 >;
+type PathPropertiesProps = PropertiesProps<z.input<typeof AnimatedPathEntity>>;
 
 export const PaintProperties: FC<PaintPropertiesProps> = ({
   entity,
@@ -44,7 +50,7 @@ export const PaintProperties: FC<PaintPropertiesProps> = ({
             if (entity.style.type !== value) {
               const paintStyle = { type: value };
 
-              const parsedPaintStyle = PaintStyle.parse(paintStyle);
+              const parsedPaintStyle = AnimatedPaintStyle.parse(paintStyle);
 
               onUpdate({ style: parsedPaintStyle });
             }
@@ -60,7 +66,7 @@ export const PaintProperties: FC<PaintPropertiesProps> = ({
           </SelectContent>
         </Select>
       </fieldset>
-      {entity.style.color && (
+      {entity.style.color && "value" in entity.style.color && (
         <ColorProperties
           label="Color"
           onUpdate={(color) =>
@@ -253,3 +259,19 @@ export const EllipseProperties: FC<EllipsePropertiesProps> = ({
     </div>
   );
 };
+
+export const PathProperties: FC<PathPropertiesProps> = ({
+  entity,
+  onUpdate,
+}) => {
+  return (
+    <div className="dark:text-white">
+      <PaintProperties
+        entity={entity.paint}
+        onUpdate={(paint) =>
+          onUpdate({ ...entity, paint: { ...entity.paint, ...paint } })
+        }
+      />
+    </div>
+  );
+};
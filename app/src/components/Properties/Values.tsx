@@ -1,6 +1,7 @@
 import { AnimatedNumber, AnimatedVec2 } from "primitives/Values";
 import { PropertiesProps } from "./common";
-import { FC } from "react";
+import { FC, useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api";
 import { z } from "zod";
 import { produce } from "immer";
 import { Interpolation, InterpolationType } from "primitives/Interpolation";
@@ -18,10 +19,59 @@ import {
   SelectItem,
 } from "components/Inputs/Select";
 
+/** Distance to the target, relative to the start, below which a spring counts as settled */
+const SPRING_SETTLE_PRECISION = 0.001;
+
 const SpringInterpolationProperties: FC<
   PropertiesProps<z.input<typeof SpringInterpolation>>
 > = ({ entity, onUpdate }) => {
-  return <div></div>;
+  const [settleTime, setSettleTime] = useState<number | null>(null);
+
+  useEffect(() => {
+    invoke("get_spring_settle_time", {
+      spring: SpringInterpolation.parse(entity),
+      precision: SPRING_SETTLE_PRECISION,
+    }).then((data) => setSettleTime(z.number().nullable().parse(data)));
+  }, [entity]);
+
+  return (
+    <fieldset>
+      <span className="label">
+        Settles after{" "}
+        {settleTime === null ? "never" : `${settleTime.toFixed(2)}s`}
+      </span>
+      <label htmlFor="spring-run-to-rest">
+        <input
+          id="spring-run-to-rest"
+          type="checkbox"
+          checked={entity.run_to_rest ?? false}
+          onChange={(e) =>
+            onUpdate(
+              produce(entity, (draft) => {
+                draft.run_to_rest = e.target.checked;
+              })
+            )
+          }
+        />
+        Run to rest
+      </label>
+      <label htmlFor="spring-inherit-velocity">
+        <input
+          id="spring-inherit-velocity"
+          type="checkbox"
+          checked={entity.inherit_velocity ?? false}
+          onChange={(e) =>
+            onUpdate(
+              produce(entity, (draft) => {
+                draft.inherit_velocity = e.target.checked;
+              })
+            )
+          }
+        />
+        Inherit velocity
+      </label>
+    </fieldset>
+  );
 };
 
 export const InterpolationProperties: FC<
@@ -55,6 +105,9 @@ export const InterpolationProperties: FC<
           ))}
         </SelectContent>
       </Select>
+      {entity.type === InterpolationType.Enum.Spring && (
+        <SpringInterpolationProperties entity={entity} onUpdate={onUpdate} />
+      )}
     </fieldset>
   );
 };
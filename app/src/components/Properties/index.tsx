@@ -5,6 +5,7 @@ import { shallow } from "zustand/shallow";
 import {
   RectProperties,
   EllipseProperties,
+  PathProperties,
   TextProperties,
   StaggeredTextProperties,
 } from "./Primitives";
@@ -67,6 +68,15 @@ const Properties = () => {
           />
         );
 
+      case "Path":
+        return (
+          <PathProperties
+            key={selectedEntity}
+            onUpdate={(entity) => updateEntity(selectedEntity, entity)}
+            entity={entity}
+          />
+        );
+
       default:
         return null;
     }
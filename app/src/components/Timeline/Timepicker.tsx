@@ -3,6 +3,7 @@ import * as Slider from "@radix-ui/react-slider";
 import { useRenderStateStore } from "stores/render-state.store";
 import { TIMELINE_SCALE } from "./common";
 import { useTimelineStore } from "stores/timeline.store";
+import { frameRateToNumber } from "primitives/FrameRate";
 
 export type TimePickerProps = {};
 
@@ -17,7 +18,7 @@ const TimePicker: FC<TimePickerProps> = () => {
       style={{ width: TIMELINE_SCALE * 10 }}
       value={[renderState.curr_frame]}
       onValueChange={(val) => setCurrentFrame(val[0])}
-      max={timeline.fps * timeline.duration}
+      max={frameRateToNumber(timeline.fps) * timeline.duration}
       step={1}
       aria-label="Current Frame"
     >
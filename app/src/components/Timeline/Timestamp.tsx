@@ -1,19 +1,42 @@
+import { invoke } from "@tauri-apps/api";
+import { useEffect, useState } from "react";
+import {
+  formatFrameRate,
+  frameRateToNumber,
+  supportsDropFrame,
+} from "primitives/FrameRate";
 import { useRenderStateStore } from "stores/render-state.store";
 import { useTimelineStore } from "stores/timeline.store";
 
 const Timestamp = () => {
   const { renderState } = useRenderStateStore();
   const timeline = useTimelineStore();
+  const [timecode, setTimecode] = useState<string>();
+  const fps = frameRateToNumber(timeline.fps);
+
+  useEffect(() => {
+    invoke("format_timecode", {
+      frame: renderState.curr_frame,
+      fps: timeline.fps,
+      dropFrame: supportsDropFrame(timeline.fps),
+    })
+      .then((data) => setTimecode(data as string))
+      .catch(() => setTimecode(undefined));
+  }, [renderState.curr_frame, timeline.fps]);
 
   return (
     <div>
       <h3>
-        Frame {renderState.curr_frame} / {timeline.fps * timeline.duration}
+        Frame {renderState.curr_frame} /{" "}
+        {Math.round(fps * timeline.duration)}
+        {timecode && <span className="ml-2 font-mono">{timecode}</span>}
       </h3>
       <h2 className="text-xl font-bold">
-        {(renderState.curr_frame / timeline.fps).toPrecision(3)} /{" "}
+        {(renderState.curr_frame / fps).toPrecision(3)} /{" "}
         {timeline.duration.toPrecision(3)}
-        <span className="text-sm font-light">/ {timeline.fps}FPS</span>
+        <span className="text-sm font-light">
+          / {formatFrameRate(timeline.fps)}FPS
+        </span>
       </h2>
     </div>
   );
@@ -20,12 +20,13 @@ import { AnimatedValue } from "primitives/Values";
 import { motion } from "framer-motion";
 import { ease } from "@unom/style";
 import { TrackDisplayType } from "../Track";
-import TrackPropertyGraph from "./TrackPropertyGraph";
-import { LineChart } from "lucide-react";
+import TrackPropertyGraph, { GraphMode } from "./TrackPropertyGraph";
+import { Gauge, LineChart } from "lucide-react";
 
 type DisplayState = {
   type: z.input<typeof TrackDisplayType>;
   selectedAnimatedProperties: Array<number>;
+  graphMode: z.input<typeof GraphMode>;
 };
 
 const TrackAnimatedPropertyKeyframes: FC<{
@@ -157,6 +158,20 @@ const TrackAnimatedProperty: FC<{
           >
             <LineChart />
           </ToggleGroupItem>
+          <ToggleGroupItem
+            selected={displayState.graphMode === GraphMode.Enum.Speed}
+            onClick={() =>
+              onDisplayStateUpdate({
+                ...displayState,
+                graphMode:
+                  displayState.graphMode === GraphMode.Enum.Speed
+                    ? GraphMode.Enum.Value
+                    : GraphMode.Enum.Speed,
+              })
+            }
+          >
+            <Gauge />
+          </ToggleGroupItem>
         </ToggleGroup>
       </div>
 
@@ -204,6 +219,7 @@ const TrackPropertiesEditor: FC<{
   const [displayState, setDisplayState] = useState<DisplayState>({
     type: TrackDisplayType.Enum.Default,
     selectedAnimatedProperties: [],
+    graphMode: GraphMode.Enum.Value,
   });
 
   return (
@@ -229,6 +245,7 @@ const TrackPropertiesEditor: FC<{
       </motion.div>
       {displayState.selectedAnimatedProperties.length > 0 && (
         <TrackPropertyGraph
+          mode={displayState.graphMode}
           animationData={entity.animation_data}
           animatedProperties={displayState.selectedAnimatedProperties.map(
             (index) => animatedProperties[index]
@@ -6,19 +6,28 @@ import { FC, useEffect, useState } from "react";
 import { z } from "zod";
 import Graph from "./Graph";
 
+/** Value plots the animated values, Speed plots their rate of change per second */
+export const GraphMode = z.enum(["Value", "Speed"]);
+
 type TrackPropertyPathProps = {
   animatedProperties: Array<z.input<typeof AnimatedProperty>>;
   animationData: z.input<typeof AnimationData>;
+  mode?: z.input<typeof GraphMode>;
 };
 
 const TrackPropertyGraph: FC<TrackPropertyPathProps> = ({
   animatedProperties,
   animationData,
+  mode = GraphMode.Enum.Value,
 }) => {
   const [values, setValues] = useState<Array<Array<number>>>([]);
 
   useEffect(() => {
     const tasks: Array<Promise<Array<Array<number>>>> = [];
+    const command =
+      mode === GraphMode.Enum.Speed
+        ? "get_velocities_at_frame_range_from_animated_float"
+        : "get_values_at_frame_range_from_animated_float";
 
     animatedProperties.forEach((animatedProperty) => {
       animatedProperty.animatedValue.type;
@@ -41,10 +50,7 @@ const TrackPropertyGraph: FC<TrackPropertyPathProps> = ({
       switch (animatedValue.type) {
         case ValueType.Enum.Number:
           tasks.push(
-            invoke(
-              "get_values_at_frame_range_from_animated_float",
-              commonValues
-            ).then((data) => {
+            invoke(command, commonValues).then((data) => {
               const numbers = data as Array<number>;
 
               return [numbers];
@@ -53,12 +59,13 @@ const TrackPropertyGraph: FC<TrackPropertyPathProps> = ({
           break;
         case ValueType.Enum.Vec2:
           tasks.push(
-            invoke(
-              "get_values_at_frame_range_from_animated_float_vec2",
-              commonValues
-            ).then((data) => {
+            invoke(`${command}_vec2`, commonValues).then((data) => {
               const vectors = data as [Array<number>, Array<number>];
 
+              if (mode === GraphMode.Enum.Speed) {
+                return [vectors.map(([x, y]) => Math.hypot(x, y))];
+              }
+
               const xValues = vectors.map((vec2) => vec2[0]);
               const yValues = vectors.map((vec2) => vec2[1]);
 
@@ -69,16 +76,17 @@ const TrackPropertyGraph: FC<TrackPropertyPathProps> = ({
 
         case ValueType.Enum.Vec3:
           tasks.push(
-            invoke(
-              "get_values_at_frame_range_from_animated_float_vec3",
-              commonValues
-            ).then((data) => {
+            invoke(`${command}_vec3`, commonValues).then((data) => {
               const vectors = data as [
                 Array<number>,
                 Array<number>,
                 Array<number>
               ];
 
+              if (mode === GraphMode.Enum.Speed) {
+                return [vectors.map(([x, y, z]) => Math.hypot(x, y, z))];
+              }
+
               const xValues = vectors.map((vec2) => vec2[0]);
               const yValues = vectors.map((vec2) => vec2[1]);
               const zValues = vectors.map((vec2) => vec2[2]);
@@ -96,7 +104,7 @@ const TrackPropertyGraph: FC<TrackPropertyPathProps> = ({
       console.log("flattened Values", flatValues);
       setValues(flatValues);
     });
-  }, animatedProperties);
+  }, [...animatedProperties, mode]);
 
   return (
     <div>
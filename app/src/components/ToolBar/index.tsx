@@ -71,7 +71,7 @@ const ToolBar = () => {
         <ToolBarButton onClick={() => entitiesService.createEllipse()}>
           <CircleIcon width="100%" height="100%" />
         </ToolBarButton>
-        <ToolBarButton>
+        <ToolBarButton onClick={() => entitiesService.createPath()}>
           <Pencil1Icon width="100%" height="100%" />
         </ToolBarButton>
         <ToolBarButton>
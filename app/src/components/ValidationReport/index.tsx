@@ -0,0 +1,54 @@
+import { invoke } from "@tauri-apps/api";
+import { FC, useEffect, useState } from "react";
+import { AnimatedEntities } from "primitives/AnimatedEntities";
+import { ValidationReport as ValidationReportSchema } from "primitives/Validation";
+import { useEntitiesStore } from "stores/entities.store";
+import { useRenderStateStore } from "stores/render-state.store";
+import { useTimelineStore } from "stores/timeline.store";
+import { z } from "zod";
+
+const ValidationReport: FC = () => {
+  const entities = useEntitiesStore((store) => store.entities);
+  const { fps, size, duration } = useTimelineStore();
+  const renderState = useRenderStateStore((store) => store.renderState);
+  const [report, setReport] =
+    useState<z.output<typeof ValidationReportSchema>>();
+
+  useEffect(() => {
+    invoke("validate_timeline_report", {
+      timeline: {
+        entities: AnimatedEntities.parse(entities),
+        render_state: renderState,
+        fps,
+        size,
+        duration,
+      },
+    })
+      .then((data) => setReport(ValidationReportSchema.parse(data)))
+      .catch((error) => console.error("Failed to validate timeline", error));
+  }, [entities, fps, size, duration]);
+
+  if (!report || report.issues.length === 0) {
+    return null;
+  }
+
+  return (
+    <div className="w-full rounded-md border border-gray-600 p-4 mb-4">
+      <h3>Probleme</h3>
+      <ul className="text-sm">
+        {report.issues.map((issue, index) => (
+          <li
+            key={index}
+            className={
+              issue.severity === "Error" ? "text-red-400" : "text-yellow-400"
+            }
+          >
+            <span className="font-mono">{issue.path}</span>: {issue.message}
+          </li>
+        ))}
+      </ul>
+    </div>
+  );
+};
+
+export default ValidationReport;
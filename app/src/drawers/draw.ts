@@ -18,6 +18,7 @@ import drawStaggeredText, {
 import drawText, { TextCache, TextEntityCache, buildTextCache } from "./text";
 import drawEllipse from "./ellipse";
 import drawRect from "./rect";
+import drawPath from "./path";
 import { useEntitiesStore } from "stores/entities.store";
 import { handleEntityCache } from "./cache";
 import { DependenciesService } from "services/dependencies.service";
@@ -183,6 +184,9 @@ export class Drawer {
           case EntityType.Enum.Ellipse:
             drawEllipse(CanvasKit, canvas, entity);
             break;
+          case EntityType.Enum.Path:
+            drawPath(CanvasKit, canvas, entity);
+            break;
           case EntityType.Enum.Text:
             {
               const cache = handleEntityCache<
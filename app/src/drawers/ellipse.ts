@@ -11,7 +11,7 @@ export default function drawEllipse(
 ) {
   const paint = new CanvasKit.Paint();
 
-  buildPaintStyle(CanvasKit, paint, entity.paint);
+  buildPaintStyle(CanvasKit, paint, entity.paint, entity.opacity);
 
   const mappedPosition = entity.position.map(
     (val, index) => val - entity.radius[index] * 0.5
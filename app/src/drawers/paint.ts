@@ -6,12 +6,14 @@ import { z } from "zod";
 export function buildPaintStyle(
   CanvasKit: CanvasKit,
   skPaint: SkPaint,
-  paint: z.output<typeof Paint>
+  paint: z.output<typeof Paint>,
+  opacity = 1
 ) {
   const color = convertToFloat(paint.style.color.value);
 
   skPaint.setAntiAlias(true);
   skPaint.setColor(color);
+  skPaint.setAlphaf(color[3] * opacity);
 
   switch (paint.style.type) {
     case "Fill":
@@ -0,0 +1,78 @@
+import { Canvas, CanvasKit } from "canvaskit-wasm";
+import { z } from "zod";
+import { PathEntity } from "primitives/Entities";
+import { buildPaintStyle } from "./paint";
+
+export default function drawPath(
+  CanvasKit: CanvasKit,
+  canvas: Canvas,
+  entity: z.infer<typeof PathEntity>
+) {
+  canvas.save();
+
+  const paint = new CanvasKit.Paint();
+
+  buildPaintStyle(CanvasKit, paint, entity.paint, entity.opacity);
+
+  const path = new CanvasKit.Path();
+
+  entity.path.forEach((command) => {
+    switch (command.type) {
+      case "MoveTo":
+        path.moveTo(command.point[0], command.point[1]);
+        break;
+      case "LineTo":
+        path.lineTo(command.point[0], command.point[1]);
+        break;
+      case "CubicTo":
+        path.cubicTo(
+          command.control1[0],
+          command.control1[1],
+          command.control2[0],
+          command.control2[1],
+          command.point[0],
+          command.point[1]
+        );
+        break;
+      case "QuadTo":
+        path.quadTo(
+          command.control[0],
+          command.control[1],
+          command.point[0],
+          command.point[1]
+        );
+        break;
+      case "Close":
+        path.close();
+        break;
+    }
+  });
+
+  canvas.translate(entity.position[0], entity.position[1]);
+
+  if (entity.transform) {
+    const origin = entity.origin;
+
+    canvas.translate(origin[0], origin[1]);
+
+    canvas.translate(
+      entity.transform.translate[0],
+      entity.transform.translate[1]
+    );
+
+    canvas.rotate(entity.transform.rotate[2], 0, 0);
+
+    canvas.skew(entity.transform.skew[0], entity.transform.skew[1]);
+
+    canvas.scale(entity.transform.scale[0], entity.transform.scale[1]);
+
+    canvas.translate(-origin[0], -origin[1]);
+  }
+
+  canvas.drawPath(path, paint);
+
+  canvas.restore();
+
+  path.delete();
+  paint.delete();
+}
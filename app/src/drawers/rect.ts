@@ -12,7 +12,7 @@ export default function drawRect(
 
   const paint = new CanvasKit.Paint();
 
-  buildPaintStyle(CanvasKit, paint, entity.paint);
+  buildPaintStyle(CanvasKit, paint, entity.paint, entity.opacity);
 
   const mappedPosition = entity.position.map(
     (val, index) => val - entity.size[index] * 0.5
@@ -199,7 +199,7 @@ export default function drawStaggeredText(
 
   const { letterMeasures: measuredLetters, font, glyphs, metrics } = cache;
 
-  buildPaintStyle(CanvasKit, paint, entity.letter.paint);
+  buildPaintStyle(CanvasKit, paint, entity.letter.paint, entity.opacity);
 
   if (glyphs) {
     // Draw all those runs.
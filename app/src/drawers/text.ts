@@ -39,8 +39,9 @@ export default function drawText(
   const paint = new CanvasKit.Paint();
 
   const color = convertToFloat(entity.paint.style.color.value);
+  color[3] *= entity.opacity;
 
-  buildPaintStyle(CanvasKit, paint, entity.paint);
+  buildPaintStyle(CanvasKit, paint, entity.paint, entity.opacity);
 
   const pStyle = new CanvasKit.ParagraphStyle({
     textStyle: {
@@ -3,17 +3,24 @@ import {
   BaseEntity,
   EllipseEntity,
   EntityType,
+  PathEntity,
   RectEntity,
   TextEntity,
 } from "./Entities";
-import { AnimatedTransform, AnimatedVec2 } from "./Values";
-import { TextPaint } from "./Paint";
+import { AnimatedNumber, AnimatedTransform, AnimatedVec2 } from "./Values";
+import { AnimatedPaint, AnimatedTextPaint, TextPaint } from "./Paint";
 import { AnimatedProperties } from "./AnimatedProperty";
 
 export const AnimationData = z.object({
   offset: z.number(),
   duration: z.number(),
   visible: z.boolean().optional().default(true),
+  /** Playback rate, 0 freezes the entity */
+  speed: z.number().optional().default(1),
+  /** Plays the entity backwards */
+  reverse: z.boolean().optional().default(false),
+  /** Maps the seconds since offset to the seconds the properties are evaluated at */
+  time_remap: AnimatedNumber.optional(),
 });
 
 export const AnimatedStaggeredTextEntity = BaseEntity.extend({
@@ -28,29 +35,70 @@ export const AnimatedStaggeredTextEntity = BaseEntity.extend({
   }),
   text: z.string(),
   origin: AnimatedVec2,
+  /** Opacity of the whole entity from 0 to 1, fully opaque when omitted */
+  opacity: AnimatedNumber.optional(),
   animation_data: AnimationData,
   type: z.literal(EntityType.Enum.StaggeredText),
 });
 
 export const AnimatedRectEntity = RectEntity.extend({
+  paint: AnimatedPaint,
   position: AnimatedVec2,
   size: AnimatedVec2,
   origin: AnimatedVec2,
   transform: AnimatedTransform.optional(),
+  /** Opacity of the whole entity from 0 to 1, fully opaque when omitted */
+  opacity: AnimatedNumber.optional(),
   animation_data: AnimationData,
 });
 
 export const AnimatedTextEntity = TextEntity.extend({
+  paint: AnimatedTextPaint,
   origin: AnimatedVec2,
   transform: AnimatedTransform.optional(),
+  /** Opacity of the whole entity from 0 to 1, fully opaque when omitted */
+  opacity: AnimatedNumber.optional(),
   animation_data: AnimationData,
 });
 
 export const AnimatedEllipseEntity = EllipseEntity.extend({
+  paint: AnimatedPaint,
   radius: AnimatedVec2,
   position: AnimatedVec2,
   origin: AnimatedVec2,
   transform: AnimatedTransform.optional(),
+  /** Opacity of the whole entity from 0 to 1, fully opaque when omitted */
+  opacity: AnimatedNumber.optional(),
+  animation_data: AnimationData,
+});
+
+/** A drawing command of a path whose points are animated independently */
+export const AnimatedPathCommand = z.discriminatedUnion("type", [
+  z.object({ type: z.literal("MoveTo"), point: AnimatedVec2 }),
+  z.object({ type: z.literal("LineTo"), point: AnimatedVec2 }),
+  z.object({
+    type: z.literal("CubicTo"),
+    control1: AnimatedVec2,
+    control2: AnimatedVec2,
+    point: AnimatedVec2,
+  }),
+  z.object({
+    type: z.literal("QuadTo"),
+    control: AnimatedVec2,
+    point: AnimatedVec2,
+  }),
+  z.object({ type: z.literal("Close") }),
+]);
+
+export const AnimatedPathEntity = PathEntity.extend({
+  paint: AnimatedPaint,
+  /** Parsed from SVG path data with the parse_svg_path command */
+  path: z.array(AnimatedPathCommand),
+  position: AnimatedVec2,
+  origin: AnimatedVec2,
+  transform: AnimatedTransform.optional(),
+  /** Opacity of the whole entity from 0 to 1, fully opaque when omitted */
+  opacity: AnimatedNumber.optional(),
   animation_data: AnimationData,
 });
 
@@ -59,6 +107,7 @@ export const AnimatedEntity = z.discriminatedUnion("type", [
   AnimatedTextEntity,
   AnimatedStaggeredTextEntity,
   AnimatedEllipseEntity,
+  AnimatedPathEntity,
 ]);
 
 export const AnimatedEntities = z.array(AnimatedEntity);
@@ -97,6 +146,59 @@ export function animatedTransformToAnimatedProperties(
   ];
 }
 
+export function animatedPathCommandToAnimatedProperties(
+  command: z.input<typeof AnimatedPathCommand>,
+  index: number
+): z.input<typeof AnimatedProperties> {
+  const basePath = `path.${index}`;
+  const label = `Point ${index + 1}`;
+
+  switch (command.type) {
+    case "MoveTo":
+    case "LineTo":
+      return [
+        {
+          animatedValue: command.point,
+          label,
+          propertyPath: basePath + ".point",
+        },
+      ];
+    case "CubicTo":
+      return [
+        {
+          animatedValue: command.control1,
+          label: label + " Control 1",
+          propertyPath: basePath + ".control1",
+        },
+        {
+          animatedValue: command.control2,
+          label: label + " Control 2",
+          propertyPath: basePath + ".control2",
+        },
+        {
+          animatedValue: command.point,
+          label,
+          propertyPath: basePath + ".point",
+        },
+      ];
+    case "QuadTo":
+      return [
+        {
+          animatedValue: command.control,
+          label: label + " Control",
+          propertyPath: basePath + ".control",
+        },
+        {
+          animatedValue: command.point,
+          label,
+          propertyPath: basePath + ".point",
+        },
+      ];
+    case "Close":
+      return [];
+  }
+}
+
 export function getAnimatedPropertiesByAnimatedEntity(
   animatedEntity: z.input<typeof AnimatedEntity>
 ) {
@@ -178,6 +280,39 @@ export function getAnimatedPropertiesByAnimatedEntity(
         );
       }
       break;
+
+    case "Path":
+      animatedProperties.push({
+        propertyPath: "origin",
+        animatedValue: animatedEntity.origin,
+        label: "Origin",
+      });
+      animatedProperties.push({
+        propertyPath: "position",
+        animatedValue: animatedEntity.position,
+        label: "Position",
+      });
+
+      animatedEntity.path.forEach((command, index) => {
+        animatedProperties.push(
+          ...animatedPathCommandToAnimatedProperties(command, index)
+        );
+      });
+
+      if (animatedEntity.transform) {
+        animatedProperties.push(
+          ...animatedTransformToAnimatedProperties(animatedEntity.transform)
+        );
+      }
+      break;
+  }
+
+  if (animatedEntity.opacity) {
+    animatedProperties.push({
+      propertyPath: "opacity",
+      animatedValue: animatedEntity.opacity,
+      label: "Opacity",
+    });
   }
 
   return animatedProperties;
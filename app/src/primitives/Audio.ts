@@ -0,0 +1,17 @@
+import { z } from "zod";
+import { FrameRate } from "./FrameRate";
+
+/** Range of frequencies in hertz, the high end is exclusive */
+export const FrequencyBand = z.object({
+  low: z.number().nonnegative(),
+  high: z.number().nonnegative(),
+});
+
+/** Loudness of an audio file at every frame, as returned by analyze_audio_file */
+export const AudioAnalysis = z.object({
+  fps: FrameRate,
+  /** RMS amplitude of every frame, 1 is the loudest frame */
+  envelope: z.array(z.number()),
+  /** Energy of every frame for each requested band */
+  bands: z.array(z.array(z.number())),
+});
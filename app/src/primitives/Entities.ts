@@ -2,7 +2,13 @@ import { z } from "zod";
 import { Vec2, Vec3 } from "./Values";
 import { Paint, TextPaint } from "./Paint";
 
-const EntityTypeOptions = ["Text", "Ellipse", "Rect", "StaggeredText"] as const;
+const EntityTypeOptions = [
+  "Text",
+  "Ellipse",
+  "Rect",
+  "StaggeredText",
+  "Path",
+] as const;
 
 export const EntityType = z.enum(EntityTypeOptions);
 
@@ -33,6 +39,8 @@ export const StaggeredTextEntity = BaseEntity.extend({
   }),
   origin: Vec2,
   text: z.string(),
+  /** Opacity of the whole entity from 0 to 1 */
+  opacity: z.number().min(0).max(1).default(1),
   type: z.literal(EntityType.Enum.StaggeredText),
 });
 
@@ -41,6 +49,8 @@ export const RectEntity = GeometryEntity.extend({
   size: Vec2,
   position: Vec2,
   origin: Vec2,
+  /** Opacity of the whole entity from 0 to 1 */
+  opacity: z.number().min(0).max(1).default(1),
   transform: z.nullable(Transform),
 });
 
@@ -49,6 +59,34 @@ export const EllipseEntity = GeometryEntity.extend({
   radius: Vec2,
   position: Vec2,
   origin: Vec2,
+  /** Opacity of the whole entity from 0 to 1 */
+  opacity: z.number().min(0).max(1).default(1),
+  transform: z.nullable(Transform),
+});
+
+/** A drawing command of a path, all points are absolute */
+export const PathCommand = z.discriminatedUnion("type", [
+  z.object({ type: z.literal("MoveTo"), point: Vec2 }),
+  z.object({ type: z.literal("LineTo"), point: Vec2 }),
+  z.object({
+    type: z.literal("CubicTo"),
+    control1: Vec2,
+    control2: Vec2,
+    point: Vec2,
+  }),
+  z.object({ type: z.literal("QuadTo"), control: Vec2, point: Vec2 }),
+  /** Draws a line back to the start of the current sub path */
+  z.object({ type: z.literal("Close") }),
+]);
+
+export const PathEntity = GeometryEntity.extend({
+  type: z.literal(EntityType.Enum.Path),
+  /** Points are relative to the position */
+  path: z.array(PathCommand),
+  position: Vec2,
+  origin: Vec2,
+  /** Opacity of the whole entity from 0 to 1 */
+  opacity: z.number().min(0).max(1).default(1),
   transform: z.nullable(Transform),
 });
 
@@ -57,6 +95,8 @@ export const TextEntity = BaseEntity.extend({
   paint: TextPaint,
   origin: Vec2,
   text: z.string(),
+  /** Opacity of the whole entity from 0 to 1 */
+  opacity: z.number().min(0).max(1).default(1),
   transform: z.nullable(Transform),
 });
 
@@ -65,6 +105,7 @@ export const Entity = z.discriminatedUnion("type", [
   EllipseEntity,
   TextEntity,
   StaggeredTextEntity,
+  PathEntity,
 ]);
 
 export const Entities = z.array(Entity);
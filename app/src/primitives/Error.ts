@@ -0,0 +1,46 @@
+import { z } from "zod";
+
+/** Structured error returned by tauri commands and thrown as JSON by the wasm module */
+export const CreatorError = z.discriminatedUnion("type", [
+  z.object({
+    type: z.literal("InvalidJson"),
+    message: z.string(),
+    line: z.number(),
+    column: z.number(),
+  }),
+  z.object({ type: z.literal("Serialization"), message: z.string() }),
+  z.object({ type: z.literal("EmptyKeyframes") }),
+  z.object({ type: z.literal("FontNotFound"), name: z.string() }),
+  z.object({
+    type: z.literal("FontLoad"),
+    name: z.string(),
+    message: z.string(),
+  }),
+  z.object({ type: z.literal("Io"), path: z.string(), message: z.string() }),
+  z.object({ type: z.literal("InvalidAudio"), message: z.string() }),
+  z.object({ type: z.literal("InvalidFrameRate"), fps: z.string() }),
+  z.object({
+    type: z.literal("InvalidTimecode"),
+    timecode: z.string(),
+    message: z.string(),
+  }),
+  z.object({
+    type: z.literal("InvalidSvgPath"),
+    message: z.string(),
+    position: z.number(),
+  }),
+]);
+
+/** Reads a CreatorError from a rejected invoke or a wasm exception */
+export function parseCreatorError(
+  error: unknown
+): z.output<typeof CreatorError> | undefined {
+  try {
+    const value = typeof error === "string" ? JSON.parse(error) : error;
+    const result = CreatorError.safeParse(value);
+
+    return result.success ? result.data : undefined;
+  } catch {
+    return undefined;
+  }
+}
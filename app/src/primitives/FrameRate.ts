@@ -0,0 +1,31 @@
+import { z } from "zod";
+
+/**
+ * Frames per second, either a plain number or an exact fraction like 30000/1001 for 29.97.
+ * Decimal NTSC rates like 23.976, 29.97 or 59.94 are read as their exact fraction.
+ */
+export const FrameRate = z.union([
+  z.number().positive(),
+  z.object({
+    numerator: z.number().int().positive(),
+    denominator: z.number().int().positive(),
+  }),
+]);
+
+export function frameRateToNumber(fps: z.input<typeof FrameRate>): number {
+  return typeof fps === "number" ? fps : fps.numerator / fps.denominator;
+}
+
+/** Integer rates as is, fractional ones with three decimals like 29.970 */
+export function formatFrameRate(fps: z.input<typeof FrameRate>): string {
+  const value = frameRateToNumber(fps);
+
+  return Number.isInteger(value) ? value.toString() : value.toFixed(3);
+}
+
+/** NTSC rates based on a multiple of 30 can use drop-frame timecode */
+export function supportsDropFrame(fps: z.input<typeof FrameRate>): boolean {
+  const value = frameRateToNumber(fps);
+
+  return !Number.isInteger(value) && Math.round(value) % 30 === 0;
+}
@@ -4,6 +4,9 @@ const InterpolationTypeOptions = [
   "Linear",
   "Spring",
   "EasingFunction",
+  "CubicBezier",
+  "Hold",
+  "Steps",
 ] as const;
 
 const EasingFunctionOptions = [
@@ -25,6 +28,18 @@ const EasingFunctionOptions = [
   "QuartOut",
   "QuartIn",
   "QuartInOut",
+  "SineOut",
+  "SineIn",
+  "SineInOut",
+  "BackOut",
+  "BackIn",
+  "BackInOut",
+  "ElasticOut",
+  "ElasticIn",
+  "ElasticInOut",
+  "BounceOut",
+  "BounceIn",
+  "BounceInOut",
 ] as const;
 
 export const EasingFunction = z.enum(EasingFunctionOptions);
@@ -37,17 +52,50 @@ export const LinearInterpolation = z.object({
 export const EasingFunctionInterpolation = z.object({
   type: z.literal(InterpolationType.Enum.EasingFunction),
   easing_function: EasingFunction.default("CircOut"),
+  /** Used by the Back easings, 1.70158 overshoots by about 10% */
+  overshoot: z.number().optional(),
+  /** Used by the Elastic easings, scales the first swing */
+  amplitude: z.number().min(1).optional(),
+  /** Used by the Elastic easings, length of one oscillation relative to the keyframe duration, or to each half for ElasticInOut */
+  period: z.number().positive().optional(),
 });
 
 export const SpringInterpolation = z.object({
   mass: z.number().default(1),
   damping: z.number().default(15),
   stiffness: z.number().default(200),
+  /** Keeps moving past the next keyframe until the spring comes to rest */
+  run_to_rest: z.boolean().optional(),
+  /** Starts with the velocity at the end of the previous segment */
+  inherit_velocity: z.boolean().optional(),
   type: z.literal(InterpolationType.Enum.Spring),
 });
 
+export const CubicBezierInterpolation = z.object({
+  x1: z.number().min(0).max(1).default(0.25),
+  y1: z.number().default(0.1),
+  x2: z.number().min(0).max(1).default(0.25),
+  y2: z.number().default(1),
+  type: z.literal(InterpolationType.Enum.CubicBezier),
+});
+
+export const HoldInterpolation = z.object({
+  type: z.literal(InterpolationType.Enum.Hold),
+});
+
+export const StepJump = z.enum(["Start", "End", "None", "Both"]);
+
+export const StepsInterpolation = z.object({
+  count: z.number().int().min(1).default(4),
+  jump: StepJump.default("End"),
+  type: z.literal(InterpolationType.Enum.Steps),
+});
+
 export const Interpolation = z.discriminatedUnion("type", [
   SpringInterpolation,
   EasingFunctionInterpolation,
   LinearInterpolation,
+  CubicBezierInterpolation,
+  HoldInterpolation,
+  StepsInterpolation,
 ]);
@@ -1,13 +1,59 @@
 import { z } from "zod";
 import { Interpolation } from "./Interpolation";
 
+/** One side of an After Effects style temporal ease */
+export const KeyframeEase = z.object({
+  /** Speed in units per second at the keyframe */
+  speed: z.number(),
+  /** Reach towards the neighbouring keyframe, from 0 to 1 */
+  influence: z.number().min(0).max(1),
+});
+
+export const TemporalEase = z.object({
+  ease_in: KeyframeEase,
+  ease_out: KeyframeEase,
+});
+
 export const Keyframe = z.object({
   id: z.string().uuid(),
   value: z.number(),
   offset: z.number(),
   interpolation: z.optional(Interpolation),
+  /** Overrides the interpolation of the segments on both sides */
+  temporal_ease: TemporalEase.optional(),
 });
 
+/** How a property behaves before its first and after its last keyframe */
+export const Extrapolation = z.enum([
+  "Constant",
+  "Linear",
+  "Cycle",
+  "PingPong",
+  "CycleWithOffset",
+]);
+
 export const Keyframes = z.object({
   values: z.array(Keyframe),
+  pre_extrapolation: Extrapolation.optional(),
+  post_extrapolation: Extrapolation.optional(),
+});
+
+/** A point of a motion path with the handles of the curve around it */
+export const SpatialPoint = z.object({
+  point: z.array(z.number()).length(2),
+  /** Handle of the path arriving at this point, relative to the point */
+  in_tangent: z.array(z.number()).length(2).default([0, 0]),
+  /** Handle of the path leaving this point, relative to the point */
+  out_tangent: z.array(z.number()).length(2).default([0, 0]),
+});
+
+export const SpatialKeyframe = Keyframe.extend({
+  value: SpatialPoint,
+});
+
+/** The keyframes of both axes are kept but ignored, only their drivers still apply */
+export const MotionPath = z.object({
+  keyframes: Keyframes.extend({
+    values: z.array(SpatialKeyframe),
+  }),
 });
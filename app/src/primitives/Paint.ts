@@ -1,4 +1,7 @@
 import { z } from "zod";
+import { Interpolation } from "./Interpolation";
+import { Extrapolation, TemporalEase } from "./Keyframe";
+import { AnimatedNumber } from "./Values";
 
 export const Color = z.object({
   value: z.array(z.number().min(0).max(255)).max(4),
@@ -39,6 +42,61 @@ export const TextPaint = z.object({
   size: z.number().min(0),
 });
 
+/** The color space two color keyframes are mixed in */
+export const ColorSpace = z.enum(["Srgb", "LinearRgb", "Hsl", "Oklab"]);
+
+export const ColorKeyframe = z.object({
+  id: z.string().uuid(),
+  value: Color,
+  offset: z.number(),
+  interpolation: z.optional(Interpolation),
+  temporal_ease: TemporalEase.optional(),
+});
+
+export const ColorKeyframes = z.object({
+  values: z.array(ColorKeyframe),
+  pre_extrapolation: Extrapolation.optional(),
+  post_extrapolation: Extrapolation.optional(),
+});
+
+export const AnimatedColorKeyframes = z.object({
+  keyframes: ColorKeyframes,
+  color_space: ColorSpace.optional(),
+});
+
+/** A static color or color keyframes */
+export const AnimatedColor = z.union([Color, AnimatedColorKeyframes]);
+
+const AnimatedColorWithDefault = AnimatedColor.optional().default({
+  value: [0, 0, 0, 1],
+});
+
+export const AnimatedStrokeStyle = StrokeStyle.extend({
+  color: AnimatedColorWithDefault,
+  /** A static width or width keyframes */
+  width: z
+    .union([z.number().min(0), AnimatedNumber])
+    .optional()
+    .default(10),
+});
+
+export const AnimatedFillStyle = FillStyle.extend({
+  color: AnimatedColorWithDefault,
+});
+
+export const AnimatedPaintStyle = z.discriminatedUnion("type", [
+  AnimatedStrokeStyle,
+  AnimatedFillStyle,
+]);
+
+export const AnimatedPaint = z.object({
+  style: AnimatedPaintStyle,
+});
+
+export const AnimatedTextPaint = TextPaint.extend({
+  style: AnimatedPaintStyle,
+});
+
 /* const NestedFillStyle = FillStyle.omit({ type: true }).default({});
 const NestedStrokeStyle = StrokeStyle.omit({ type: true }).default({});
 
@@ -1,5 +1,6 @@
 import { z } from "zod";
 import { AnimatedEntities } from "./AnimatedEntities";
+import { FrameRate } from "./FrameRate";
 
 export const RenderState = z.object({
   curr_frame: z.number(),
@@ -9,6 +10,6 @@ export const Timeline = z.object({
   entities: AnimatedEntities,
   render_state: RenderState,
   duration: z.number(),
-  fps: z.number().int(),
+  fps: FrameRate,
   size: z.array(z.number().int()).length(2),
 });
@@ -0,0 +1,16 @@
+import { z } from "zod";
+
+export const Severity = z.enum(["Warning", "Error"]);
+
+export const ValidationIssue = z.object({
+  severity: Severity,
+  /** Where the problem is, such as entities[2].transform.rotate.x.keyframes[0] */
+  path: z.string(),
+  message: z.string(),
+  kind: z.object({ type: z.string() }).passthrough(),
+});
+
+/** Problems found by validate_timeline_report, empty when the timeline is fine */
+export const ValidationReport = z.object({
+  issues: z.array(ValidationIssue),
+});
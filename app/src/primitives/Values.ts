@@ -1,6 +1,7 @@
 import { z } from "zod";
-import { Keyframes } from "./Keyframe";
+import { Keyframes, MotionPath } from "./Keyframe";
 import { v4 as uuid } from "uuid";
+import { FrameRate } from "./FrameRate";
 
 export const Vec2 = z.array(z.number()).length(2);
 export const Vec3 = z.array(z.number()).length(3);
@@ -11,13 +12,106 @@ const ValueTypeOptions = ["Vec2", "Vec3", "Number"] as const;
 
 export const ValueType = z.enum(ValueTypeOptions);
 
+/** How the value of a driver is combined with the value it is applied to */
+export const DriverBlend = z.enum(["Add", "Replace", "Multiply"]);
+
+/** Shape of one cycle of an oscillator */
+export const Waveform = z.enum(["Sine", "Triangle", "Square", "Sawtooth"]);
+
+/** Parsed type of a schema, or the type it accepts when Input is true */
+type Schema<T extends z.ZodTypeAny, Input extends boolean> = Input extends true
+  ? z.input<T>
+  : z.output<T>;
+
+type AnimatedNumberType<Input extends boolean> = {
+  keyframes: Schema<typeof Keyframes, Input>;
+  drivers?: Array<DriverType<Input>>;
+  type: "Number";
+};
+
+type DriverType<Input extends boolean> = {
+  blend?: z.infer<typeof DriverBlend>;
+  source:
+    | {
+        type: "Noise";
+        seed: number;
+        frequency: AnimatedNumberType<Input>;
+        amplitude: AnimatedNumberType<Input>;
+        octaves?: AnimatedNumberType<Input>;
+      }
+    | {
+        type: "Audio";
+        fps: Schema<typeof FrameRate, Input>;
+        values: Array<number>;
+        amplitude: AnimatedNumberType<Input>;
+        offset?: AnimatedNumberType<Input>;
+      }
+    | {
+        type: "Oscillator";
+        waveform: z.infer<typeof Waveform>;
+        frequency: AnimatedNumberType<Input>;
+        phase?: AnimatedNumberType<Input>;
+        amplitude: AnimatedNumberType<Input>;
+        offset?: AnimatedNumberType<Input>;
+      };
+};
+
+/** Smooth seeded random motion, the same seed always gives the same motion */
+export const NoiseDriver = z.object({
+  type: z.literal("Noise"),
+  seed: z.number().int().nonnegative(),
+  frequency: z.lazy(() => AnimatedNumber),
+  amplitude: z.lazy(() => AnimatedNumber),
+  octaves: z.lazy(() => AnimatedNumber).optional(),
+});
+
+/** Periodic motion, phase is given in cycles */
+export const OscillatorDriver = z.object({
+  type: z.literal("Oscillator"),
+  waveform: Waveform,
+  frequency: z.lazy(() => AnimatedNumber),
+  phase: z.lazy(() => AnimatedNumber).optional(),
+  amplitude: z.lazy(() => AnimatedNumber),
+  offset: z.lazy(() => AnimatedNumber).optional(),
+});
+
+/** Follows per frame loudness values, usually from an AudioAnalysis */
+export const AudioDriver = z.object({
+  type: z.literal("Audio"),
+  fps: FrameRate,
+  values: z.array(z.number()),
+  amplitude: z.lazy(() => AnimatedNumber),
+  offset: z.lazy(() => AnimatedNumber).optional(),
+});
+
+export const DriverSource = z.discriminatedUnion("type", [
+  NoiseDriver,
+  OscillatorDriver,
+  AudioDriver,
+]);
+
+// Annotated because drivers nest animated numbers, the input type differs from the output
+// where defaults are filled in
+export const Driver: z.ZodType<
+  DriverType<false>,
+  z.ZodTypeDef,
+  DriverType<true>
+> = z.object({
+  blend: DriverBlend.optional(),
+  source: DriverSource,
+});
+
 export const AnimatedNumber = z.object({
   keyframes: Keyframes,
+  /** Applied in order on top of the keyframed value */
+  drivers: z.array(z.lazy(() => Driver)).optional(),
   type: z.literal(ValueType.Enum.Number),
 });
 
 export const AnimatedVec2 = z.object({
   keyframes: z.array(AnimatedNumber).length(2),
+  /** Moves along a curved path instead of animating both axes independently */
+  motion_path: MotionPath.optional(),
   type: z.literal(ValueType.Enum.Vec2),
 });
 
@@ -26,6 +120,9 @@ export const AnimatedVec3 = z.object({
   type: z.literal(ValueType.Enum.Vec3),
 });
 
+/** How the angles of rotate are interpolated, Quaternion takes the shortest path */
+export const RotationMode = z.enum(["Euler", "Quaternion"]);
+
 export const AnimatedTransform = z.object({
   type: z.literal("Transform"),
   /** Translates by the given animated vec2 */
@@ -36,6 +133,9 @@ export const AnimatedTransform = z.object({
   rotate: AnimatedVec3,
   /** Scales on the x and y axis by the given animated vec2 */
   scale: AnimatedVec2,
+  /** Rotates along the direction of travel of translate */
+  auto_orient: z.boolean().optional(),
+  rotation_mode: RotationMode.optional(),
 });
 
 export const AnimatedValue = z.discriminatedUnion("type", [
@@ -1,6 +1,7 @@
 import { invoke } from "@tauri-apps/api";
 import { AnimatedEntities } from "primitives/AnimatedEntities";
 import { Entities, EntityType } from "primitives/Entities";
+import { parseCreatorError } from "primitives/Error";
 import { z } from "zod";
 
 function typedArrayToBuffer(array: Uint8Array): ArrayBuffer {
@@ -66,6 +67,8 @@ export class DependenciesService {
           const buffer = typedArrayToBuffer(u8);
           this.dependencies.fonts.set(fontName, buffer);
         }
+      }).catch((error) => {
+        console.error(`Failed to load font ${fontName}`, parseCreatorError(error) ?? error);
       });
     };
 
@@ -1,9 +1,16 @@
+import { invoke } from "@tauri-apps/api";
+import { AnimatedPathCommand } from "primitives/AnimatedEntities";
 import { EntityType } from "primitives/Entities";
 import { PaintStyleType, TextAlign } from "primitives/Paint";
 import { staticAnimatedTransform, staticAnimatedVec2 } from "primitives/Values";
 import { useEntitiesStore } from "stores/entities.store";
 import { useTimelineStore } from "stores/timeline.store";
 import { v4 as uuid } from "uuid";
+import { z } from "zod";
+
+/** Five pointed star centered on the origin */
+const DEFAULT_SVG_PATH =
+  "M0 -100 L29 -40 L95 -31 L47 15 L59 81 L0 50 L-59 81 L-47 15 L-95 -31 L-29 -40 Z";
 
 export class EntitiesService {
   get entitiesStore() {
@@ -66,6 +73,36 @@ export class EntitiesService {
     });
   }
 
+  /** Rejects with a CreatorError when the SVG path data can't be parsed */
+  async createPath(svgPath?: string) {
+    const data = await invoke("parse_svg_path", {
+      path: svgPath || DEFAULT_SVG_PATH,
+    });
+    const [width, height] = this.timelineStore.size;
+
+    return this.entitiesStore.createEntity({
+      type: EntityType.Enum.Path,
+      id: this.createUuid(),
+      cache: {},
+      paint: {
+        style: {
+          type: PaintStyleType.Enum.Fill,
+          color: {
+            value: [233, 100, 150, 1.0],
+          },
+        },
+      },
+      path: z.array(AnimatedPathCommand).parse(data),
+      origin: staticAnimatedVec2(0, 0),
+      position: staticAnimatedVec2(width / 2, height / 2),
+      transform: staticAnimatedTransform([0, 0], [1, 1], [0, 0, 0], [0, 0]),
+      animation_data: {
+        offset: 0,
+        duration: 3,
+      },
+    });
+  }
+
   createText(text?: string) {
     return this.entitiesStore.createEntity({
       type: EntityType.Enum.Text,
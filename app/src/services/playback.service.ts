@@ -1,5 +1,6 @@
 import { Drawer } from "drawers/draw";
 import { AnimatedEntities } from "primitives/AnimatedEntities";
+import { frameRateToNumber } from "primitives/FrameRate";
 import { useEntitiesStore } from "stores/entities.store";
 import { useRenderStateStore } from "stores/render-state.store";
 import { useTimelineStore } from "stores/timeline.store";
@@ -74,7 +75,7 @@ export class PlaybackService {
   }
 
   get fpsInterval() {
-    return 1000 / this.timelineStore.fps;
+    return 1000 / frameRateToNumber(this.timelineStore.fps);
   }
 
   get currFrame() {
@@ -82,7 +83,9 @@ export class PlaybackService {
   }
 
   get totalFrameCount() {
-    return this.timelineStore.fps * this.timelineStore.duration;
+    return (
+      frameRateToNumber(this.timelineStore.fps) * this.timelineStore.duration
+    );
   }
 
   playLoop(currentTime: number) {
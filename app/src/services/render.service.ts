@@ -2,6 +2,7 @@ import { Timeline } from "primitives/Timeline";
 // TODO: publish package maybe provide wrapper etc.
 import * as creatorWasm from "../../../lib/creator_rs/pkg";
 import { z } from "zod";
+import { parseCreatorError } from "primitives/Error";
 import { useTimelineStore } from "stores/timeline.store";
 import { useEntitiesStore } from "stores/entities.store";
 import { useRenderStateStore } from "stores/render-state.store";
@@ -25,8 +26,12 @@ export class RenderService {
 
         timeline = Timeline.parse(timeline);
 
-        const renderedEntities = creatorWasm.calculate_timeline_from_json_at_curr_frame(JSON.stringify(timeline));
+        try {
+            const renderedEntities = creatorWasm.calculate_timeline_from_json_at_curr_frame(JSON.stringify(timeline));
 
-        console.log(renderedEntities);
+            console.log(renderedEntities);
+        } catch (error) {
+            console.error("Failed to calculate timeline", parseCreatorError(error) ?? error);
+        }
     }
 }
\ No newline at end of file
@@ -1,7 +1,10 @@
 import { create } from "zustand";
+import { z } from "zod";
+import { FrameRate } from "primitives/FrameRate";
 
 interface TimelineStore {
-  fps: number;
+  /** A plain number or an exact fraction like 30000/1001 for NTSC rates */
+  fps: z.input<typeof FrameRate>;
   duration: number;
   size: [number, number];
 }
@@ -60,6 +60,21 @@ export function flattenedKeyframesByEntity(
       keyframes.push(...flattenAnimatedVec2Keyframes(entity.position));
       keyframes.push(...flattenAnimatedVec2Keyframes(entity.radius));
       break;
+    case "Path":
+      keyframes.push(...flattenAnimatedVec2Keyframes(entity.position));
+      entity.path.forEach((command) => {
+        if (command.type === "CubicTo") {
+          keyframes.push(...flattenAnimatedVec2Keyframes(command.control1));
+          keyframes.push(...flattenAnimatedVec2Keyframes(command.control2));
+        }
+        if (command.type === "QuadTo") {
+          keyframes.push(...flattenAnimatedVec2Keyframes(command.control));
+        }
+        if (command.type !== "Close") {
+          keyframes.push(...flattenAnimatedVec2Keyframes(command.point));
+        }
+      });
+      break;
     case "StaggeredText":
       keyframes.push(
         ...flattenAnimatedVec3Keyframes(entity.letter.transform.rotate)
@@ -0,0 +1,62 @@
+use creator_rs::animation::primitives::{
+    entities::common::AnimationData,
+    frame_rate::FrameRate,
+    interpolations::{EasingFunction, InterpolationType},
+    keyframe::{Keyframe, Keyframes},
+};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+fn build_keyframes(count: usize) -> Keyframes {
+    Keyframes::new(
+        (0..count)
+            .map(|index| {
+                Keyframe::new(
+                    (index % 7) as f32 * 10.0,
+                    index as f32 * 0.1,
+                    index.to_string().into(),
+                    Some(InterpolationType::EasingFunction(
+                        EasingFunction::CubicInOut,
+                    )),
+                )
+            })
+            .collect(),
+    )
+}
+
+fn get_value_at_frame(c: &mut Criterion) {
+    let animation_data = AnimationData {
+        offset: 0.0,
+        duration: 60.0,
+        visible: true,
+        ..Default::default()
+    };
+    let fps = FrameRate::new(60, 1).unwrap();
+
+    let mut group = c.benchmark_group("get_value_at_frame");
+
+    for count in [2, 10, 100, 500] {
+        let keyframes = build_keyframes(count);
+        let last_frame = count as i32 * 6;
+
+        group.bench_with_input(
+            BenchmarkId::from_parameter(count),
+            &keyframes,
+            |b, keyframes| {
+                b.iter(|| {
+                    for frame in (0..last_frame).step_by(7) {
+                        black_box(keyframes.get_value_at_frame(
+                            black_box(frame),
+                            &animation_data,
+                            fps,
+                        ));
+                    }
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, get_value_at_frame);
+criterion_main!(benches);
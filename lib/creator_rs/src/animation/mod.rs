@@ -1,2 +1,3 @@
 pub mod primitives;
 pub mod timeline;
+pub mod validation;
@@ -0,0 +1,373 @@
+use std::f32::consts::{PI, TAU};
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    drivers::{AudioDriver, Driver, DriverBlend, DriverSource},
+    frame_rate::FrameRate,
+};
+use crate::error::{CreatorError, CreatorResult};
+
+const WAVE_FORMAT_PCM: u16 = 1;
+const WAVE_FORMAT_IEEE_FLOAT: u16 = 3;
+const WAVE_FORMAT_EXTENSIBLE: u16 = 0xfffe;
+
+/// Decoded audio mixed down to a single channel, samples range from -1 to 1.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioClip {
+    pub sample_rate: u32,
+    pub samples: Vec<f32>,
+}
+
+/// Range of frequencies in hertz, the high end is exclusive.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct FrequencyBand {
+    pub low: f32,
+    pub high: f32,
+}
+
+/// Loudness of an audio clip at every frame, ready to be used by an audio driver.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AudioAnalysis {
+    pub fps: FrameRate,
+    /// RMS amplitude of every frame, 1 is the loudest frame of the clip.
+    pub envelope: Vec<f32>,
+    /// Energy of every frame for each requested band, 1 is the highest energy of all bands.
+    pub bands: Vec<Vec<f32>>,
+}
+
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub fn analyze_audio_file(
+    path: String,
+    fps: FrameRate,
+    bands: Vec<FrequencyBand>,
+) -> CreatorResult<AudioAnalysis> {
+    let bytes = std::fs::read(&path).map_err(|error| CreatorError::Io {
+        path,
+        message: error.to_string(),
+    })?;
+
+    AudioClip::from_wav(&bytes).map(|clip| clip.analyze(fps, &bands))
+}
+
+impl AudioClip {
+    /// Decodes 8, 16, 24 or 32 bit integer PCM and 32 or 64 bit float WAV files.
+    pub fn from_wav(bytes: &[u8]) -> CreatorResult<AudioClip> {
+        if bytes.get(0..4) != Some(b"RIFF") || bytes.get(8..12) != Some(b"WAVE") {
+            return Err(invalid_audio("Not a RIFF WAVE file"));
+        }
+
+        let mut format = None;
+        let mut data = None;
+        let mut position = 12;
+
+        while position + 8 <= bytes.len() {
+            let id = &bytes[position..position + 4];
+            let size = read_u32(bytes, position + 4)
+                .ok_or_else(|| invalid_audio("Truncated chunk header"))?
+                as usize;
+            let start = position + 8;
+            // Sizes near u32::MAX overflow usize on 32 bit targets like wasm
+            let end = start
+                .checked_add(size)
+                .ok_or_else(|| invalid_audio("Chunk size out of range"))?;
+            // Some encoders write a wrong size for the last chunk, it is cut to the file
+            let chunk = &bytes[start..end.min(bytes.len())];
+
+            match id {
+                b"fmt " => format = Some(WavFormat::parse(chunk)?),
+                b"data" => data = Some(chunk),
+                _ => {}
+            }
+
+            // Chunks are padded to an even size
+            position = end
+                .checked_add(size % 2)
+                .ok_or_else(|| invalid_audio("Chunk size out of range"))?;
+        }
+
+        let format = format.ok_or_else(|| invalid_audio("Missing fmt chunk"))?;
+        let data = data.ok_or_else(|| invalid_audio("Missing data chunk"))?;
+        let bytes_per_sample = (format.bits_per_sample / 8) as usize;
+        let block_size = bytes_per_sample * format.channels as usize;
+
+        let samples = data
+            .chunks_exact(block_size)
+            .map(|block| {
+                let sum = block
+                    .chunks_exact(bytes_per_sample)
+                    .map(|sample| format.decode(sample))
+                    .sum::<Option<f32>>()?;
+
+                Some(sum / format.channels as f32)
+            })
+            .collect::<Option<Vec<f32>>>()
+            .ok_or_else(|| invalid_audio("Malformed samples"))?;
+
+        Ok(AudioClip {
+            sample_rate: format.sample_rate,
+            samples,
+        })
+    }
+
+    /// Duration in seconds.
+    pub fn duration(&self) -> f32 {
+        self.samples.len() as f32 / self.sample_rate as f32
+    }
+
+    /// Samples that belong to the given frame.
+    fn frame_samples(&self, frame: usize, fps: FrameRate) -> &[f32] {
+        // f32 can't represent the sample indices of long clips exactly
+        let samples_per_frame = self.sample_rate as f64 / fps.as_f64();
+        let index = |frame: usize| {
+            ((frame as f64 * samples_per_frame) as u64).min(self.samples.len() as u64) as usize
+        };
+        let start = index(frame);
+        let end = index(frame + 1);
+
+        &self.samples[start..end]
+    }
+
+    pub fn analyze(&self, fps: FrameRate, bands: &[FrequencyBand]) -> AudioAnalysis {
+        let frame_count = (self.duration() * fps.as_f32()).ceil() as usize;
+
+        let envelope = normalize(
+            (0..frame_count)
+                .map(|frame| {
+                    let samples = self.frame_samples(frame, fps);
+
+                    if samples.is_empty() {
+                        return 0.0;
+                    }
+
+                    (samples.iter().map(|sample| sample * sample).sum::<f32>()
+                        / samples.len() as f32)
+                        .sqrt()
+                })
+                .collect(),
+        );
+
+        let mut band_energies = vec![Vec::with_capacity(frame_count); bands.len()];
+
+        if !bands.is_empty() {
+            for frame in 0..frame_count {
+                let spectrum = power_spectrum(self.frame_samples(frame, fps));
+                let bin_width = self.sample_rate as f32 / (spectrum.len() * 2) as f32;
+
+                for (band, energies) in bands.iter().zip(band_energies.iter_mut()) {
+                    let energy = spectrum
+                        .iter()
+                        .enumerate()
+                        .filter(|(bin, _)| {
+                            let frequency = *bin as f32 * bin_width;
+                            frequency >= band.low && frequency < band.high
+                        })
+                        .map(|(_, power)| power)
+                        .sum();
+
+                    energies.push(energy);
+                }
+            }
+        }
+
+        // Bands share one scale so quiet bands stay quiet next to loud ones
+        let peak = band_energies
+            .iter()
+            .flatten()
+            .fold(0.0_f32, |a, b| a.max(*b));
+
+        AudioAnalysis {
+            fps,
+            envelope,
+            bands: band_energies
+                .into_iter()
+                .map(|energies| scale(energies, peak))
+                .collect(),
+        }
+    }
+}
+
+impl AudioAnalysis {
+    /// Maps the envelope from 0 to the given amplitude, to be added onto a property.
+    pub fn envelope_driver(&self, amplitude: f32) -> Driver {
+        self.driver(self.envelope.clone(), amplitude)
+    }
+
+    /// Maps the energy of a band from 0 to the given amplitude, to be added onto a property.
+    pub fn band_driver(&self, band: usize, amplitude: f32) -> Option<Driver> {
+        Some(self.driver(self.bands.get(band)?.clone(), amplitude))
+    }
+
+    fn driver(&self, values: Vec<f32>, amplitude: f32) -> Driver {
+        Driver {
+            blend: DriverBlend::Add,
+            source: DriverSource::Audio(AudioDriver::new(self.fps, values, amplitude)),
+        }
+    }
+}
+
+struct WavFormat {
+    format: u16,
+    channels: u16,
+    sample_rate: u32,
+    bits_per_sample: u16,
+}
+
+impl WavFormat {
+    fn parse(chunk: &[u8]) -> CreatorResult<WavFormat> {
+        let truncated = || invalid_audio("Truncated fmt chunk");
+        let mut format = read_u16(chunk, 0).ok_or_else(truncated)?;
+
+        // The actual format is the start of the sub format GUID
+        if format == WAVE_FORMAT_EXTENSIBLE {
+            format = read_u16(chunk, 24).ok_or_else(truncated)?;
+        }
+
+        let format = WavFormat {
+            format,
+            channels: read_u16(chunk, 2).ok_or_else(truncated)?,
+            sample_rate: read_u32(chunk, 4).ok_or_else(truncated)?,
+            bits_per_sample: read_u16(chunk, 14).ok_or_else(truncated)?,
+        };
+
+        let supported = match format.format {
+            WAVE_FORMAT_PCM => matches!(format.bits_per_sample, 8 | 16 | 24 | 32),
+            WAVE_FORMAT_IEEE_FLOAT => matches!(format.bits_per_sample, 32 | 64),
+            _ => false,
+        };
+
+        if !supported {
+            return Err(invalid_audio(&format!(
+                "Unsupported format {} with {} bits per sample",
+                format.format, format.bits_per_sample
+            )));
+        }
+
+        if format.channels == 0 || format.sample_rate == 0 {
+            return Err(invalid_audio("No channels or a sample rate of 0"));
+        }
+
+        Ok(format)
+    }
+
+    fn decode(&self, sample: &[u8]) -> Option<f32> {
+        let value = match (self.format, self.bits_per_sample) {
+            // 8 bit samples are the only unsigned ones
+            (WAVE_FORMAT_PCM, 8) => (sample[0] as f32 - 128.0) / 128.0,
+            (WAVE_FORMAT_PCM, 16) => i16::from_le_bytes(sample.try_into().ok()?) as f32 / 32768.0,
+            (WAVE_FORMAT_PCM, 24) => {
+                // Shifts the sign bit into place and back
+                (i32::from_le_bytes([0, sample[0], sample[1], sample[2]]) >> 8) as f32 / 8388608.0
+            }
+            (WAVE_FORMAT_PCM, 32) => {
+                i32::from_le_bytes(sample.try_into().ok()?) as f32 / 2147483648.0
+            }
+            (WAVE_FORMAT_IEEE_FLOAT, 32) => f32::from_le_bytes(sample.try_into().ok()?),
+            (WAVE_FORMAT_IEEE_FLOAT, 64) => f64::from_le_bytes(sample.try_into().ok()?) as f32,
+            _ => return None,
+        };
+
+        Some(value)
+    }
+}
+
+fn invalid_audio(message: &str) -> CreatorError {
+    CreatorError::InvalidAudio {
+        message: message.to_string(),
+    }
+}
+
+fn read_u16(bytes: &[u8], position: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(
+        bytes.get(position..position + 2)?.try_into().ok()?,
+    ))
+}
+
+fn read_u32(bytes: &[u8], position: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        bytes.get(position..position + 4)?.try_into().ok()?,
+    ))
+}
+
+fn normalize(values: Vec<f32>) -> Vec<f32> {
+    let peak = values.iter().fold(0.0_f32, |a, b| a.max(*b));
+
+    scale(values, peak)
+}
+
+fn scale(values: Vec<f32>, peak: f32) -> Vec<f32> {
+    if peak <= 0.0 {
+        return values;
+    }
+
+    values.into_iter().map(|value| value / peak).collect()
+}
+
+/// Power of every frequency bin up to the Nyquist frequency, the samples are Hann windowed and
+/// zero padded to a power of two.
+fn power_spectrum(samples: &[f32]) -> Vec<f32> {
+    let size = samples.len().next_power_of_two().max(2);
+
+    let mut real: Vec<f32> = (0..size)
+        .map(|index| match samples.get(index) {
+            Some(sample) => {
+                let window = 0.5 - 0.5 * (TAU * index as f32 / samples.len() as f32).cos();
+                sample * window
+            }
+            None => 0.0,
+        })
+        .collect();
+    let mut imaginary = vec![0.0; size];
+
+    fft(&mut real, &mut imaginary);
+
+    real.iter()
+        .zip(imaginary.iter())
+        .take(size / 2)
+        .map(|(re, im)| re * re + im * im)
+        .collect()
+}
+
+/// In place iterative radix 2 FFT, the length has to be a power of two.
+fn fft(real: &mut [f32], imaginary: &mut [f32]) {
+    let size = real.len();
+
+    // Bit reversal permutation
+    let mut j = 0;
+    for i in 1..size {
+        let mut bit = size >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+
+        if i < j {
+            real.swap(i, j);
+            imaginary.swap(i, j);
+        }
+    }
+
+    let mut length = 2;
+    while length <= size {
+        let angle = -2.0 * PI / length as f32;
+
+        for start in (0..size).step_by(length) {
+            for k in 0..length / 2 {
+                let (sin, cos) = (angle * k as f32).sin_cos();
+                let even = start + k;
+                let odd = even + length / 2;
+
+                let odd_real = real[odd] * cos - imaginary[odd] * sin;
+                let odd_imaginary = real[odd] * sin + imaginary[odd] * cos;
+
+                real[odd] = real[even] - odd_real;
+                imaginary[odd] = imaginary[even] - odd_imaginary;
+                real[even] += odd_real;
+                imaginary[even] += odd_imaginary;
+            }
+        }
+
+        length <<= 1;
+    }
+}
@@ -0,0 +1,215 @@
+use uuid::Uuid;
+
+use super::{
+    entities::common::AnimationData,
+    frame_rate::FrameRate,
+    interpolations::{CubicBezier, InterpolationType},
+    keyframe::{Keyframe, Keyframes},
+    utils::frame_to_timestamp,
+};
+
+/// Values closer than this are treated as equal when normalizing a segment.
+const VALUE_EPSILON: f32 = 1e-6;
+
+/// Keyframes a segment is fitted through at most, longer segments are fitted through evenly
+/// spaced samples of their keyframes, which bounds the cost of simplifying long recordings.
+const MAX_FIT_SAMPLES: usize = 1024;
+
+impl Keyframes {
+    /// Samples the keyframes at every frame from `start_frame` up to but excluding `end_frame`,
+    /// like `get_values_at_frame_range`, and returns them as linear keyframes.
+    /// Any interpolation, springs included, is evaluated so the result can be used by tools
+    /// that only understand linear keyframes.
+    pub fn bake(
+        &self,
+        start_frame: i32,
+        end_frame: i32,
+        animation_data: &AnimationData,
+        fps: FrameRate,
+    ) -> Keyframes {
+        Keyframes::sample(start_frame, end_frame, animation_data, fps, |time| {
+            self.get_value_at_time(time, animation_data)
+        })
+    }
+
+    /// Builds linear keyframes from the value at every frame in the range, the end is exclusive.
+    pub fn sample(
+        start_frame: i32,
+        end_frame: i32,
+        animation_data: &AnimationData,
+        fps: FrameRate,
+        value_at_time: impl Fn(f32) -> f32,
+    ) -> Keyframes {
+        let values = (start_frame..end_frame)
+            .map(|frame| {
+                let time = frame_to_timestamp(frame as f32, fps);
+
+                Keyframe::new(
+                    value_at_time(time),
+                    time - animation_data.offset,
+                    Uuid::new_v4().to_string().into(),
+                    Some(InterpolationType::Linear),
+                )
+            })
+            .collect();
+
+        Keyframes::new(values)
+    }
+
+    /// Reduces dense keyframes to fewer linear and cubic bezier keyframes while no original
+    /// keyframe deviates more than `tolerance` from the simplified curve. Keyframes that are
+    /// kept retain their id.
+    ///
+    /// Every segment is greedily extended as far as it still fits, so the result is usually
+    /// but not always the fewest keyframes. The tolerance is only checked at the original
+    /// keyframes, the curve between them isn't sampled.
+    pub fn simplify(&self, tolerance: f32) -> Keyframes {
+        if self.values().len() <= 2 {
+            return self.clone();
+        }
+
+        let tolerance = tolerance.max(0.0);
+        let last_index = self.values().len() - 1;
+        let mut values = Vec::new();
+        let mut start = 0;
+
+        // Greedily extends every segment as far as it can be fitted within the tolerance. The
+        // search assumes shorter segments fit whenever a longer one does, which usually but not
+        // always holds for a least squares fit
+        while start < last_index {
+            let fits = |end: usize| self.fit_within(start, end, tolerance).is_some();
+
+            let mut fitting_end = start + 1;
+            let mut failing_end = start + 2;
+
+            while failing_end <= last_index && fits(failing_end) {
+                fitting_end = failing_end;
+                failing_end = start + (failing_end - start) * 2;
+            }
+
+            failing_end = failing_end.min(last_index + 1);
+
+            while failing_end - fitting_end > 1 {
+                let end = (fitting_end + failing_end) / 2;
+
+                if fits(end) {
+                    fitting_end = end;
+                } else {
+                    failing_end = end;
+                }
+            }
+
+            let mut keyframe = self.values()[start].clone();
+
+            // Temporal eases depend on the neighbouring keyframe, so every segment gets an
+            // interpolation of its own
+            keyframe.interpolation = if fitting_end - start > 1 {
+                self.fit_within(start, fitting_end, tolerance)
+            } else {
+                Some(keyframe.segment_interpolation(&self.values()[fitting_end]))
+            };
+            keyframe.temporal_ease = None;
+
+            values.push(keyframe);
+            start = fitting_end;
+        }
+
+        let mut last_keyframe = self.values()[last_index].clone();
+        last_keyframe.temporal_ease = None;
+        values.push(last_keyframe);
+
+        Keyframes::new(values).with_extrapolation(self.pre_extrapolation, self.post_extrapolation)
+    }
+
+    /// Interpolation from `start` to `end` that passes all keyframes in between within the tolerance.
+    fn fit_within(&self, start: usize, end: usize, tolerance: f32) -> Option<InterpolationType> {
+        let segment = &self.values()[start..=end];
+
+        fit_segment(segment).filter(|interpolation| max_error(segment, *interpolation) <= tolerance)
+    }
+}
+
+/// Fits a single interpolation through the keyframes, either linear or a cubic bezier whose
+/// x handles are fixed at a third of the segment so the fit stays linear in the y handles.
+fn fit_segment(keyframes: &[Keyframe]) -> Option<InterpolationType> {
+    if keyframes.len() > MAX_FIT_SAMPLES {
+        let step = (keyframes.len() - 1) as f64 / (MAX_FIT_SAMPLES - 1) as f64;
+        let samples: Vec<Keyframe> = (0..MAX_FIT_SAMPLES)
+            .map(|index| keyframes[(index as f64 * step).round() as usize].clone())
+            .collect();
+
+        return fit_segment(&samples);
+    }
+
+    let first = &keyframes[0];
+    let last = &keyframes[keyframes.len() - 1];
+    let value_range = last.value - first.value;
+    let duration = last.offset - first.offset;
+
+    if duration <= 0.0 {
+        return None;
+    }
+
+    if value_range.abs() <= VALUE_EPSILON {
+        return Some(InterpolationType::Linear);
+    }
+
+    // Least squares for y(t) = b1(t) * y1 + b2(t) * y2 + t^3
+    let (mut a11, mut a12, mut a22, mut r1, mut r2) = (0.0f64, 0.0f64, 0.0f64, 0.0f64, 0.0f64);
+
+    for keyframe in &keyframes[1..keyframes.len() - 1] {
+        let t = ((keyframe.offset - first.offset) / duration) as f64;
+        let y = ((keyframe.value - first.value) / value_range) as f64;
+        let mt = 1.0 - t;
+        let b1 = 3.0 * mt * mt * t;
+        let b2 = 3.0 * mt * t * t;
+        let residual = y - t * t * t;
+
+        a11 += b1 * b1;
+        a12 += b1 * b2;
+        a22 += b2 * b2;
+        r1 += b1 * residual;
+        r2 += b2 * residual;
+    }
+
+    let determinant = a11 * a22 - a12 * a12;
+
+    let y1 = (r1 * a22 - r2 * a12) / determinant;
+    let y2 = (a11 * r2 - a12 * r1) / determinant;
+
+    if determinant.abs() <= f64::EPSILON || !y1.is_finite() || !y2.is_finite() {
+        return Some(InterpolationType::Linear);
+    }
+
+    let linear_error = max_error(keyframes, InterpolationType::Linear);
+    let bezier = InterpolationType::CubicBezier(CubicBezier::new(
+        1.0 / 3.0,
+        y1 as f32,
+        2.0 / 3.0,
+        y2 as f32,
+    ));
+
+    if linear_error <= max_error(keyframes, bezier) {
+        Some(InterpolationType::Linear)
+    } else {
+        Some(bezier)
+    }
+}
+
+/// Largest distance between the keyframes and a single interpolation between the outer ones.
+fn max_error(keyframes: &[Keyframe], interpolation: InterpolationType) -> f32 {
+    let first = &keyframes[0];
+    let last = &keyframes[keyframes.len() - 1];
+    let duration = last.offset - first.offset;
+
+    keyframes[1..keyframes.len() - 1]
+        .iter()
+        .map(|keyframe| {
+            let elapsed = keyframe.offset - first.offset;
+            let eased = interpolation.ease(elapsed / duration, elapsed);
+            let value = first.value + (last.value - first.value) * eased;
+
+            (value - keyframe.value).abs()
+        })
+        .fold(0.0, f32::max)
+}
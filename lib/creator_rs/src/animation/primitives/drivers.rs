@@ -0,0 +1,478 @@
+use std::{f32::consts::TAU, sync::OnceLock};
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    entities::common::AnimationData,
+    frame_rate::FrameRate,
+    values::animated_values::{AnimatedFloat, AnimatedValue},
+};
+
+/// Octaves above this are ignored, their detail is far below a frame at typical frequencies.
+const MAX_OCTAVES: u32 = 8;
+
+/// Weight of every octave relative to the previous one.
+const OCTAVE_PERSISTENCE: f32 = 0.5;
+
+/// Frequency of every octave relative to the previous one.
+const OCTAVE_LACUNARITY: f32 = 2.0;
+
+/// Longest step in seconds used when integrating an animated frequency.
+const MAX_INTEGRATION_STEP: f32 = 0.25;
+
+/// Computes a value procedurally and combines it with the keyframed value of an [`AnimatedFloat`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Driver {
+    #[serde(default)]
+    pub blend: DriverBlend,
+    pub source: DriverSource,
+}
+
+/// How the value of a driver is combined with the value it is applied to.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+pub enum DriverBlend {
+    /// Adds the value of the driver.
+    #[default]
+    Add,
+    /// Uses the value of the driver instead.
+    Replace,
+    /// Scales the value by the value of the driver.
+    Multiply,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type")]
+pub enum DriverSource {
+    Noise(NoiseDriver),
+    Oscillator(OscillatorDriver),
+    Audio(AudioDriver),
+}
+
+/// Smooth, band-limited random motion like the wiggle expression, for camera shake or organic
+/// jitter. The same seed always gives the same motion, on every platform.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NoiseDriver {
+    pub seed: u32,
+    /// Wiggles per second.
+    pub frequency: AnimatedFloat,
+    /// Largest distance from the value the noise can move to.
+    pub amplitude: AnimatedFloat,
+    /// Layers of finer noise, fractional octaves fade the last layer in.
+    #[serde(default = "default_octaves")]
+    pub octaves: AnimatedFloat,
+    #[serde(skip)]
+    phase_integral: PhaseIntegral,
+}
+
+fn default_octaves() -> AnimatedFloat {
+    AnimatedFloat::new(1.0)
+}
+
+/// Periodic motion for pulsing, breathing or spinning elements.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OscillatorDriver {
+    pub waveform: Waveform,
+    /// Cycles per second.
+    pub frequency: AnimatedFloat,
+    /// Shifts the wave in cycles, 0.25 starts a quarter of a cycle later.
+    #[serde(default = "default_zero")]
+    pub phase: AnimatedFloat,
+    /// Largest distance from the offset the wave moves to.
+    pub amplitude: AnimatedFloat,
+    /// Value the wave oscillates around.
+    #[serde(default = "default_zero")]
+    pub offset: AnimatedFloat,
+    #[serde(skip)]
+    phase_integral: PhaseIntegral,
+}
+
+/// Phase of an animated frequency at each of its keyframes, so sampling only integrates from the
+/// closest keyframe. Filled on first use and reset whenever the keyframes are sorted.
+#[derive(Debug, Clone, Default)]
+struct PhaseIntegral(OnceLock<Vec<f32>>);
+
+/// Derived from the frequency, so it never makes two drivers different.
+impl PartialEq for PhaseIntegral {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+/// Shape of one cycle of an oscillator. Every waveform starts at 0, or at its peak for
+/// square, and rises first so they can be swapped for each other.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum Waveform {
+    Sine,
+    Triangle,
+    Square,
+    Sawtooth,
+}
+
+/// Follows the loudness of an audio clip, such as the envelope or a band of an
+/// [`AudioAnalysis`](super::audio::AudioAnalysis). The audio starts with the entity and is silent outside of the clip.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AudioDriver {
+    /// Frame rate the values were analyzed at.
+    pub fps: FrameRate,
+    /// Loudness at every frame, usually from 0 to 1.
+    pub values: Vec<f32>,
+    /// Value the loudest frame maps to.
+    pub amplitude: AnimatedFloat,
+    /// Value silence maps to.
+    #[serde(default = "default_zero")]
+    pub offset: AnimatedFloat,
+}
+
+fn default_zero() -> AnimatedFloat {
+    AnimatedFloat::new(0.0)
+}
+
+impl Driver {
+    pub fn sort_keyframes(&mut self) {
+        self.source.sort_keyframes();
+    }
+
+    pub fn quantize_keyframes(&mut self, quantize: &dyn Fn(f32) -> f32) {
+        self.source.quantize_keyframes(quantize);
+    }
+
+    pub fn apply(&self, value: f32, time: f32, animation_data: &AnimationData) -> f32 {
+        let driver_value = self.source.get_value_at_time(time, animation_data);
+
+        match self.blend {
+            DriverBlend::Add => value + driver_value,
+            DriverBlend::Replace => driver_value,
+            DriverBlend::Multiply => value * driver_value,
+        }
+    }
+}
+
+impl DriverSource {
+    pub fn sort_keyframes(&mut self) {
+        match self {
+            DriverSource::Noise(noise) => noise.sort_keyframes(),
+            DriverSource::Oscillator(oscillator) => oscillator.sort_keyframes(),
+            DriverSource::Audio(audio) => audio.sort_keyframes(),
+        }
+    }
+
+    pub fn quantize_keyframes(&mut self, quantize: &dyn Fn(f32) -> f32) {
+        match self {
+            DriverSource::Noise(noise) => noise.quantize_keyframes(quantize),
+            DriverSource::Oscillator(oscillator) => oscillator.quantize_keyframes(quantize),
+            DriverSource::Audio(audio) => audio.quantize_keyframes(quantize),
+        }
+    }
+
+    pub fn get_value_at_time(&self, time: f32, animation_data: &AnimationData) -> f32 {
+        match self {
+            DriverSource::Noise(noise) => noise.get_value_at_time(time, animation_data),
+            DriverSource::Oscillator(oscillator) => {
+                oscillator.get_value_at_time(time, animation_data)
+            }
+            DriverSource::Audio(audio) => audio.get_value_at_time(time, animation_data),
+        }
+    }
+}
+
+impl NoiseDriver {
+    pub fn new(seed: u32, frequency: f32, amplitude: f32, octaves: f32) -> Self {
+        NoiseDriver {
+            seed,
+            frequency: AnimatedFloat::new(frequency),
+            amplitude: AnimatedFloat::new(amplitude),
+            octaves: AnimatedFloat::new(octaves),
+            phase_integral: PhaseIntegral::default(),
+        }
+    }
+
+    pub fn sort_keyframes(&mut self) {
+        self.phase_integral = PhaseIntegral::default();
+        self.frequency.sort_keyframes();
+        self.amplitude.sort_keyframes();
+        self.octaves.sort_keyframes();
+    }
+
+    pub fn quantize_keyframes(&mut self, quantize: &dyn Fn(f32) -> f32) {
+        self.phase_integral = PhaseIntegral::default();
+        self.frequency.quantize_keyframes(quantize);
+        self.amplitude.quantize_keyframes(quantize);
+        self.octaves.quantize_keyframes(quantize);
+    }
+
+    pub fn get_value_at_time(&self, time: f32, animation_data: &AnimationData) -> f32 {
+        let phase = self
+            .phase_integral
+            .phase_at_time(&self.frequency, time, animation_data);
+        let octaves = self.octaves.get_value_at_time(time, animation_data);
+
+        self.amplitude.get_value_at_time(time, animation_data)
+            * fractal_noise(self.seed, phase, octaves)
+    }
+}
+
+impl OscillatorDriver {
+    pub fn new(waveform: Waveform, frequency: f32, amplitude: f32) -> Self {
+        OscillatorDriver {
+            waveform,
+            frequency: AnimatedFloat::new(frequency),
+            phase: default_zero(),
+            amplitude: AnimatedFloat::new(amplitude),
+            offset: default_zero(),
+            phase_integral: PhaseIntegral::default(),
+        }
+    }
+
+    pub fn sort_keyframes(&mut self) {
+        self.phase_integral = PhaseIntegral::default();
+        self.frequency.sort_keyframes();
+        self.phase.sort_keyframes();
+        self.amplitude.sort_keyframes();
+        self.offset.sort_keyframes();
+    }
+
+    pub fn quantize_keyframes(&mut self, quantize: &dyn Fn(f32) -> f32) {
+        self.phase_integral = PhaseIntegral::default();
+        self.frequency.quantize_keyframes(quantize);
+        self.phase.quantize_keyframes(quantize);
+        self.amplitude.quantize_keyframes(quantize);
+        self.offset.quantize_keyframes(quantize);
+    }
+
+    pub fn get_value_at_time(&self, time: f32, animation_data: &AnimationData) -> f32 {
+        let cycle = self
+            .phase_integral
+            .phase_at_time(&self.frequency, time, animation_data)
+            + self.phase.get_value_at_time(time, animation_data);
+
+        self.offset.get_value_at_time(time, animation_data)
+            + self.amplitude.get_value_at_time(time, animation_data) * self.waveform.sample(cycle)
+    }
+}
+
+impl Waveform {
+    /// Value from -1 to 1 at the given number of cycles.
+    pub fn sample(&self, cycle: f32) -> f32 {
+        let t = cycle.rem_euclid(1.0);
+
+        match self {
+            Waveform::Sine => (t * TAU).sin(),
+            Waveform::Triangle => {
+                if t < 0.25 {
+                    t * 4.0
+                } else if t < 0.75 {
+                    2.0 - t * 4.0
+                } else {
+                    t * 4.0 - 4.0
+                }
+            }
+            Waveform::Square => {
+                if t < 0.5 {
+                    1.0
+                } else {
+                    -1.0
+                }
+            }
+            Waveform::Sawtooth => (t + 0.5).rem_euclid(1.0) * 2.0 - 1.0,
+        }
+    }
+}
+
+impl AudioDriver {
+    pub fn new(fps: FrameRate, values: Vec<f32>, amplitude: f32) -> Self {
+        AudioDriver {
+            fps,
+            values,
+            amplitude: AnimatedFloat::new(amplitude),
+            offset: default_zero(),
+        }
+    }
+
+    pub fn sort_keyframes(&mut self) {
+        self.amplitude.sort_keyframes();
+        self.offset.sort_keyframes();
+    }
+
+    pub fn quantize_keyframes(&mut self, quantize: &dyn Fn(f32) -> f32) {
+        self.amplitude.quantize_keyframes(quantize);
+        self.offset.quantize_keyframes(quantize);
+    }
+
+    /// Loudness at the given time, linearly interpolated between frames.
+    pub fn level_at_time(&self, time: f32, animation_data: &AnimationData) -> f32 {
+        let frame = (time - animation_data.offset) * self.fps.as_f32();
+
+        if frame < 0.0 {
+            return 0.0;
+        }
+
+        let index = frame.floor() as usize;
+        let t = frame.fract();
+
+        match (self.values.get(index), self.values.get(index + 1)) {
+            (Some(from), Some(to)) => from + (to - from) * t,
+            (Some(from), None) => *from,
+            _ => 0.0,
+        }
+    }
+
+    pub fn get_value_at_time(&self, time: f32, animation_data: &AnimationData) -> f32 {
+        let offset = self.offset.get_value_at_time(time, animation_data);
+        let amplitude = self.amplitude.get_value_at_time(time, animation_data);
+
+        offset + (amplitude - offset) * self.level_at_time(time, animation_data)
+    }
+}
+
+impl PhaseIntegral {
+    /// Cycles completed since the start of the entity, the frequency is integrated so animating
+    /// it changes the speed smoothly instead of jumping through the noise.
+    fn phase_at_time(
+        &self,
+        frequency: &AnimatedFloat,
+        time: f32,
+        animation_data: &AnimationData,
+    ) -> f32 {
+        let start = animation_data.offset;
+        let keyframes = &frequency.keyframes.values();
+
+        if keyframes.len() <= 1 && frequency.drivers.is_empty() {
+            return (time - start) * frequency.get_value_at_time(time, animation_data);
+        }
+
+        let integrate = |from: f32, to: f32| integrate(frequency, from, to, animation_data);
+
+        if keyframes.is_empty() {
+            return integrate(start, time);
+        }
+
+        let phases = self.0.get_or_init(|| {
+            let mut phase = 0.0;
+            let mut previous_time = start;
+
+            keyframes
+                .iter()
+                .map(|keyframe| {
+                    let keyframe_time = start + keyframe.offset;
+                    phase += integrate(previous_time, keyframe_time);
+                    previous_time = keyframe_time;
+                    phase
+                })
+                .collect()
+        });
+
+        // Integrates from the closest keyframe at or before the time, or the first keyframe
+        let index = keyframes
+            .partition_point(|keyframe| start + keyframe.offset <= time)
+            .saturating_sub(1);
+        let keyframe_time = start + keyframes[index].offset;
+
+        phases[index] + integrate(keyframe_time, time)
+    }
+}
+
+/// Integral of the frequency from `from` to `to`, negative when `to` comes first. Piecewise
+/// between the keyframes, where the frequency curve is smooth.
+fn integrate(frequency: &AnimatedFloat, from: f32, to: f32, animation_data: &AnimationData) -> f32 {
+    let (from, to, sign) = if from <= to {
+        (from, to, 1.0)
+    } else {
+        (to, from, -1.0)
+    };
+
+    let keyframe_times = frequency
+        .keyframes
+        .values()
+        .iter()
+        .map(|keyframe| animation_data.offset + keyframe.offset)
+        .filter(|keyframe_time| *keyframe_time > from && *keyframe_time < to);
+
+    let points: Vec<f32> = std::iter::once(from)
+        .chain(keyframe_times)
+        .chain(std::iter::once(to))
+        .collect();
+
+    let frequency_at = |time: f32| frequency.get_value_at_time(time, animation_data);
+
+    let phase: f32 = points
+        .windows(2)
+        .map(|piece| {
+            let steps = ((piece[1] - piece[0]) / MAX_INTEGRATION_STEP)
+                .ceil()
+                .max(1.0);
+            let step = (piece[1] - piece[0]) / steps;
+
+            (0..steps as usize)
+                .map(|index| {
+                    let a = piece[0] + step * index as f32;
+                    let b = a + step;
+
+                    // Simpson's rule
+                    step / 6.0
+                        * (frequency_at(a) + 4.0 * frequency_at((a + b) / 2.0) + frequency_at(b))
+                })
+                .sum::<f32>()
+        })
+        .sum();
+
+    phase * sign
+}
+
+/// Sum of octaves of [`gradient_noise`], normalized to -1 to 1. Every octave is shifted by a
+/// seeded fraction of a cell, so the sum isn't 0 at whole phases.
+pub fn fractal_noise(seed: u32, phase: f32, octaves: f32) -> f32 {
+    let octaves = octaves.clamp(1.0, MAX_OCTAVES as f32);
+
+    let mut sum = 0.0;
+    let mut total_weight = 0.0;
+    let mut weight = 1.0;
+    let mut frequency = 1.0;
+
+    for octave in 0..octaves.ceil() as u32 {
+        let coverage = (octaves - octave as f32).min(1.0);
+        let octave_seed = hash(seed.wrapping_add(octave));
+        // The upper 24 bits convert to f32 exactly
+        let shift = (hash(octave_seed) >> 8) as f32 / (1 << 24) as f32;
+
+        sum += gradient_noise(octave_seed, phase * frequency + shift) * weight * coverage;
+        total_weight += weight * coverage;
+
+        weight *= OCTAVE_PERSISTENCE;
+        frequency *= OCTAVE_LACUNARITY;
+    }
+
+    sum / total_weight
+}
+
+/// One dimensional Perlin noise from -1 to 1 that is 0 at every integer. Only uses operations
+/// that are exactly specified by IEEE 754, so native and wasm builds give identical results.
+pub fn gradient_noise(seed: u32, x: f32) -> f32 {
+    let cell = x.floor();
+    let t = x - cell;
+    let index = cell as i32;
+
+    let from = gradient(seed, index) * t;
+    let to = gradient(seed, index.wrapping_add(1)) * (t - 1.0);
+    let fade = t * t * t * (t * (t * 6.0 - 15.0) + 10.0);
+
+    // A single octave peaks at 0.5
+    (from + (to - from) * fade) * 2.0
+}
+
+/// Slope from -1 to 1 at the given lattice point.
+fn gradient(seed: u32, index: i32) -> f32 {
+    let hash = hash(seed ^ hash(index as u32));
+
+    // The upper 24 bits convert to f32 exactly
+    (hash >> 8) as f32 / (1 << 23) as f32 - 1.0
+}
+
+/// Integer hash with a good avalanche, from Chris Wellons' hash prospector.
+fn hash(mut x: u32) -> u32 {
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x7feb_352d);
+    x ^= x >> 15;
+    x = x.wrapping_mul(0x846c_a68b);
+    x ^= x >> 16;
+    x
+}
@@ -1,9 +1,17 @@
 use serde::{Deserialize, Serialize};
 
-use crate::animation::{primitives::utils::timestamp_to_frame, timeline::Timeline};
+use crate::animation::{
+    primitives::{
+        frame_rate::FrameRate,
+        utils::{frame_to_timestamp, is_same_time},
+        values::animated_values::{AnimatedFloat, AnimatedValue},
+    },
+    timeline::Timeline,
+};
 
 use super::{
     ellipse::{AnimatedEllipseEntity, EllipseEntity},
+    path::{AnimatedPathEntity, PathEntity},
     rect::{AnimatedRectEntity, RectEntity},
     staggered_text::{AnimatedStaggeredTextEntity, StaggeredTextEntity},
     text::{AnimatedTextEntity, TextEntity},
@@ -11,30 +19,50 @@ use super::{
 
 pub trait Drawable {
     fn should_draw(&self, animation_data: &AnimationData, timeline: &Timeline) -> bool {
-        let start_frame = timestamp_to_frame(animation_data.offset, timeline.fps);
-        let end_frame = timestamp_to_frame(
-            animation_data.offset + animation_data.duration,
-            timeline.fps,
-        );
-
-        // println!("start {0} end {1}", start_frame, end_frame);
-
-        let is_before = timeline.render_state.curr_frame < start_frame;
-        let is_after = timeline.render_state.curr_frame > end_frame;
-        let is_between = !is_after && !is_before;
-
-        if is_between {
-            return true;
-        } else {
-            return false;
-        }
+        self.should_draw_at_time(
+            animation_data,
+            frame_to_timestamp(timeline.render_state.curr_frame as f32, timeline.fps),
+        )
+    }
+
+    fn should_draw_at_time(&self, animation_data: &AnimationData, time: f32) -> bool {
+        let start = animation_data.offset;
+        let end = animation_data.offset + animation_data.duration;
+
+        let is_before = time < start && !is_same_time(time, start);
+        let is_after = time > end && !is_same_time(time, end);
+
+        !is_after && !is_before
     }
 }
 
 pub trait Animateable {
     fn sort_keyframes(&mut self);
 
-    fn calculate(&mut self, timeline: &Timeline) -> Option<Entity>;
+    /// Snaps the entity and all of its keyframes to the frames of the given frame rate.
+    fn quantize(&mut self, fps: FrameRate);
+
+    fn calculate(&self, timeline: &Timeline) -> Option<Entity> {
+        self.calculate_at_time(
+            timeline,
+            frame_to_timestamp(timeline.render_state.curr_frame as f32, timeline.fps),
+        )
+    }
+
+    /// Calculates the entity at the given time in seconds, which doesn't have to fall on a frame.
+    fn calculate_at_time(&self, timeline: &Timeline, time: f32) -> Option<Entity>;
+}
+
+/// Entities without an opacity are fully opaque.
+pub fn default_opacity() -> AnimatedFloat {
+    AnimatedFloat::new(1.0)
+}
+
+/// Opacity of the whole entity at the given time, clamped to 0..1.
+pub fn opacity_at_time(opacity: &AnimatedFloat, time: f32, animation_data: &AnimationData) -> f32 {
+    opacity
+        .get_value_at_time(time, animation_data)
+        .clamp(0.0, 1.0)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -42,6 +70,8 @@ pub struct Cache {
     pub valid: bool,
 }
 
+// Entities are built once per timeline and cloned rarely, boxing them isn't worth the indirection
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum AnimatedEntity {
@@ -49,6 +79,7 @@ pub enum AnimatedEntity {
     StaggeredText(AnimatedStaggeredTextEntity),
     Ellipse(AnimatedEllipseEntity),
     Rect(AnimatedRectEntity),
+    Path(AnimatedPathEntity),
 }
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
@@ -57,15 +88,39 @@ pub enum Entity {
     StaggeredText(StaggeredTextEntity),
     Ellipse(EllipseEntity),
     Rect(RectEntity),
+    Path(PathEntity),
 }
 
 impl AnimatedEntity {
-    pub fn calculate(&mut self, timeline: &Timeline) -> Option<Entity> {
+    pub fn calculate(&self, timeline: &Timeline) -> Option<Entity> {
         match self {
             Self::Text(text_entity) => text_entity.calculate(timeline),
             Self::Rect(box_entity) => box_entity.calculate(timeline),
             Self::StaggeredText(staggered_text_entity) => staggered_text_entity.calculate(timeline),
             Self::Ellipse(ellipse_entity) => ellipse_entity.calculate(timeline),
+            Self::Path(path_entity) => path_entity.calculate(timeline),
+        }
+    }
+
+    pub fn calculate_at_time(&self, timeline: &Timeline, time: f32) -> Option<Entity> {
+        match self {
+            Self::Text(text_entity) => text_entity.calculate_at_time(timeline, time),
+            Self::Rect(box_entity) => box_entity.calculate_at_time(timeline, time),
+            Self::StaggeredText(staggered_text_entity) => {
+                staggered_text_entity.calculate_at_time(timeline, time)
+            }
+            Self::Ellipse(ellipse_entity) => ellipse_entity.calculate_at_time(timeline, time),
+            Self::Path(path_entity) => path_entity.calculate_at_time(timeline, time),
+        }
+    }
+
+    pub fn quantize(&mut self, fps: FrameRate) {
+        match self {
+            Self::Text(text_entity) => text_entity.quantize(fps),
+            Self::Rect(box_entity) => box_entity.quantize(fps),
+            Self::StaggeredText(staggered_text_entity) => staggered_text_entity.quantize(fps),
+            Self::Ellipse(ellipse_entity) => ellipse_entity.quantize(fps),
+            Self::Path(path_entity) => path_entity.quantize(fps),
         }
     }
 }
@@ -75,4 +130,69 @@ pub struct AnimationData {
     pub offset: f32,
     pub duration: f32,
     pub visible: bool,
+    /// Playback rate of the entity, 0.5 plays at half speed and 0 freezes it.
+    #[serde(default = "default_speed")]
+    pub speed: f32,
+    /// Plays the entity backwards from the end of its duration.
+    #[serde(default)]
+    pub reverse: bool,
+    /// Maps the seconds since `offset` to the seconds since `offset` the properties are evaluated at.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_remap: Option<AnimatedFloat>,
+}
+
+fn default_speed() -> f32 {
+    1.0
+}
+
+impl Default for AnimationData {
+    fn default() -> Self {
+        AnimationData {
+            offset: 0.0,
+            duration: 0.0,
+            visible: true,
+            speed: default_speed(),
+            reverse: false,
+            time_remap: None,
+        }
+    }
+}
+
+impl AnimationData {
+    /// Time the properties of the entity are evaluated at for the given timeline time. The local
+    /// time is reversed first, then remapped and then scaled by the speed.
+    pub fn source_time(&self, time: f32) -> f32 {
+        let mut local_time = time - self.offset;
+
+        if self.reverse {
+            local_time = self.duration - local_time;
+        }
+
+        if let Some(time_remap) = &self.time_remap {
+            local_time = time_remap.get_value_at_time(local_time, &AnimationData::default());
+        }
+
+        self.offset + local_time * self.speed
+    }
+
+    /// Moves the start and end of the entity to the nearest frame. Returns how keyframe offsets
+    /// have to change so every keyframe lands on the frame nearest to where it was.
+    pub fn quantize(&mut self, fps: FrameRate) -> impl Fn(f32) -> f32 {
+        let offset = self.offset;
+        let end = fps.quantize(self.offset + self.duration);
+
+        self.offset = fps.quantize(self.offset);
+        self.duration = end - self.offset;
+
+        let quantized_offset = self.offset;
+        let quantize =
+            move |keyframe_offset: f32| fps.quantize(offset + keyframe_offset) - quantized_offset;
+
+        // Remapped time starts at the offset of the entity, just like keyframe offsets
+        if let Some(time_remap) = &mut self.time_remap {
+            time_remap.quantize_keyframes(&quantize);
+        }
+
+        quantize
+    }
 }
@@ -2,18 +2,25 @@ use serde::{Deserialize, Serialize};
 
 use crate::animation::{
     primitives::{
-        paint::Paint,
+        frame_rate::FrameRate,
+        paint::{AnimatedPaint, Paint},
         transform::{AnimatedTransform, Transform},
-        values::animated_values::{AnimatedFloatVec2, AnimatedValue},
+        values::animated_values::{AnimatedFloat, AnimatedFloatVec2, AnimatedValue},
     },
     timeline::Timeline,
 };
 
-use super::common::{Animateable, AnimationData, Cache, Drawable, Entity};
+use super::common::{
+    default_opacity, opacity_at_time, Animateable, AnimationData, Cache, Drawable, Entity,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnimatedEllipseEntity {
-    pub paint: Paint,
+    pub paint: AnimatedPaint,
+    /// Opacity of the whole entity from 0 to 1.
+    #[serde(default = "default_opacity")]
+    pub opacity: AnimatedFloat,
+
     pub id: String,
     pub cache: Cache,
     pub radius: AnimatedFloatVec2,
@@ -31,39 +38,28 @@ pub struct EllipseEntity {
     pub position: (f32, f32),
     pub origin: (f32, f32),
     pub paint: Paint,
+    pub opacity: f32,
     pub transform: Option<Transform>,
 }
 
 impl Drawable for AnimatedEllipseEntity {}
 impl Animateable for AnimatedEllipseEntity {
-    fn calculate(&mut self, timeline: &Timeline) -> Option<Entity> {
-        let should_draw = self.should_draw(&self.animation_data, timeline);
+    fn calculate_at_time(&self, _timeline: &Timeline, time: f32) -> Option<Entity> {
+        let should_draw = self.should_draw_at_time(&self.animation_data, time);
 
         if should_draw {
-            self.sort_keyframes();
-
-            let radius = self.radius.get_value_at_frame(
-                timeline.render_state.curr_frame,
-                &self.animation_data,
-                timeline.fps,
-            );
-
-            let position = self.position.get_value_at_frame(
-                timeline.render_state.curr_frame,
-                &self.animation_data,
-                timeline.fps,
-            );
-
-            let origin = self.origin.get_value_at_frame(
-                timeline.render_state.curr_frame,
-                &self.animation_data,
-                timeline.fps,
-            );
-
-            let transform: Option<Transform> = match self.transform.clone() {
-                Some(mut val) => Some(val.calculate(timeline, &self.animation_data)),
-                None => None,
-            };
+            let time = self.animation_data.source_time(time);
+
+            let radius = self.radius.get_value_at_time(time, &self.animation_data);
+
+            let position = self.position.get_value_at_time(time, &self.animation_data);
+
+            let origin = self.origin.get_value_at_time(time, &self.animation_data);
+
+            let transform: Option<Transform> = self
+                .transform
+                .as_ref()
+                .map(|val| val.calculate_at_time(time, &self.animation_data));
 
             Some(Entity::Ellipse(EllipseEntity {
                 id: self.id.clone(),
@@ -71,7 +67,8 @@ impl Animateable for AnimatedEllipseEntity {
                 position,
                 origin,
                 cache: self.cache.clone(),
-                paint: self.paint.clone(),
+                paint: self.paint.calculate_at_time(time, &self.animation_data),
+                opacity: opacity_at_time(&self.opacity, time, &self.animation_data),
                 transform,
             }))
         } else {
@@ -80,13 +77,27 @@ impl Animateable for AnimatedEllipseEntity {
     }
 
     fn sort_keyframes(&mut self) {
-        let transform = self.transform.clone();
-
-        if let Some(mut transform) = transform {
+        if let Some(transform) = &mut self.transform {
             transform.sort_keyframes();
         }
 
         self.position.sort_keyframes();
         self.radius.sort_keyframes();
+        self.paint.sort_keyframes();
+        self.opacity.sort_keyframes();
+    }
+
+    fn quantize(&mut self, fps: FrameRate) {
+        let quantize = self.animation_data.quantize(fps);
+
+        if let Some(transform) = &mut self.transform {
+            transform.quantize_keyframes(&quantize);
+        }
+
+        self.position.quantize_keyframes(&quantize);
+        self.radius.quantize_keyframes(&quantize);
+        self.origin.quantize_keyframes(&quantize);
+        self.paint.quantize_keyframes(&quantize);
+        self.opacity.quantize_keyframes(&quantize);
     }
 }
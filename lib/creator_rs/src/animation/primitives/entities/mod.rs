@@ -1,5 +1,6 @@
 pub mod common;
 pub mod ellipse;
+pub mod path;
 pub mod rect;
 pub mod staggered_text;
 pub mod text;
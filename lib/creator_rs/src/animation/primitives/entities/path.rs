@@ -0,0 +1,144 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    animation::{
+        primitives::{
+            frame_rate::FrameRate,
+            paint::{AnimatedPaint, Paint},
+            path::{AnimatedPathCommand, PathCommand},
+            transform::{AnimatedTransform, Transform},
+            values::animated_values::{AnimatedFloat, AnimatedFloatVec2, AnimatedValue},
+        },
+        timeline::Timeline,
+    },
+    error::CreatorResult,
+};
+
+use super::common::{
+    default_opacity, opacity_at_time, Animateable, AnimationData, Cache, Drawable, Entity,
+};
+
+/// A custom shape like an icon or a logo, the points of the path are relative to the position.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnimatedPathEntity {
+    pub paint: AnimatedPaint,
+    /// Opacity of the whole entity from 0 to 1.
+    #[serde(default = "default_opacity")]
+    pub opacity: AnimatedFloat,
+
+    pub id: String,
+    pub cache: Cache,
+    pub path: Vec<AnimatedPathCommand>,
+    pub origin: AnimatedFloatVec2,
+    pub position: AnimatedFloatVec2,
+    pub animation_data: AnimationData,
+    pub transform: Option<AnimatedTransform>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PathEntity {
+    pub path: Vec<PathCommand>,
+    pub cache: Cache,
+    pub id: String,
+    pub position: (f32, f32),
+    pub origin: (f32, f32),
+    pub paint: Paint,
+    pub opacity: f32,
+    pub transform: Option<Transform>,
+}
+
+impl AnimatedPathEntity {
+    /// Entity with the shape of the SVG path data, none of the points are animated yet.
+    pub fn from_svg(
+        id: String,
+        svg_path: &str,
+        paint: AnimatedPaint,
+        animation_data: AnimationData,
+    ) -> CreatorResult<AnimatedPathEntity> {
+        Ok(AnimatedPathEntity {
+            paint,
+            opacity: default_opacity(),
+            id,
+            cache: Cache { valid: false },
+            path: PathCommand::parse_svg(svg_path)?
+                .into_iter()
+                .map(AnimatedPathCommand::from)
+                .collect(),
+            origin: AnimatedFloatVec2::new(0.0, 0.0),
+            position: AnimatedFloatVec2::new(0.0, 0.0),
+            animation_data,
+            transform: None,
+        })
+    }
+}
+
+impl Drawable for AnimatedPathEntity {}
+impl Animateable for AnimatedPathEntity {
+    fn calculate_at_time(&self, _timeline: &Timeline, time: f32) -> Option<Entity> {
+        let should_draw = self.should_draw_at_time(&self.animation_data, time);
+
+        if should_draw {
+            let time = self.animation_data.source_time(time);
+
+            let path = self
+                .path
+                .iter()
+                .map(|command| command.calculate_at_time(time, &self.animation_data))
+                .collect();
+
+            let position = self.position.get_value_at_time(time, &self.animation_data);
+
+            let origin = self.origin.get_value_at_time(time, &self.animation_data);
+
+            let transform: Option<Transform> = self
+                .transform
+                .as_ref()
+                .map(|val| val.calculate_at_time(time, &self.animation_data));
+
+            Some(Entity::Path(PathEntity {
+                id: self.id.clone(),
+                path,
+                position,
+                origin,
+                cache: self.cache.clone(),
+                paint: self.paint.calculate_at_time(time, &self.animation_data),
+                opacity: opacity_at_time(&self.opacity, time, &self.animation_data),
+                transform,
+            }))
+        } else {
+            None
+        }
+    }
+
+    fn sort_keyframes(&mut self) {
+        if let Some(transform) = &mut self.transform {
+            transform.sort_keyframes();
+        }
+
+        for command in &mut self.path {
+            command.sort_keyframes();
+        }
+
+        self.position.sort_keyframes();
+        self.origin.sort_keyframes();
+        self.paint.sort_keyframes();
+        self.opacity.sort_keyframes();
+    }
+
+    fn quantize(&mut self, fps: FrameRate) {
+        let quantize = self.animation_data.quantize(fps);
+
+        if let Some(transform) = &mut self.transform {
+            transform.quantize_keyframes(&quantize);
+        }
+
+        for command in &mut self.path {
+            command.quantize_keyframes(&quantize);
+        }
+
+        self.position.quantize_keyframes(&quantize);
+        self.origin.quantize_keyframes(&quantize);
+        self.paint.quantize_keyframes(&quantize);
+        self.opacity.quantize_keyframes(&quantize);
+    }
+}
@@ -2,14 +2,17 @@ use serde::{Deserialize, Serialize};
 
 use crate::animation::{
     primitives::{
-        paint::Paint,
+        frame_rate::FrameRate,
+        paint::{AnimatedPaint, Paint},
         transform::{AnimatedTransform, Transform},
-        values::animated_values::{AnimatedFloatVec2, AnimatedValue},
+        values::animated_values::{AnimatedFloat, AnimatedFloatVec2, AnimatedValue},
     },
     timeline::Timeline,
 };
 
-use super::common::{Animateable, AnimationData, Cache, Drawable, Entity};
+use super::common::{
+    default_opacity, opacity_at_time, Animateable, AnimationData, Cache, Drawable, Entity,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnimatedRectEntity {
@@ -18,7 +21,10 @@ pub struct AnimatedRectEntity {
     pub position: AnimatedFloatVec2,
     pub size: AnimatedFloatVec2,
     pub origin: AnimatedFloatVec2,
-    pub paint: Paint,
+    pub paint: AnimatedPaint,
+    /// Opacity of the whole entity from 0 to 1.
+    #[serde(default = "default_opacity")]
+    pub opacity: AnimatedFloat,
     pub animation_data: AnimationData,
     pub transform: Option<AnimatedTransform>,
 }
@@ -31,6 +37,7 @@ pub struct RectEntity {
     pub size: (f32, f32),
     pub origin: (f32, f32),
     pub paint: Paint,
+    pub opacity: f32,
     pub transform: Option<Transform>,
 }
 
@@ -43,36 +50,40 @@ impl Animateable for AnimatedRectEntity {
 
         self.position.sort_keyframes();
         self.size.sort_keyframes();
+        self.paint.sort_keyframes();
+        self.opacity.sort_keyframes();
+    }
+
+    fn quantize(&mut self, fps: FrameRate) {
+        let quantize = self.animation_data.quantize(fps);
+
+        if let Some(x) = &mut self.transform {
+            x.quantize_keyframes(&quantize);
+        }
+
+        self.position.quantize_keyframes(&quantize);
+        self.size.quantize_keyframes(&quantize);
+        self.origin.quantize_keyframes(&quantize);
+        self.paint.quantize_keyframes(&quantize);
+        self.opacity.quantize_keyframes(&quantize);
     }
 
-    fn calculate(&mut self, timeline: &Timeline) -> Option<Entity> {
-        let should_draw = self.should_draw(&self.animation_data, timeline);
+    fn calculate_at_time(&self, _timeline: &Timeline, time: f32) -> Option<Entity> {
+        let should_draw = self.should_draw_at_time(&self.animation_data, time);
 
         if should_draw {
-            self.sort_keyframes();
-
-            let position = self.position.get_value_at_frame(
-                timeline.render_state.curr_frame,
-                &self.animation_data,
-                timeline.fps,
-            );
-
-            let size = self.size.get_value_at_frame(
-                timeline.render_state.curr_frame,
-                &self.animation_data,
-                timeline.fps,
-            );
-
-            let origin = self.origin.get_value_at_frame(
-                timeline.render_state.curr_frame,
-                &self.animation_data,
-                timeline.fps,
-            );
-
-            let transform: Option<Transform> = match self.transform.clone() {
-                Some(mut val) => Some(val.calculate(timeline, &self.animation_data)),
-                None => None,
-            };
+            let time = self.animation_data.source_time(time);
+
+            let position = self.position.get_value_at_time(time, &self.animation_data);
+
+            let size = self.size.get_value_at_time(time, &self.animation_data);
+
+            let origin = self.origin.get_value_at_time(time, &self.animation_data);
+
+            let transform: Option<Transform> = self
+                .transform
+                .as_ref()
+                .map(|val| val.calculate_at_time(time, &self.animation_data));
 
             Some(Entity::Rect(RectEntity {
                 id: self.id.clone(),
@@ -80,7 +91,8 @@ impl Animateable for AnimatedRectEntity {
                 position,
                 size,
                 origin,
-                paint: self.paint.clone(),
+                paint: self.paint.calculate_at_time(time, &self.animation_data),
+                opacity: opacity_at_time(&self.opacity, time, &self.animation_data),
                 transform,
             }))
         } else {
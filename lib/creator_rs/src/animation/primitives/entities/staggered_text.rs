@@ -1,9 +1,12 @@
-use super::common::{Animateable, AnimationData, Cache, Drawable, Entity};
+use super::common::{
+    default_opacity, opacity_at_time, Animateable, AnimationData, Cache, Drawable, Entity,
+};
 use crate::animation::{
     primitives::{
+        frame_rate::FrameRate,
         paint::TextPaint,
         transform::{AnimatedTransform, Transform},
-        values::animated_values::{AnimatedFloatVec2, AnimatedValue},
+        values::animated_values::{AnimatedFloat, AnimatedFloatVec2, AnimatedValue},
     },
     timeline::Timeline,
 };
@@ -31,6 +34,9 @@ pub struct AnimatedStaggeredTextEntity {
     pub animation_data: AnimationData,
     pub transform: Option<AnimatedTransform>,
     pub letter: AnimatedStaggeredTextLetter,
+    /// Opacity of the whole entity from 0 to 1.
+    #[serde(default = "default_opacity")]
+    pub opacity: AnimatedFloat,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -43,31 +49,33 @@ pub struct StaggeredTextEntity {
     pub transform: Option<Transform>,
     pub animation_data: AnimationData,
     pub letter: StaggeredTextLetter,
+    pub opacity: f32,
 }
 
 impl Drawable for AnimatedStaggeredTextEntity {}
 impl Animateable for AnimatedStaggeredTextEntity {
-    fn calculate(&mut self, timeline: &Timeline) -> Option<Entity> {
-        let should_draw: bool = self.should_draw(&self.animation_data, timeline);
+    fn calculate_at_time(&self, _timeline: &Timeline, time: f32) -> Option<Entity> {
+        let should_draw: bool = self.should_draw_at_time(&self.animation_data, time);
 
         if should_draw {
-            self.sort_keyframes();
+            let time = self.animation_data.source_time(time);
 
-            let transform: Option<Transform> = match self.transform.clone() {
-                Some(mut val) => Some(val.calculate(timeline, &self.animation_data)),
-                None => None,
-            };
+            let transform: Option<Transform> = self
+                .transform
+                .as_ref()
+                .map(|val| val.calculate_at_time(time, &self.animation_data));
 
             // Iterate over the chars of the string and calculate the animation with the staggered offset
-            let letter_transform: Option<Vec<Transform>> = match self.letter.transform.clone() {
-                Some(mut val) => {
+            let letter_transform: Option<Vec<Transform>> = match &self.letter.transform {
+                Some(val) => {
                     let mut transforms: Vec<Transform> = Vec::new();
 
                     for c in self.text.chars().enumerate() {
+                        // The time is already remapped, the letters only shift their keyframes
                         let mut animation_data = self.animation_data.clone();
                         animation_data.offset += self.stagger * c.0 as f32;
 
-                        let transform = val.calculate(timeline, &animation_data);
+                        let transform = val.calculate_at_time(time, &animation_data);
                         transforms.push(transform);
                     }
 
@@ -76,11 +84,7 @@ impl Animateable for AnimatedStaggeredTextEntity {
                 None => None,
             };
 
-            let origin = self.origin.get_value_at_frame(
-                timeline.render_state.curr_frame,
-                &self.animation_data,
-                timeline.fps,
-            );
+            let origin = self.origin.get_value_at_time(time, &self.animation_data);
 
             Some(Entity::StaggeredText(StaggeredTextEntity {
                 id: self.id.clone(),
@@ -90,6 +94,7 @@ impl Animateable for AnimatedStaggeredTextEntity {
                 origin,
                 text: self.text.clone(),
                 animation_data: self.animation_data.clone(),
+                opacity: opacity_at_time(&self.opacity, time, &self.animation_data),
                 letter: StaggeredTextLetter {
                     transform: letter_transform,
                     paint: self.letter.paint.clone(),
@@ -108,5 +113,25 @@ impl Animateable for AnimatedStaggeredTextEntity {
         if let Some(x) = &mut self.letter.transform {
             x.sort_keyframes();
         }
+
+        self.origin.sort_keyframes();
+        self.opacity.sort_keyframes();
+    }
+
+    fn quantize(&mut self, fps: FrameRate) {
+        let quantize = self.animation_data.quantize(fps);
+
+        if let Some(x) = &mut self.transform {
+            x.quantize_keyframes(&quantize);
+        }
+
+        if let Some(x) = &mut self.letter.transform {
+            x.quantize_keyframes(&quantize);
+        }
+
+        // Every letter starts on a frame as long as the stagger is a whole number of frames
+        self.stagger = fps.quantize(self.stagger);
+        self.origin.quantize_keyframes(&quantize);
+        self.opacity.quantize_keyframes(&quantize);
     }
 }
@@ -1,14 +1,17 @@
 use crate::animation::{
     primitives::{
-        paint::TextPaint,
+        frame_rate::FrameRate,
+        paint::{AnimatedTextPaint, TextPaint},
         transform::{AnimatedTransform, Transform},
-        values::animated_values::{AnimatedFloatVec2, AnimatedValue},
+        values::animated_values::{AnimatedFloat, AnimatedFloatVec2, AnimatedValue},
     },
     timeline::Timeline,
 };
 use serde::{Deserialize, Serialize};
 
-use super::common::{Animateable, AnimationData, Cache, Drawable, Entity};
+use super::common::{
+    default_opacity, opacity_at_time, Animateable, AnimationData, Cache, Drawable, Entity,
+};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TextEntity {
@@ -17,6 +20,7 @@ pub struct TextEntity {
     pub text: String,
     pub origin: (f32, f32),
     pub paint: TextPaint,
+    pub opacity: f32,
     pub transform: Option<Transform>,
 }
 
@@ -26,7 +30,10 @@ pub struct AnimatedTextEntity {
     pub cache: Cache,
     pub text: String,
     pub origin: AnimatedFloatVec2,
-    pub paint: TextPaint,
+    pub paint: AnimatedTextPaint,
+    /// Opacity of the whole entity from 0 to 1.
+    #[serde(default = "default_opacity")]
+    pub opacity: AnimatedFloat,
     pub animation_data: AnimationData,
     pub transform: Option<AnimatedTransform>,
 }
@@ -34,19 +41,13 @@ pub struct AnimatedTextEntity {
 impl Drawable for AnimatedTextEntity {}
 
 impl AnimatedTextEntity {
-    fn into_static(&mut self, timeline: &Timeline) -> TextEntity {
-        self.sort_keyframes();
-
-        let origin = self.origin.get_value_at_frame(
-            timeline.render_state.curr_frame,
-            &self.animation_data,
-            timeline.fps,
-        );
+    fn to_static(&self, time: f32) -> TextEntity {
+        let origin = self.origin.get_value_at_time(time, &self.animation_data);
 
-        let transform: Option<Transform> = match self.transform.clone() {
-            Some(mut val) => Some(val.calculate(timeline, &self.animation_data)),
-            None => None,
-        };
+        let transform: Option<Transform> = self
+            .transform
+            .as_ref()
+            .map(|val| val.calculate_at_time(time, &self.animation_data));
 
         TextEntity {
             id: self.id.clone(),
@@ -54,19 +55,20 @@ impl AnimatedTextEntity {
             transform,
             text: self.text.clone(),
             origin,
-            paint: self.paint.clone(),
+            paint: self.paint.calculate_at_time(time, &self.animation_data),
+            opacity: opacity_at_time(&self.opacity, time, &self.animation_data),
         }
     }
 }
 
 impl Animateable for AnimatedTextEntity {
-    fn calculate(&mut self, timeline: &Timeline) -> Option<Entity> {
-        let should_draw = self.should_draw(&self.animation_data, timeline);
+    fn calculate_at_time(&self, _timeline: &Timeline, time: f32) -> Option<Entity> {
+        let should_draw = self.should_draw_at_time(&self.animation_data, time);
 
         if should_draw {
-            self.sort_keyframes();
+            let time = self.animation_data.source_time(time);
 
-            Some(Entity::Text(self.into_static(timeline)))
+            Some(Entity::Text(self.to_static(time)))
         } else {
             None
         }
@@ -78,5 +80,19 @@ impl Animateable for AnimatedTextEntity {
         }
 
         self.origin.sort_keyframes();
+        self.paint.sort_keyframes();
+        self.opacity.sort_keyframes();
+    }
+
+    fn quantize(&mut self, fps: FrameRate) {
+        let quantize = self.animation_data.quantize(fps);
+
+        if let Some(x) = &mut self.transform {
+            x.quantize_keyframes(&quantize);
+        }
+
+        self.origin.quantize_keyframes(&quantize);
+        self.paint.quantize_keyframes(&quantize);
+        self.opacity.quantize_keyframes(&quantize);
     }
 }
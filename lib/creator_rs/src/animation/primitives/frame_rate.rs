@@ -0,0 +1,190 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::CreatorError;
+
+/// Tolerance when recognizing a decimal frame rate like 29.97 as an NTSC rate.
+const NTSC_EPSILON: f64 = 0.01;
+
+/// Frames per second as a fraction, so NTSC rates like 30000/1001 are exact. Deserializes from
+/// a plain number as well, common decimal NTSC rates like 23.976 or 29.97 become their exact
+/// fraction.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(try_from = "FrameRateRepr", into = "FrameRateRepr")]
+pub struct FrameRate {
+    numerator: u32,
+    denominator: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum FrameRateRepr {
+    Rational { numerator: u32, denominator: u32 },
+    Integer(u32),
+    Decimal(f64),
+}
+
+impl TryFrom<FrameRateRepr> for FrameRate {
+    type Error = CreatorError;
+
+    fn try_from(repr: FrameRateRepr) -> Result<Self, Self::Error> {
+        match repr {
+            FrameRateRepr::Rational {
+                numerator,
+                denominator,
+            } => FrameRate::new(numerator, denominator),
+            FrameRateRepr::Integer(fps) => FrameRate::new(fps, 1),
+            FrameRateRepr::Decimal(fps) => FrameRate::from_decimal(fps),
+        }
+    }
+}
+
+impl From<FrameRate> for FrameRateRepr {
+    fn from(fps: FrameRate) -> Self {
+        if fps.denominator == 1 {
+            FrameRateRepr::Integer(fps.numerator)
+        } else {
+            FrameRateRepr::Rational {
+                numerator: fps.numerator,
+                denominator: fps.denominator,
+            }
+        }
+    }
+}
+
+/// Integer frame rates, which is what every project before rational frame rates used.
+impl TryFrom<i16> for FrameRate {
+    type Error = CreatorError;
+
+    fn try_from(fps: i16) -> Result<Self, Self::Error> {
+        let fps = u32::try_from(fps).map_err(|_| CreatorError::InvalidFrameRate {
+            fps: fps.to_string(),
+        })?;
+
+        FrameRate::new(fps, 1)
+    }
+}
+
+impl fmt::Display for FrameRate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.denominator == 1 {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{:.3}", self.as_f64())
+        }
+    }
+}
+
+impl FrameRate {
+    pub const NTSC_FILM: FrameRate = FrameRate {
+        numerator: 24000,
+        denominator: 1001,
+    };
+    pub const NTSC: FrameRate = FrameRate {
+        numerator: 30000,
+        denominator: 1001,
+    };
+    pub const NTSC_HIGH: FrameRate = FrameRate {
+        numerator: 60000,
+        denominator: 1001,
+    };
+
+    /// The fraction is reduced, so 60/2 and 30/1 are the same frame rate.
+    pub fn new(numerator: u32, denominator: u32) -> Result<FrameRate, CreatorError> {
+        if numerator == 0 || denominator == 0 {
+            return Err(CreatorError::InvalidFrameRate {
+                fps: format!("{numerator}/{denominator}"),
+            });
+        }
+
+        let divisor = gcd(numerator, denominator);
+
+        Ok(FrameRate {
+            numerator: numerator / divisor,
+            denominator: denominator / divisor,
+        })
+    }
+
+    /// Whole numbers and NTSC rates like 23.976, 29.97 or 59.94 are exact, any other rate is
+    /// rounded to thousandths of a frame.
+    pub fn from_decimal(fps: f64) -> Result<FrameRate, CreatorError> {
+        if !fps.is_finite() || fps <= 0.0 || fps > u32::MAX as f64 / 1001.0 {
+            return Err(CreatorError::InvalidFrameRate {
+                fps: fps.to_string(),
+            });
+        }
+
+        if (fps - fps.round()).abs() < 1e-9 {
+            return FrameRate::new(fps.round() as u32, 1);
+        }
+
+        let nominal = (fps * 1.001).round();
+
+        if (nominal * 1000.0 / 1001.0 - fps).abs() < NTSC_EPSILON {
+            return FrameRate::new(nominal as u32 * 1000, 1001);
+        }
+
+        FrameRate::new((fps * 1000.0).round() as u32, 1000)
+    }
+
+    pub fn numerator(&self) -> u32 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> u32 {
+        self.denominator
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+
+    pub fn as_f32(&self) -> f32 {
+        self.as_f64() as f32
+    }
+
+    /// Whole frames per second a timecode counts, 30 for 29.97.
+    pub fn nominal(&self) -> u32 {
+        self.as_f64().round().max(1.0) as u32
+    }
+
+    /// NTSC rates based on a multiple of 30 can use drop-frame timecode.
+    pub fn supports_drop_frame(&self) -> bool {
+        self.denominator == 1001 && self.nominal().is_multiple_of(30)
+    }
+
+    pub fn frame_to_timestamp(&self, frame: f32) -> f32 {
+        (frame as f64 * self.denominator as f64 / self.numerator as f64) as f32
+    }
+
+    /// Nearest frame, exactly halfway between two frames rounds away from zero.
+    pub fn timestamp_to_frame(&self, timestamp: f32) -> i32 {
+        (timestamp as f64 * self.numerator as f64 / self.denominator as f64).round() as i32
+    }
+
+    /// Moves the time to the nearest frame.
+    pub fn quantize(&self, timestamp: f32) -> f32 {
+        self.frame_to_timestamp(self.timestamp_to_frame(timestamp) as f32)
+    }
+
+    /// The frame of this frame rate that shows the same moment as the frame of the other one.
+    pub fn convert_frame(&self, frame: i32, from: FrameRate) -> i32 {
+        // i128 holds the product of an i32 and two u32 without overflowing
+        let numerator = frame as i128 * self.numerator as i128 * from.denominator as i128;
+        let denominator = self.denominator as i128 * from.numerator as i128;
+
+        // Rounds half away from zero like timestamp_to_frame, but without float error
+        let rounded = (2 * numerator.abs() + denominator) / (2 * denominator);
+
+        (rounded * numerator.signum()).clamp(i32::MIN as i128, i32::MAX as i128) as i32
+    }
+}
+
+fn gcd(mut a: u32, mut b: u32) -> u32 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    a
+}
@@ -1,18 +1,117 @@
-use std::cmp::Ordering;
-
-use super::keyframe::RenderedKeyframe;
+use super::{frame_rate::FrameRate, keyframe::RenderedKeyframe};
 use serde::{Deserialize, Serialize};
 use simple_easing::{
-    circ_in, circ_in_out, circ_out, cubic_in, cubic_in_out, cubic_out, expo_in, expo_in_out,
-    expo_out, quad_in, quad_in_out, quad_out, quart_in, quart_in_out, quart_out, quint_in,
-    quint_in_out, quint_out,
+    bounce_in, bounce_in_out, bounce_out, circ_in, circ_in_out, circ_out, cubic_in, cubic_in_out,
+    cubic_out, expo_in, expo_in_out, expo_out, quad_in, quad_in_out, quad_out, quart_in,
+    quart_in_out, quart_out, quint_in, quint_in_out, quint_out, sine_in, sine_in_out, sine_out,
 };
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 pub struct SpringProperties {
     pub mass: f32,
     pub damping: f32,
     pub stiffness: f32,
+    /// Keeps the spring moving past the next keyframe until it comes to rest, its remaining
+    /// motion is added on top of the following keyframes.
+    #[serde(default)]
+    pub run_to_rest: bool,
+    /// Starts with the velocity the value has at the end of the previous segment instead of
+    /// at rest, so chained springs move continuously.
+    #[serde(default)]
+    pub inherit_velocity: bool,
+}
+
+/// Smallest bounce of a spring built from a duration, the most overdamped one allowed.
+const MIN_BOUNCE: f32 = -0.99;
+
+impl SpringProperties {
+    pub fn new(mass: f32, damping: f32, stiffness: f32) -> Self {
+        SpringProperties {
+            mass,
+            damping,
+            stiffness,
+            run_to_rest: false,
+            inherit_velocity: false,
+        }
+    }
+
+    /// Builds a spring from perceptual parameters like SwiftUI does. `duration` is the period of
+    /// one oscillation in seconds, `bounce` is 0 for a critically damped spring, approaches 1
+    /// for a spring that keeps oscillating and goes down to -1 for an overdamped spring.
+    pub fn from_duration_and_bounce(duration: f32, bounce: f32) -> Self {
+        let duration = duration.max(f32::EPSILON);
+        let bounce = bounce.clamp(MIN_BOUNCE, 1.0);
+
+        let damping_ratio = if bounce >= 0.0 {
+            1.0 - bounce
+        } else {
+            1.0 / (1.0 + bounce)
+        };
+        let natural_frequency = TAU / duration;
+
+        SpringProperties::new(
+            1.0,
+            2.0 * damping_ratio * natural_frequency,
+            natural_frequency * natural_frequency,
+        )
+    }
+
+    /// Seconds until the spring stays within `precision` of its target, relative to the distance
+    /// it started from. Springs without damping never settle and return infinity.
+    pub fn settle_time(&self, precision: f32) -> f32 {
+        let precision = precision.abs().clamp(f32::EPSILON, 1.0) as f64;
+
+        if self.mass <= 0.0 || precision >= 1.0 {
+            return 0.0;
+        }
+
+        if self.stiffness <= 0.0 || self.damping <= 0.0 {
+            return f32::INFINITY;
+        }
+
+        let omega = self.natural_frequency() as f64;
+        let zeta = self.damping_ratio() as f64;
+
+        if zeta < 1.0 - SPRING_DAMPING_EPSILON {
+            // The oscillation stays within its decaying envelope, which starts above 1.0
+            let amplitude = 1.0 / (1.0 - zeta * zeta).sqrt();
+
+            return ((amplitude / precision).ln() / (zeta * omega)) as f32;
+        }
+
+        // Without oscillation the distance shrinks monotonically, so the time can be bisected
+        let displacement = |time: f64| spring_state(self, 1.0, 0.0, time).0.abs();
+        let mut end = 1.0 / omega;
+
+        while displacement(end) > precision {
+            end *= 2.0;
+        }
+
+        let mut start = 0.0;
+
+        for _ in 0..SETTLE_BISECTION_ITERATIONS {
+            let middle = (start + end) / 2.0;
+
+            if displacement(middle) > precision {
+                start = middle;
+            } else {
+                end = middle;
+            }
+        }
+
+        end as f32
+    }
+
+    /// Undamped angular frequency of the spring in radians per second.
+    pub fn natural_frequency(&self) -> f32 {
+        (self.stiffness / self.mass).sqrt()
+    }
+
+    /// Ratio between the damping and the critical damping, the spring oscillates below 1.0.
+    pub fn damping_ratio(&self) -> f32 {
+        self.damping / (2.0 * (self.stiffness * self.mass).sqrt())
+    }
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
@@ -42,10 +141,70 @@ pub enum EasingFunction {
     QuartOut,
     QuartIn,
     QuartInOut,
+    SineOut,
+    SineIn,
+    SineInOut,
+    /// Overshoots the target by `overshoot` before settling, 1.70158 overshoots by about 10%.
+    BackOut {
+        #[serde(default = "default_overshoot")]
+        overshoot: f32,
+    },
+    BackIn {
+        #[serde(default = "default_overshoot")]
+        overshoot: f32,
+    },
+    BackInOut {
+        #[serde(default = "default_overshoot")]
+        overshoot: f32,
+    },
+    /// Oscillates around the target, `amplitude` scales the first swing and `period` is the
+    /// length of one oscillation as a fraction of the keyframe duration.
+    ElasticOut {
+        #[serde(default = "default_amplitude")]
+        amplitude: f32,
+        #[serde(default = "default_period")]
+        period: f32,
+    },
+    ElasticIn {
+        #[serde(default = "default_amplitude")]
+        amplitude: f32,
+        #[serde(default = "default_period")]
+        period: f32,
+    },
+    /// Plays the elastic in curve in the first and the out curve in the second half, `period` is
+    /// a fraction of each half.
+    ElasticInOut {
+        #[serde(default = "default_amplitude")]
+        amplitude: f32,
+        #[serde(default = "default_in_out_period")]
+        period: f32,
+    },
+    BounceOut,
+    BounceIn,
+    BounceInOut,
+}
+
+fn default_overshoot() -> f32 {
+    1.70158
+}
+
+fn default_amplitude() -> f32 {
+    1.0
+}
+
+fn default_period() -> f32 {
+    0.3
 }
 
+fn default_in_out_period() -> f32 {
+    0.45
+}
+
+/// Penner's scale of the overshoot so the in-out curve overshoots as much as the in and out curves.
+const BACK_IN_OUT_OVERSHOOT_SCALE: f32 = 1.525;
+
 impl EasingFunction {
-    fn ease(self: &Self, t: f32) -> f32 {
+    fn ease(&self, t: f32) -> f32 {
         match self {
             EasingFunction::QuintOut => quint_out(t),
             EasingFunction::QuintIn => quint_in(t),
@@ -65,61 +224,612 @@ impl EasingFunction {
             EasingFunction::QuartOut => quart_out(t),
             EasingFunction::QuartIn => quart_in(t),
             EasingFunction::QuartInOut => quart_in_out(t),
+            EasingFunction::SineOut => sine_out(t),
+            EasingFunction::SineIn => sine_in(t),
+            EasingFunction::SineInOut => sine_in_out(t),
+            EasingFunction::BackOut { overshoot } => back_out(t, *overshoot),
+            EasingFunction::BackIn { overshoot } => ease_in(t, |t| back_out(t, *overshoot)),
+            EasingFunction::BackInOut { overshoot } => {
+                ease_in_out(t, |t| back_out(t, overshoot * BACK_IN_OUT_OVERSHOOT_SCALE))
+            }
+            EasingFunction::ElasticOut { amplitude, period } => elastic_out(t, *amplitude, *period),
+            EasingFunction::ElasticIn { amplitude, period } => {
+                ease_in(t, |t| elastic_out(t, *amplitude, *period))
+            }
+            EasingFunction::ElasticInOut { amplitude, period } => {
+                ease_in_out(t, |t| elastic_out(t, *amplitude, *period))
+            }
+            EasingFunction::BounceOut => bounce_out(t),
+            EasingFunction::BounceIn => bounce_in(t),
+            EasingFunction::BounceInOut => bounce_in_out(t),
+        }
+    }
+}
+
+/// Mirrors an ease out curve into the matching ease in curve.
+fn ease_in(t: f32, ease_out: impl Fn(f32) -> f32) -> f32 {
+    1.0 - ease_out(1.0 - t)
+}
+
+/// Plays the ease in curve in the first and the ease out curve in the second half.
+fn ease_in_out(t: f32, ease_out: impl Fn(f32) -> f32) -> f32 {
+    if t < 0.5 {
+        ease_in(2.0 * t, ease_out) / 2.0
+    } else {
+        (1.0 + ease_out(2.0 * t - 1.0)) / 2.0
+    }
+}
+
+/// Derivative of [`ease_in`] given the derivative of the ease out curve.
+fn ease_in_derivative(t: f32, ease_out_derivative: impl Fn(f32) -> f32) -> f32 {
+    ease_out_derivative(1.0 - t)
+}
+
+/// Derivative of [`ease_in_out`] given the derivative of the ease out curve.
+fn ease_in_out_derivative(t: f32, ease_out_derivative: impl Fn(f32) -> f32) -> f32 {
+    if t < 0.5 {
+        ease_in_derivative(2.0 * t, ease_out_derivative)
+    } else {
+        ease_out_derivative(2.0 * t - 1.0)
+    }
+}
+
+// simple_easing only has the default overshoot, amplitude and period, so the back and elastic
+// curves are parametrized here. They match simple_easing with the default parameters.
+
+fn back_out(t: f32, overshoot: f32) -> f32 {
+    let u = t - 1.0;
+
+    1.0 + (overshoot + 1.0) * u.powi(3) + overshoot * u.powi(2)
+}
+
+fn back_out_derivative(t: f32, overshoot: f32) -> f32 {
+    let u = t - 1.0;
+
+    3.0 * (overshoot + 1.0) * u.powi(2) + 2.0 * overshoot * u
+}
+
+/// Angular frequency and phase shift of an elastic curve, amplitudes below 1.0 can't reach
+/// the target and are raised to 1.0.
+fn elastic_shape(amplitude: f32, period: f32) -> (f32, f32, f32) {
+    let period = period.max(f32::EPSILON);
+    let amplitude = amplitude.max(1.0);
+    let phase = period / TAU * (1.0 / amplitude).asin();
+
+    (amplitude, TAU / period, phase)
+}
+
+fn elastic_out(t: f32, amplitude: f32, period: f32) -> f32 {
+    if t <= 0.0 {
+        return 0.0;
+    }
+    if t >= 1.0 {
+        return 1.0;
+    }
+
+    let (amplitude, frequency, phase) = elastic_shape(amplitude, period);
+
+    amplitude * 2f32.powf(-10.0 * t) * ((t - phase) * frequency).sin() + 1.0
+}
+
+fn elastic_out_derivative(t: f32, amplitude: f32, period: f32) -> f32 {
+    if !(0.0..1.0).contains(&t) {
+        return 0.0;
+    }
+
+    let (amplitude, frequency, phase) = elastic_shape(amplitude, period);
+    let angle = (t - phase) * frequency;
+
+    amplitude
+        * 2f32.powf(-10.0 * t)
+        * (frequency * angle.cos() - 10.0 * std::f32::consts::LN_2 * angle.sin())
+}
+
+/// Derivative of `simple_easing::bounce_out`, every bounce is a parabola of the same width.
+fn bounce_out_derivative(t: f32) -> f32 {
+    const SCALE: f32 = 7.5625;
+    const WIDTH: f32 = 2.75;
+
+    let center = if t < 1.0 / WIDTH {
+        0.0
+    } else if t < 2.0 / WIDTH {
+        1.5 / WIDTH
+    } else if t < 2.5 / WIDTH {
+        2.25 / WIDTH
+    } else {
+        2.625 / WIDTH
+    };
+
+    2.0 * SCALE * (t - center)
+}
+
+/// Derivative of `1 - (1 - t)^n`
+fn power_out_derivative(t: f32, n: i32) -> f32 {
+    n as f32 * (1.0 - t).powi(n - 1)
+}
+
+/// Derivative of the in-out power curves, `2^(n-1) * t^n` in the first half
+fn power_in_out_derivative(t: f32, n: i32) -> f32 {
+    if t < 0.5 {
+        n as f32 * 2f32.powi(n - 1) * t.powi(n - 1)
+    } else {
+        n as f32 * (-2.0 * t + 2.0).powi(n - 1)
+    }
+}
+
+/// Derivative of `sqrt(1 - x^2)` with respect to x, clamped before it becomes infinite
+fn circle_derivative(x: f32) -> f32 {
+    x / (1.0 - x * x).max(1e-6).sqrt()
+}
+
+impl EasingFunction {
+    /// Derivative of the easing function with respect to its progress.
+    pub fn derivative(&self, t: f32) -> f32 {
+        let ln_2 = std::f32::consts::LN_2;
+
+        match self {
+            // simple_easing implements quint_in as t^4
+            EasingFunction::QuintIn => 4.0 * t.powi(3),
+            EasingFunction::QuintOut => power_out_derivative(t, 5),
+            EasingFunction::QuintInOut => power_in_out_derivative(t, 5),
+            EasingFunction::QuartIn => 4.0 * t.powi(3),
+            EasingFunction::QuartOut => power_out_derivative(t, 4),
+            EasingFunction::QuartInOut => power_in_out_derivative(t, 4),
+            EasingFunction::CubicIn => 3.0 * t.powi(2),
+            EasingFunction::CubicOut => power_out_derivative(t, 3),
+            EasingFunction::CubicInOut => power_in_out_derivative(t, 3),
+            EasingFunction::QuadIn => 2.0 * t,
+            EasingFunction::QuadOut => power_out_derivative(t, 2),
+            EasingFunction::QuadInOut => power_in_out_derivative(t, 2),
+            EasingFunction::CircIn => circle_derivative(t),
+            EasingFunction::CircOut => circle_derivative(1.0 - t),
+            EasingFunction::CircInOut => {
+                if t < 0.5 {
+                    circle_derivative(2.0 * t)
+                } else {
+                    circle_derivative(2.0 - 2.0 * t)
+                }
+            }
+            EasingFunction::ExpoIn => {
+                if t <= 0.0 {
+                    0.0
+                } else {
+                    10.0 * ln_2 * 2f32.powf(10.0 * t - 10.0)
+                }
+            }
+            EasingFunction::ExpoOut => {
+                if t >= 1.0 {
+                    0.0
+                } else {
+                    10.0 * ln_2 * 2f32.powf(-10.0 * t)
+                }
+            }
+            EasingFunction::ExpoInOut => {
+                if t <= 0.0 || t >= 1.0 {
+                    0.0
+                } else if t < 0.5 {
+                    10.0 * ln_2 * 2f32.powf(20.0 * t - 10.0)
+                } else {
+                    10.0 * ln_2 * 2f32.powf(-20.0 * t + 10.0)
+                }
+            }
+            EasingFunction::SineOut => FRAC_PI_2 * (t * FRAC_PI_2).cos(),
+            EasingFunction::SineIn => FRAC_PI_2 * (t * FRAC_PI_2).sin(),
+            EasingFunction::SineInOut => FRAC_PI_2 * (t * PI).sin(),
+            EasingFunction::BackOut { overshoot } => back_out_derivative(t, *overshoot),
+            EasingFunction::BackIn { overshoot } => {
+                ease_in_derivative(t, |t| back_out_derivative(t, *overshoot))
+            }
+            EasingFunction::BackInOut { overshoot } => ease_in_out_derivative(t, |t| {
+                back_out_derivative(t, overshoot * BACK_IN_OUT_OVERSHOOT_SCALE)
+            }),
+            EasingFunction::ElasticOut { amplitude, period } => {
+                elastic_out_derivative(t, *amplitude, *period)
+            }
+            EasingFunction::ElasticIn { amplitude, period } => {
+                ease_in_derivative(t, |t| elastic_out_derivative(t, *amplitude, *period))
+            }
+            EasingFunction::ElasticInOut { amplitude, period } => {
+                ease_in_out_derivative(t, |t| elastic_out_derivative(t, *amplitude, *period))
+            }
+            EasingFunction::BounceOut => bounce_out_derivative(t),
+            EasingFunction::BounceIn => ease_in_derivative(t, bounce_out_derivative),
+            EasingFunction::BounceInOut => ease_in_out_derivative(t, bounce_out_derivative),
         }
     }
 }
 
+/// A CSS style `cubic-bezier(x1, y1, x2, y2)` timing curve, the start and end points are fixed at (0, 0) and (1, 1).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct CubicBezier {
+    pub x1: f32,
+    pub y1: f32,
+    pub x2: f32,
+    pub y2: f32,
+}
+
+impl CubicBezier {
+    const NEWTON_ITERATIONS: usize = 8;
+    const BISECTION_ITERATIONS: usize = 64;
+    const EPSILON: f64 = 1e-7;
+
+    pub fn new(x1: f32, y1: f32, x2: f32, y2: f32) -> Self {
+        CubicBezier { x1, y1, x2, y2 }
+    }
+
+    /// Polynomial coefficients (a, b, c) of one axis, so that `p(t) = ((a * t + b) * t + c) * t`.
+    fn coefficients(p1: f64, p2: f64) -> (f64, f64, f64) {
+        let c = 3.0 * p1;
+        let b = 3.0 * (p2 - p1) - c;
+        let a = 1.0 - c - b;
+
+        (a, b, c)
+    }
+
+    fn sample((a, b, c): (f64, f64, f64), t: f64) -> f64 {
+        ((a * t + b) * t + c) * t
+    }
+
+    fn sample_derivative((a, b, c): (f64, f64, f64), t: f64) -> f64 {
+        (3.0 * a * t + 2.0 * b) * t + c
+    }
+
+    /// Finds the curve parameter for the given x, first using newtons method and
+    /// falling back to bisection if it doesn't converge.
+    fn solve_curve_x(&self, x: f64) -> f64 {
+        let x_coefficients = Self::coefficients(
+            self.x1.clamp(0.0, 1.0) as f64,
+            self.x2.clamp(0.0, 1.0) as f64,
+        );
+
+        let mut t = x;
+
+        for _ in 0..Self::NEWTON_ITERATIONS {
+            let error = Self::sample(x_coefficients, t) - x;
+
+            if error.abs() < Self::EPSILON {
+                return t;
+            }
+
+            let derivative = Self::sample_derivative(x_coefficients, t);
+
+            if derivative.abs() < 1e-6 {
+                break;
+            }
+
+            t -= error / derivative;
+        }
+
+        let mut lower = 0.0;
+        let mut upper = 1.0;
+        t = x;
+
+        for _ in 0..Self::BISECTION_ITERATIONS {
+            let value = Self::sample(x_coefficients, t);
+
+            if (value - x).abs() < Self::EPSILON {
+                break;
+            }
+
+            if x > value {
+                lower = t;
+            } else {
+                upper = t;
+            }
+
+            t = (upper - lower) * 0.5 + lower;
+        }
+
+        t
+    }
+
+    pub fn ease(&self, x: f32) -> f32 {
+        let x = (x as f64).clamp(0.0, 1.0);
+
+        if x == 0.0 || x == 1.0 {
+            return x as f32;
+        }
+
+        let t = self.solve_curve_x(x);
+        let y_coefficients = Self::coefficients(self.y1 as f64, self.y2 as f64);
+
+        Self::sample(y_coefficients, t) as f32
+    }
+
+    /// Slope of the curve (dy/dx) at the given x.
+    pub fn derivative(&self, x: f32) -> f32 {
+        let t = self.solve_curve_x((x as f64).clamp(0.0, 1.0));
+        let x_coefficients = Self::coefficients(
+            self.x1.clamp(0.0, 1.0) as f64,
+            self.x2.clamp(0.0, 1.0) as f64,
+        );
+        let y_coefficients = Self::coefficients(self.y1 as f64, self.y2 as f64);
+
+        let dx = Self::sample_derivative(x_coefficients, t);
+        let dy = Self::sample_derivative(y_coefficients, t);
+
+        if dx.abs() < 1e-9 {
+            return 0.0;
+        }
+
+        (dy / dx) as f32
+    }
+}
+
+/// Smallest influence of a [`KeyframeEase`], as in After Effects.
+const MIN_INFLUENCE: f32 = 0.001;
+
+/// Change in value below which the speeds of a [`TemporalEase`] are ignored.
+const MIN_EASE_CHANGE: f32 = 1e-6;
+
+/// One side of an After Effects style temporal ease.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct KeyframeEase {
+    /// Speed in units per second at the keyframe.
+    pub speed: f32,
+    /// How far the ease reaches towards the neighbouring keyframe, from 0 to 1 of the time between them.
+    pub influence: f32,
+}
+
+/// After Effects style temporal ease of a keyframe, the segment between two keyframes is shaped
+/// by the outgoing ease of the first and the incoming ease of the second one.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct TemporalEase {
+    pub ease_in: KeyframeEase,
+    pub ease_out: KeyframeEase,
+}
+
+impl TemporalEase {
+    /// Timing curve of a segment lasting `duration` seconds whose value changes by `change`.
+    /// A side without an ease continues with the average speed of the segment, speeds are
+    /// treated as 0 when the change can't be measured or the values are equal.
+    pub fn segment_curve(
+        ease_out: Option<KeyframeEase>,
+        ease_in: Option<KeyframeEase>,
+        change: Option<f32>,
+        duration: f32,
+    ) -> CubicBezier {
+        let change = change.filter(|change| change.abs() > MIN_EASE_CHANGE);
+
+        // Progress gained over the handle, the handle points along the average speed without an ease
+        let handle = |ease: Option<KeyframeEase>| match ease {
+            Some(ease) => {
+                let influence = ease.influence.clamp(MIN_INFLUENCE, 1.0);
+                let progress =
+                    change.map_or(0.0, |change| ease.speed * influence * duration / change);
+
+                (influence, progress)
+            }
+            None => (1.0 / 3.0, 1.0 / 3.0),
+        };
+
+        let (out_influence, out_progress) = handle(ease_out);
+        let (in_influence, in_progress) = handle(ease_in);
+
+        CubicBezier::new(
+            out_influence,
+            out_progress,
+            1.0 - in_influence,
+            1.0 - in_progress,
+        )
+    }
+}
+
+/// Where the jumps of a [`Steps`] interpolation happen, equivalent to the CSS `jump-*` terms.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+pub enum StepJump {
+    Start,
+    #[default]
+    End,
+    None,
+    Both,
+}
+
+/// A CSS style `steps(count, jump)` timing function.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct Steps {
+    pub count: u32,
+    #[serde(default)]
+    pub jump: StepJump,
+}
+
+impl Steps {
+    pub fn new(count: u32, jump: StepJump) -> Self {
+        Steps { count, jump }
+    }
+
+    pub fn ease(&self, x: f32) -> f32 {
+        let count = self.count.max(1) as f32;
+
+        let jumps = match self.jump {
+            StepJump::Start | StepJump::End => count,
+            StepJump::None => (count - 1.0).max(1.0),
+            StepJump::Both => count + 1.0,
+        };
+
+        let mut step = (x * count).floor();
+
+        if let StepJump::Start | StepJump::Both = self.jump {
+            step += 1.0;
+        }
+
+        (step.clamp(0.0, jumps)) / jumps
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "type")]
 pub enum InterpolationType {
     Linear,
     Spring(SpringProperties),
     EasingFunction(EasingFunction),
+    CubicBezier(CubicBezier),
+    /// Keeps the value of the keyframe until the next keyframe is reached.
+    Hold,
+    Steps(Steps),
 }
 
+const SPRING_DAMPING_EPSILON: f64 = 1e-6;
+
+const SETTLE_BISECTION_ITERATIONS: usize = 48;
+
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub fn get_spring_settle_time(spring: SpringProperties, precision: f32) -> f32 {
+    spring.settle_time(precision)
+}
+
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub fn get_spring_from_duration_and_bounce(duration: f32, bounce: f32) -> SpringProperties {
+    SpringProperties::from_duration_and_bounce(duration, bounce)
+}
+
+/// Displacement from the rest position and velocity (units per second) of a damped harmonic
+/// oscillator after `time` seconds, when it starts with the given displacement and velocity.
+fn spring_state(
+    spring_props: &SpringProperties,
+    displacement: f64,
+    velocity: f64,
+    time: f64,
+) -> (f64, f64) {
+    let mass = spring_props.mass as f64;
+    let stiffness = spring_props.stiffness as f64;
+    let damping = spring_props.damping as f64;
+
+    if mass <= 0.0 {
+        return (0.0, 0.0);
+    }
+
+    if stiffness <= 0.0 {
+        return (displacement, 0.0);
+    }
+
+    let omega = (stiffness / mass).sqrt();
+    let zeta = damping / (2.0 * (stiffness * mass).sqrt());
+
+    if zeta < 1.0 - SPRING_DAMPING_EPSILON {
+        let omega_d = omega * (1.0 - zeta * zeta).sqrt();
+        let decay = zeta * omega;
+        let envelope = (-decay * time).exp();
+        let (sin, cos) = (omega_d * time).sin_cos();
+
+        let a = displacement;
+        let b = (velocity + decay * displacement) / omega_d;
+
+        (
+            envelope * (a * cos + b * sin),
+            envelope * ((b * omega_d - decay * a) * cos - (a * omega_d + decay * b) * sin),
+        )
+    } else if zeta > 1.0 + SPRING_DAMPING_EPSILON {
+        let root = omega * (zeta * zeta - 1.0).sqrt();
+        let r1 = -zeta * omega + root;
+        let r2 = -zeta * omega - root;
+        let c1 = (velocity - r2 * displacement) / (r1 - r2);
+        let c2 = displacement - c1;
+
+        let e1 = (r1 * time).exp();
+        let e2 = (r2 * time).exp();
+
+        (c1 * e1 + c2 * e2, c1 * r1 * e1 + c2 * r2 * e2)
+    } else {
+        let a = displacement;
+        let b = velocity + omega * displacement;
+        let envelope = (-omega * time).exp();
+
+        (
+            (a + b * time) * envelope,
+            (b - omega * (a + b * time)) * envelope,
+        )
+    }
+}
+
+/// Evaluates the spring moving from `start_value` towards `target_value` after `time` seconds.
 pub fn calculate_spring_value(
-    curr_frame: i32,
+    time: f32,
     start_value: f32,
     target_value: f32,
-    start_frame: i32,
-    _end_frame: i32,
     spring_props: &SpringProperties,
 ) -> f32 {
-    const PRECISION: f32 = 0.01;
-    const STEP: f32 = 10.0;
-    const REST_VELOCITY: f32 = PRECISION / 10.0;
-
-    let _is_growing = match start_value.total_cmp(&target_value) {
-        Ordering::Equal => false,
-        Ordering::Less => false,
-        Ordering::Greater => true,
-    };
+    let displacement = (start_value - target_value) as f64;
 
-    let mut _is_moving = false;
-    let mut spring_state = SpringState {
-        last_val: start_value,
-        velocity: 0.0,
-    };
+    let (displacement, _) = spring_state(spring_props, displacement, 0.0, time.max(0.0) as f64);
+
+    target_value + displacement as f32
+}
+
+/// Velocity in units per second of the spring moving from `start_value` towards `target_value`
+/// after `time` seconds.
+pub fn calculate_spring_velocity(
+    time: f32,
+    start_value: f32,
+    target_value: f32,
+    spring_props: &SpringProperties,
+) -> f32 {
+    let displacement = (start_value - target_value) as f64;
+    let (_, velocity) = spring_state(spring_props, displacement, 0.0, time.max(0.0) as f64);
 
-    let mut position = start_value;
-    let relative_curr_frame = curr_frame - start_frame;
+    velocity as f32
+}
 
-    //  println!("target_value {target_value} start_value {start_value}");
-    //  println!("start_frame {start_frame} end_frame {end_frame}");
+/// Progress of a spring moving from 0 towards 1 after `time` seconds, when it starts with
+/// `velocity` in progress per second.
+pub fn calculate_spring_progress(time: f32, velocity: f32, spring_props: &SpringProperties) -> f32 {
+    let (displacement, _) = spring_state(spring_props, -1.0, velocity as f64, time.max(0.0) as f64);
 
-    for _ in 0..relative_curr_frame {
-        let _is_moving = spring_state.velocity.abs() > REST_VELOCITY;
+    1.0 + displacement as f32
+}
+
+/// Rate of change of [`calculate_spring_progress`] per second.
+pub fn calculate_spring_progress_velocity(
+    time: f32,
+    velocity: f32,
+    spring_props: &SpringProperties,
+) -> f32 {
+    let (_, velocity) = spring_state(spring_props, -1.0, velocity as f64, time.max(0.0) as f64);
 
-        let spring_force = -spring_props.stiffness * 0.000001 * (position - target_value);
-        let damping_force = -spring_props.damping * 0.001 * spring_state.velocity;
-        let acceleration = (spring_force + damping_force) / spring_props.mass; // pt/ms^2
+    velocity as f32
+}
 
-        spring_state.velocity = spring_state.velocity + acceleration * STEP; // pt/ms
-        position = position + spring_state.velocity * STEP;
-        //       println!("{position}")
+impl InterpolationType {
+    /// Maps the linear progress between two keyframes to the interpolated progress,
+    /// `elapsed` is the time in seconds since the first keyframe.
+    pub fn ease(&self, progress: f32, elapsed: f32) -> f32 {
+        match self {
+            InterpolationType::Linear => progress,
+            InterpolationType::EasingFunction(easing_function) => easing_function.ease(progress),
+            InterpolationType::CubicBezier(cubic_bezier) => cubic_bezier.ease(progress),
+            InterpolationType::Hold => {
+                if progress < 1.0 {
+                    0.0
+                } else {
+                    1.0
+                }
+            }
+            InterpolationType::Steps(steps) => steps.ease(progress),
+            InterpolationType::Spring(spring_properties) => {
+                calculate_spring_value(elapsed, 0.0, 1.0, spring_properties)
+            }
+        }
     }
 
-    position
+    /// Rate of change of the eased progress per second, `duration` is the time in seconds
+    /// between the two keyframes.
+    pub fn ease_velocity(&self, progress: f32, elapsed: f32, duration: f32) -> f32 {
+        let progress_velocity = if duration > 0.0 { 1.0 / duration } else { 0.0 };
+
+        match self {
+            InterpolationType::Linear => progress_velocity,
+            InterpolationType::EasingFunction(easing_function) => {
+                easing_function.derivative(progress) * progress_velocity
+            }
+            InterpolationType::CubicBezier(cubic_bezier) => {
+                cubic_bezier.derivative(progress) * progress_velocity
+            }
+            InterpolationType::Hold | InterpolationType::Steps(_) => 0.0,
+            InterpolationType::Spring(spring_properties) => {
+                calculate_spring_velocity(elapsed, 0.0, 1.0, spring_properties)
+            }
+        }
+    }
 }
 
 pub fn interpolate_rendered_keyframes(
@@ -127,44 +837,43 @@ pub fn interpolate_rendered_keyframes(
     second_ren_keyframe: &RenderedKeyframe,
     curr_frame: i32,
     interpolation_type: InterpolationType,
-    _fps: i16,
+    fps: FrameRate,
 ) -> f32 {
     let frame_range = second_ren_keyframe.absolute_frame - first_ren_keyframe.absolute_frame;
     let position_in_range = curr_frame - first_ren_keyframe.absolute_frame;
     let progress: f32 = (1.0 / frame_range as f32) * position_in_range as f32;
+    let elapsed = fps.frame_to_timestamp(position_in_range as f32);
 
-    /*     println!(
-        "Progress:{0} Frame_Range: {1} Position_In_Range: {2}",
-        progress, frame_range, position_in_range
-    ); */
-
-    let value_diff = second_ren_keyframe.keyframe.value - first_ren_keyframe.keyframe.value;
-
-    match interpolation_type {
-        InterpolationType::Linear => {
-            let interpolated_val =
-                first_ren_keyframe.keyframe.value + (value_diff * progress as f32);
-
-            return interpolated_val;
-        }
-        InterpolationType::EasingFunction(easing_function) => {
-            let eased_progress = easing_function.ease(progress);
+    interpolate_values(
+        first_ren_keyframe.keyframe.value,
+        second_ren_keyframe.keyframe.value,
+        progress,
+        elapsed,
+        interpolation_type,
+    )
+}
 
-            let interpolated_val =
-                first_ren_keyframe.keyframe.value + (value_diff * eased_progress as f32);
+/// Interpolates between two keyframe values, `progress` is the linear progress between the
+/// keyframes and `elapsed` the time in seconds since the first one.
+pub fn interpolate_values(
+    first_value: f32,
+    second_value: f32,
+    progress: f32,
+    elapsed: f32,
+    interpolation_type: InterpolationType,
+) -> f32 {
+    first_value + (second_value - first_value) * interpolation_type.ease(progress, elapsed)
+}
 
-            return interpolated_val;
-        }
-        InterpolationType::Spring(spring_properties) => {
-            let interpolated_value = calculate_spring_value(
-                curr_frame,
-                first_ren_keyframe.keyframe.value,
-                second_ren_keyframe.keyframe.value,
-                first_ren_keyframe.absolute_frame,
-                second_ren_keyframe.absolute_frame,
-                &spring_properties,
-            );
-            return interpolated_value;
-        }
-    };
+/// Rate of change in units per second between two keyframe values, `duration` is the time in
+/// seconds between the keyframes.
+pub fn interpolate_velocity(
+    first_value: f32,
+    second_value: f32,
+    progress: f32,
+    elapsed: f32,
+    duration: f32,
+    interpolation_type: InterpolationType,
+) -> f32 {
+    (second_value - first_value) * interpolation_type.ease_velocity(progress, elapsed, duration)
 }
@@ -1,25 +1,37 @@
-use std::{cmp::Ordering, sync::Arc};
+use std::{
+    ops::{Deref, DerefMut},
+    sync::Arc,
+};
 
 use serde::{Deserialize, Serialize};
 
 use super::{
     entities::common::AnimationData,
-    interpolations::{interpolate_rendered_keyframes, InterpolationType},
-    utils::render_keyframe,
-    values::values::Float,
+    frame_rate::FrameRate,
+    interpolations::{
+        calculate_spring_progress, calculate_spring_progress_velocity, InterpolationType,
+        TemporalEase,
+    },
+    utils::{frame_to_timestamp, is_same_time},
+    values::{interpolate::Interpolate, values::Float},
 };
+use crate::error::{CreatorError, CreatorResult};
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
-pub struct Keyframe {
-    pub value: Float,
+pub struct Keyframe<T = Float> {
+    pub value: T,
     pub offset: f32,
     pub id: Arc<str>,
     pub interpolation: Option<InterpolationType>,
+    /// Shapes the segments on both sides of the keyframe, the segment leaving it ignores
+    /// `interpolation` when this or the next keyframe has a temporal ease.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temporal_ease: Option<TemporalEase>,
 }
 
-impl Keyframe {
+impl<T> Keyframe<T> {
     pub fn new(
-        value: f32,
+        value: T,
         offset: f32,
         id: Arc<str>,
         interpolation: Option<InterpolationType>,
@@ -29,8 +41,41 @@ impl Keyframe {
             offset,
             id,
             interpolation,
+            temporal_ease: None,
         }
     }
+
+    pub fn with_temporal_ease(mut self, temporal_ease: TemporalEase) -> Self {
+        self.temporal_ease = Some(temporal_ease);
+        self
+    }
+
+    /// Whether the segment to the next keyframe is a spring that keeps moving past it, see
+    /// `SpringProperties::run_to_rest`. A temporal ease replaces the spring.
+    fn runs_to_rest(&self, next: &Keyframe<T>) -> bool {
+        self.temporal_ease.is_none()
+            && next.temporal_ease.is_none()
+            && matches!(
+                self.interpolation,
+                Some(InterpolationType::Spring(spring)) if spring.run_to_rest
+            )
+    }
+}
+
+impl<T: Interpolate> Keyframe<T> {
+    /// Interpolation of the segment from this keyframe to the next one.
+    pub fn segment_interpolation(&self, next: &Keyframe<T>) -> InterpolationType {
+        if self.temporal_ease.is_none() && next.temporal_ease.is_none() {
+            return self.interpolation.unwrap_or(InterpolationType::Linear);
+        }
+
+        InterpolationType::CubicBezier(TemporalEase::segment_curve(
+            self.temporal_ease.map(|ease| ease.ease_out),
+            next.temporal_ease.map(|ease| ease.ease_in),
+            self.value.difference(&next.value),
+            next.offset - self.offset,
+        ))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -42,117 +87,626 @@ pub struct RenderedKeyframe {
     pub abs_distance_from_curr: i32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub struct Keyframes {
-    pub values: Vec<Keyframe>,
+/// How a property behaves before its first and after its last keyframe.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+pub enum Extrapolation {
+    /// Holds the value of the closest keyframe.
+    #[default]
+    Constant,
+    /// Continues with the rate of change the value has at the outermost keyframe.
+    Linear,
+    /// Repeats the keyframes.
+    Cycle,
+    /// Repeats the keyframes, playing every other repetition backwards.
+    PingPong,
+    /// Repeats the keyframes, each repetition is offset by the change in value across the keyframes.
+    CycleWithOffset,
+}
+
+/// Where a time outside of the keyframes maps to, based on the extrapolation.
+enum ExtrapolatedTime {
+    /// Evaluate the keyframes at `time` and add the change across all keyframes `offset_cycles`
+    /// times, `direction` is negative when the keyframes play backwards.
+    Keyframes {
+        time: f32,
+        offset_cycles: f32,
+        direction: f32,
+    },
+    /// Continue the segment starting at the keyframe with the given index with the velocity it
+    /// has at the outermost keyframe, `progress` and `velocity` are relative to the segment.
+    Linear {
+        segment: usize,
+        progress: f32,
+        velocity: f32,
+    },
+}
+
+/// Position within the segment between two keyframes, see [`Keyframes::segment_at_time`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SegmentProgress {
+    /// Index of the keyframe the segment starts at.
+    pub index: usize,
+    /// Eased progress from the start to the end keyframe of the segment.
+    pub progress: f32,
+    /// Rate of change of `progress` per second.
+    pub velocity: f32,
+}
+
+/// Keyframes of a single property, kept sorted by their offset so lookups can use a binary search.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "UnsortedKeyframes<T>")]
+pub struct Keyframes<T = Float> {
+    /// Private so the keyframes can only change through methods that keep them sorted.
+    values: Vec<Keyframe<T>>,
+    pub pre_extrapolation: Extrapolation,
+    pub post_extrapolation: Extrapolation,
+    /// Indices of the segments that run to rest, so lookups don't have to check every earlier
+    /// segment. Updated whenever the keyframes are sorted.
+    #[serde(skip)]
+    rest_segments: Vec<usize>,
+}
+
+#[derive(Deserialize)]
+struct UnsortedKeyframes<T> {
+    values: Vec<Keyframe<T>>,
+    #[serde(default)]
+    pre_extrapolation: Extrapolation,
+    #[serde(default)]
+    post_extrapolation: Extrapolation,
+}
+
+/// The cached segments are derived from the keyframes, so they aren't compared.
+impl<T: PartialEq> PartialEq for Keyframes<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.values == other.values
+            && self.pre_extrapolation == other.pre_extrapolation
+            && self.post_extrapolation == other.post_extrapolation
+    }
+}
+
+/// Mutable access to the keyframes, which are sorted again once it is dropped.
+pub struct KeyframesMut<'a, T> {
+    keyframes: &'a mut Keyframes<T>,
+}
+
+impl<T> Deref for KeyframesMut<'_, T> {
+    type Target = Vec<Keyframe<T>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.keyframes.values
+    }
+}
+
+impl<T> DerefMut for KeyframesMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.keyframes.values
+    }
+}
+
+impl<T> Drop for KeyframesMut<'_, T> {
+    fn drop(&mut self) {
+        self.keyframes.sort();
+    }
+}
+
+impl<T> From<UnsortedKeyframes<T>> for Keyframes<T> {
+    fn from(keyframes: UnsortedKeyframes<T>) -> Self {
+        Keyframes::new(keyframes.values)
+            .with_extrapolation(keyframes.pre_extrapolation, keyframes.post_extrapolation)
+    }
+}
+
+impl<T> Keyframes<T> {
+    pub fn new(values: Vec<Keyframe<T>>) -> Self {
+        let mut keyframes = Keyframes {
+            values,
+            pre_extrapolation: Extrapolation::Constant,
+            post_extrapolation: Extrapolation::Constant,
+            rest_segments: Vec::new(),
+        };
+        keyframes.sort();
+        keyframes
+    }
+
+    pub fn with_extrapolation(
+        mut self,
+        pre_extrapolation: Extrapolation,
+        post_extrapolation: Extrapolation,
+    ) -> Self {
+        self.pre_extrapolation = pre_extrapolation;
+        self.post_extrapolation = post_extrapolation;
+        self
+    }
+
+    /// The keyframes sorted by their offset.
+    pub fn values(&self) -> &[Keyframe<T>] {
+        &self.values
+    }
+
+    /// Changes the keyframes in place, they are sorted again afterwards.
+    pub fn values_mut(&mut self) -> KeyframesMut<'_, T> {
+        KeyframesMut { keyframes: self }
+    }
+
+    pub fn set_values(&mut self, values: Vec<Keyframe<T>>) {
+        self.values = values;
+        self.sort();
+    }
+
+    pub fn remove(&mut self, index: usize) -> Keyframe<T> {
+        let keyframe = self.values.remove(index);
+        self.update_rest_segments();
+        keyframe
+    }
+
+    /// Inserts the keyframe after all keyframes with a smaller or equal offset.
+    pub fn insert(&mut self, keyframe: Keyframe<T>) {
+        let index = self
+            .values
+            .partition_point(|value| value.offset <= keyframe.offset);
+
+        self.values.insert(index, keyframe);
+        self.update_rest_segments();
+    }
+
+    pub fn sort(&mut self) {
+        self.values.sort_by(|a, b| a.offset.total_cmp(&b.offset));
+        self.update_rest_segments();
+    }
+
+    fn update_rest_segments(&mut self) {
+        self.rest_segments = self
+            .values
+            .windows(2)
+            .enumerate()
+            .filter(|(_, segment)| segment[0].runs_to_rest(&segment[1]))
+            .map(|(index, _)| index)
+            .collect();
+    }
+
+    /// Moves every keyframe to the offset `quantize` maps its offset to. Keyframes that land on
+    /// the same time as the keyframe before them are dropped, so the earliest one wins.
+    pub fn quantize(&mut self, quantize: &dyn Fn(f32) -> f32) {
+        for keyframe in &mut self.values {
+            keyframe.offset = quantize(keyframe.offset);
+        }
+
+        self.sort();
+        self.values
+            .dedup_by(|keyframe, previous| is_same_time(keyframe.offset, previous.offset));
+        self.update_rest_segments();
+    }
 }
 
-impl Keyframes {
+impl<T: Interpolate> Keyframes<T> {
+    fn extrapolate_time(&self, time: f32, animation_data: &AnimationData) -> ExtrapolatedTime {
+        let inside = ExtrapolatedTime::Keyframes {
+            time,
+            offset_cycles: 0.0,
+            direction: 1.0,
+        };
+
+        let (Some(first_keyframe), Some(last_keyframe)) = (self.values.first(), self.values.last())
+        else {
+            return inside;
+        };
+
+        let start = animation_data.offset + first_keyframe.offset;
+        let end = animation_data.offset + last_keyframe.offset;
+        let duration = end - start;
+
+        let extrapolation = if time < start && !is_same_time(time, start) {
+            self.pre_extrapolation
+        } else if time > end && !is_same_time(time, end) {
+            self.post_extrapolation
+        } else {
+            Extrapolation::Constant
+        };
+
+        if duration <= 0.0 || is_same_time(start, end) {
+            return inside;
+        }
+
+        let cycles = ((time - start) / duration).floor();
+        let cycle_time = (time - start).rem_euclid(duration);
+
+        match extrapolation {
+            Extrapolation::Constant => inside,
+            Extrapolation::Linear => {
+                let (segment, progress, boundary) = if time < start {
+                    (0, 0.0, start)
+                } else {
+                    (self.values.len() - 2, 1.0, end)
+                };
+
+                let segment_duration =
+                    self.values[segment + 1].offset - self.values[segment].offset;
+
+                if segment_duration <= 0.0 {
+                    return inside;
+                }
+
+                let velocity =
+                    self.segment_ease_velocity(segment, progress, progress * segment_duration);
+
+                ExtrapolatedTime::Linear {
+                    segment,
+                    progress: progress + velocity * (time - boundary),
+                    velocity,
+                }
+            }
+            Extrapolation::Cycle => ExtrapolatedTime::Keyframes {
+                time: start + cycle_time,
+                offset_cycles: 0.0,
+                direction: 1.0,
+            },
+            Extrapolation::PingPong => {
+                if cycles.rem_euclid(2.0) == 0.0 {
+                    ExtrapolatedTime::Keyframes {
+                        time: start + cycle_time,
+                        offset_cycles: 0.0,
+                        direction: 1.0,
+                    }
+                } else {
+                    ExtrapolatedTime::Keyframes {
+                        time: end - cycle_time,
+                        offset_cycles: 0.0,
+                        direction: -1.0,
+                    }
+                }
+            }
+            Extrapolation::CycleWithOffset => ExtrapolatedTime::Keyframes {
+                time: start + cycle_time,
+                offset_cycles: cycles,
+                direction: 1.0,
+            },
+        }
+    }
+
     pub fn get_value_at_frame(
         &self,
         curr_frame: i32,
         animation_data: &AnimationData,
-        fps: i16,
-    ) -> f32 {
-        let keyframe_count = self.values.len();
-
-        if keyframe_count > 0 {
-            let mut rendered_keyframes: Vec<RenderedKeyframe> = self
-                .values
-                .to_vec()
-                .into_iter()
-                .enumerate()
-                .map(|(index, keyframe)| {
-                    render_keyframe(keyframe, animation_data, index, curr_frame, fps)
-                })
-                .collect();
-
-            rendered_keyframes
-                .sort_by(|a, b| a.abs_distance_from_curr.cmp(&b.abs_distance_from_curr));
-
-            let closest_keyframe = rendered_keyframes.get(0).unwrap();
-
-            let result = match (closest_keyframe.distance_from_curr).cmp(&0) {
-                Ordering::Equal => closest_keyframe.keyframe.value,
-                Ordering::Greater => {
-                    if closest_keyframe.absolute_frame == curr_frame {
-                        return closest_keyframe.keyframe.value;
-                    } else {
-                        let previous_keyframe =
-                            rendered_keyframes.to_vec().into_iter().find(|keyframe| {
-                                if closest_keyframe.index > 0 {
-                                    keyframe.index == closest_keyframe.index - 1
-                                } else {
-                                    false
-                                }
-                            });
-
-                        if let Some(previous_keyframe) = previous_keyframe {
-                            let interpolation = match previous_keyframe.keyframe.interpolation {
-                                Some(val) => val,
-                                None => InterpolationType::Linear,
-                            };
-
-                            let interpolated_value = interpolate_rendered_keyframes(
-                                &previous_keyframe,
-                                closest_keyframe,
-                                curr_frame,
-                                interpolation,
-                                fps,
-                            );
-
-                            return interpolated_value;
-                        } else {
-                            if closest_keyframe.absolute_frame > curr_frame {
-                                return closest_keyframe.keyframe.value;
-                            } else {
-                                return 0.0;
-                            }
-                        }
+        fps: FrameRate,
+    ) -> T {
+        self.get_value_at_time(frame_to_timestamp(curr_frame as f32, fps), animation_data)
+    }
+
+    /// Evaluates the keyframes at the given time in seconds, which doesn't have to fall on a frame.
+    pub fn get_value_at_time(&self, time: f32, animation_data: &AnimationData) -> T {
+        self.get_value_at_time_with(time, animation_data, T::interpolate)
+    }
+
+    /// Like [`Keyframes::get_value_at_time`], but fails instead of returning the default value
+    /// when there are no keyframes.
+    pub fn try_get_value_at_time(
+        &self,
+        time: f32,
+        animation_data: &AnimationData,
+    ) -> CreatorResult<T> {
+        if self.values.is_empty() {
+            return Err(CreatorError::EmptyKeyframes);
+        }
+
+        Ok(self.get_value_at_time(time, animation_data))
+    }
+
+    /// Like [`Keyframes::get_value_at_time`], but mixes two keyframe values with the given
+    /// function instead of [`Interpolate::interpolate`].
+    pub fn get_value_at_time_with(
+        &self,
+        time: f32,
+        animation_data: &AnimationData,
+        interpolate: impl Fn(&T, &T, f32) -> T,
+    ) -> T {
+        match self.extrapolate_time(time, animation_data) {
+            ExtrapolatedTime::Keyframes {
+                time,
+                offset_cycles,
+                ..
+            } => {
+                let value = self.interpolate_at_time(time, animation_data, interpolate);
+
+                match (self.values.first(), self.values.last()) {
+                    (Some(first), Some(last)) if offset_cycles != 0.0 => {
+                        value.accumulate(&first.value, &last.value, offset_cycles)
                     }
+                    _ => value,
                 }
-                Ordering::Less => {
-                    if closest_keyframe.absolute_frame == curr_frame {
-                        return closest_keyframe.keyframe.value;
-                    } else {
-                        let next_keyframe = rendered_keyframes
-                            .to_vec()
-                            .into_iter()
-                            .find(|keyframe| keyframe.index == closest_keyframe.index + 1);
-
-                        if let Some(next_keyframe) = next_keyframe {
-                            let interpolation = match closest_keyframe.keyframe.interpolation {
-                                Some(val) => val,
-                                None => InterpolationType::Linear,
-                            };
-
-                            let interpolated_value = interpolate_rendered_keyframes(
-                                closest_keyframe,
-                                &next_keyframe,
-                                curr_frame,
-                                interpolation,
-                                fps,
-                            );
-
-                            return interpolated_value;
-                        } else {
-                            if closest_keyframe.absolute_frame < curr_frame {
-                                return closest_keyframe.keyframe.value;
-                            } else {
-                                return 0.0;
-                            }
-                        }
-                    }
+            }
+            ExtrapolatedTime::Linear {
+                segment, progress, ..
+            } => interpolate(
+                &self.values[segment].value,
+                &self.values[segment + 1].value,
+                progress,
+            ),
+        }
+    }
+
+    fn interpolate_at_time(
+        &self,
+        time: f32,
+        animation_data: &AnimationData,
+        interpolate: impl Fn(&T, &T, f32) -> T,
+    ) -> T {
+        let absolute_time = |keyframe: &Keyframe<T>| animation_data.offset + keyframe.offset;
+
+        // Index of the first keyframe at or after the given time
+        let index = self.values.partition_point(|keyframe| {
+            let keyframe_time = absolute_time(keyframe);
+            keyframe_time < time && !is_same_time(keyframe_time, time)
+        });
+
+        let previous_keyframe = index.checked_sub(1).and_then(|i| self.values.get(i));
+        let next_keyframe = self.values.get(index);
+
+        let value = match (previous_keyframe, next_keyframe) {
+            (_, Some(next_keyframe)) if is_same_time(absolute_time(next_keyframe), time) => {
+                next_keyframe.value.clone()
+            }
+            (Some(previous_keyframe), Some(next_keyframe)) => {
+                let elapsed = time - absolute_time(previous_keyframe);
+                let progress = elapsed / (next_keyframe.offset - previous_keyframe.offset);
+
+                interpolate(
+                    &previous_keyframe.value,
+                    &next_keyframe.value,
+                    self.segment_ease(index - 1, progress, elapsed),
+                )
+            }
+            (None, Some(keyframe)) | (Some(keyframe), None) => keyframe.value.clone(),
+            (None, None) => T::default(),
+        };
+
+        self.springs_running_to_rest(time, animation_data)
+            .fold(value, |value, index| {
+                let (from, to) = (&self.values[index], &self.values[index + 1]);
+                let elapsed = time - absolute_time(from);
+                let spring_value = interpolate(
+                    &from.value,
+                    &to.value,
+                    self.segment_ease(index, 1.0, elapsed),
+                );
+
+                value.accumulate(&to.value, &spring_value, 1.0)
+            })
+    }
+
+    /// Segment the time falls into, at a keyframe the segment leaving it is used and outside of
+    /// the keyframes the closest segment. None when there are less than two keyframes.
+    pub fn segment_at_time(
+        &self,
+        time: f32,
+        animation_data: &AnimationData,
+    ) -> Option<SegmentProgress> {
+        let last_segment = self.values.len().checked_sub(2)?;
+
+        let (time, direction) = match self.extrapolate_time(time, animation_data) {
+            ExtrapolatedTime::Keyframes {
+                time, direction, ..
+            } => (time, direction),
+            ExtrapolatedTime::Linear {
+                segment,
+                progress,
+                velocity,
+            } => {
+                return Some(SegmentProgress {
+                    index: segment,
+                    progress,
+                    velocity,
+                });
+            }
+        };
+
+        let absolute_time = |keyframe: &Keyframe<T>| animation_data.offset + keyframe.offset;
+
+        let index = self.values.partition_point(|keyframe| {
+            let keyframe_time = absolute_time(keyframe);
+            keyframe_time < time && !is_same_time(keyframe_time, time)
+        });
+
+        let is_at_keyframe = self
+            .values
+            .get(index)
+            .is_some_and(|keyframe| is_same_time(absolute_time(keyframe), time));
+
+        let segment = if is_at_keyframe {
+            index
+        } else {
+            index.saturating_sub(1)
+        };
+
+        let Some(to) = self.values.get(segment + 1) else {
+            return Some(SegmentProgress {
+                index: last_segment,
+                progress: 1.0,
+                velocity: 0.0,
+            });
+        };
+        let from = &self.values[segment];
+
+        let duration = to.offset - from.offset;
+        let elapsed = time - absolute_time(from);
+
+        if elapsed < 0.0 || duration <= 0.0 {
+            return Some(SegmentProgress {
+                index: segment,
+                progress: 0.0,
+                velocity: 0.0,
+            });
+        }
+
+        let progress = elapsed / duration;
+
+        Some(SegmentProgress {
+            index: segment,
+            progress: self.segment_ease(segment, progress, elapsed),
+            velocity: self.segment_ease_velocity(segment, progress, elapsed) * direction,
+        })
+    }
+
+    /// Indices of the spring segments that ended at or before the given time but keep moving
+    /// until they come to rest, see `SpringProperties::run_to_rest`.
+    fn springs_running_to_rest(
+        &self,
+        time: f32,
+        animation_data: &AnimationData,
+    ) -> impl Iterator<Item = usize> + '_ {
+        let ended_keyframes = self.values.partition_point(|keyframe| {
+            let keyframe_time = animation_data.offset + keyframe.offset;
+            keyframe_time < time || is_same_time(keyframe_time, time)
+        });
+        let ended_segments = self
+            .rest_segments
+            .partition_point(|&index| index + 1 < ended_keyframes);
+
+        self.rest_segments[..ended_segments].iter().copied()
+    }
+
+    /// Eased progress of the segment starting at the keyframe with the given index.
+    fn segment_ease(&self, index: usize, progress: f32, elapsed: f32) -> f32 {
+        match self.values[index].segment_interpolation(&self.values[index + 1]) {
+            InterpolationType::Spring(spring) if spring.inherit_velocity => {
+                calculate_spring_progress(elapsed, self.segment_start_velocity(index), &spring)
+            }
+            interpolation => interpolation.ease(progress, elapsed),
+        }
+    }
+
+    /// Rate of change of [`Keyframes::segment_ease`] per second.
+    fn segment_ease_velocity(&self, index: usize, progress: f32, elapsed: f32) -> f32 {
+        let duration = self.values[index + 1].offset - self.values[index].offset;
+
+        match self.values[index].segment_interpolation(&self.values[index + 1]) {
+            InterpolationType::Spring(spring) if spring.inherit_velocity => {
+                calculate_spring_progress_velocity(
+                    elapsed,
+                    self.segment_start_velocity(index),
+                    &spring,
+                )
+            }
+            interpolation => interpolation.ease_velocity(progress, elapsed, duration),
+        }
+    }
+
+    fn inherits_velocity(&self, index: usize) -> bool {
+        matches!(
+            self.values[index].segment_interpolation(&self.values[index + 1]),
+            InterpolationType::Spring(spring) if spring.inherit_velocity
+        )
+    }
+
+    /// Progress per second the segment starting at the given index starts with. Springs that
+    /// inherit the velocity continue with the velocity of the value at the end of the previous
+    /// segment, unless it runs to rest. Every other segment starts at rest.
+    fn segment_start_velocity(&self, index: usize) -> f32 {
+        // The first segment of the chain of inheriting segments starts at rest
+        let mut start = index;
+
+        while start > 0 && self.inherits_velocity(start) {
+            start -= 1;
+        }
+
+        let mut velocity = 0.0;
+
+        for segment in start..index {
+            let (from, to, next) = (
+                &self.values[segment],
+                &self.values[segment + 1],
+                &self.values[segment + 2],
+            );
+            let duration = to.offset - from.offset;
+
+            let end_velocity = match from.segment_interpolation(to) {
+                // The remaining motion is already added on top of the next segment
+                InterpolationType::Spring(spring) if spring.run_to_rest => 0.0,
+                InterpolationType::Spring(spring) if spring.inherit_velocity => {
+                    calculate_spring_progress_velocity(duration, velocity, &spring)
                 }
+                interpolation => interpolation.ease_velocity(1.0, duration, duration),
             };
 
-            result
-        } else {
-            0.0
+            // Converts the velocity into progress of the next segment
+            velocity = match (
+                from.value.difference(&to.value),
+                to.value.difference(&next.value),
+            ) {
+                (Some(change), Some(next_change)) if next_change.abs() > f32::EPSILON => {
+                    end_velocity * change / next_change
+                }
+                _ => 0.0,
+            };
         }
+
+        velocity
     }
+}
 
-    pub fn sort(&mut self) {
-        self.values.sort_by(|a, b| a.offset.total_cmp(&b.offset));
+impl Keyframes<Float> {
+    pub fn get_velocity_at_frame(
+        &self,
+        curr_frame: i32,
+        animation_data: &AnimationData,
+        fps: FrameRate,
+    ) -> f32 {
+        self.get_velocity_at_time(frame_to_timestamp(curr_frame as f32, fps), animation_data)
+    }
+
+    /// Instantaneous rate of change in units per second, at a keyframe the segment leaving it is used.
+    pub fn get_velocity_at_time(&self, time: f32, animation_data: &AnimationData) -> f32 {
+        match self.extrapolate_time(time, animation_data) {
+            ExtrapolatedTime::Keyframes {
+                time, direction, ..
+            } => self.velocity_at_time(time, animation_data) * direction,
+            ExtrapolatedTime::Linear {
+                segment, velocity, ..
+            } => (self.values[segment + 1].value - self.values[segment].value) * velocity,
+        }
+    }
+
+    fn velocity_at_time(&self, time: f32, animation_data: &AnimationData) -> f32 {
+        let absolute_time = |keyframe: &Keyframe| animation_data.offset + keyframe.offset;
+
+        let index = self.values.partition_point(|keyframe| {
+            let keyframe_time = absolute_time(keyframe);
+            keyframe_time < time && !is_same_time(keyframe_time, time)
+        });
+
+        let is_at_keyframe = self
+            .values
+            .get(index)
+            .is_some_and(|keyframe| is_same_time(absolute_time(keyframe), time));
+
+        let segment_index = if is_at_keyframe {
+            Some(index)
+        } else {
+            index.checked_sub(1)
+        };
+
+        let Some(segment) = segment_index.filter(|&i| i + 1 < self.values.len()) else {
+            return 0.0;
+        };
+
+        let (from, to) = (&self.values[segment], &self.values[segment + 1]);
+
+        let duration = to.offset - from.offset;
+        let elapsed = time - absolute_time(from);
+        let progress = if duration > 0.0 {
+            elapsed / duration
+        } else {
+            0.0
+        };
+
+        let velocity =
+            (to.value - from.value) * self.segment_ease_velocity(segment, progress, elapsed);
+
+        self.springs_running_to_rest(time, animation_data)
+            .fold(velocity, |velocity, index| {
+                let (from, to) = (&self.values[index], &self.values[index + 1]);
+                let elapsed = time - absolute_time(from);
+
+                velocity + (to.value - from.value) * self.segment_ease_velocity(index, 1.0, elapsed)
+            })
     }
 }
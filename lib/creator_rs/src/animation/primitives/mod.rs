@@ -1,8 +1,15 @@
+pub mod audio;
+pub mod bake;
+pub mod drivers;
 pub mod entities;
+pub mod frame_rate;
 pub mod interpolations;
 pub mod keyframe;
+pub mod motion_path;
 pub mod paint;
+pub mod path;
 pub mod tests;
+pub mod timecode;
 pub mod transform;
 pub mod utils;
 pub mod values;
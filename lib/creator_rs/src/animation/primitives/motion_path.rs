@@ -0,0 +1,270 @@
+use serde::{Deserialize, Serialize};
+
+use super::{
+    entities::common::AnimationData,
+    keyframe::{Keyframe, Keyframes, SegmentProgress},
+    values::{interpolate::Interpolate, values::FloatVec2},
+};
+
+/// Amount of samples used to approximate the arc length of a path segment.
+const ARC_LENGTH_SAMPLES: usize = 64;
+
+/// A point of a motion path together with the handles of the curve around it, the tangents
+/// are relative to the point.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct SpatialPoint {
+    pub point: FloatVec2,
+    #[serde(default)]
+    pub in_tangent: FloatVec2,
+    #[serde(default)]
+    pub out_tangent: FloatVec2,
+}
+
+impl SpatialPoint {
+    pub fn new(point: FloatVec2, in_tangent: FloatVec2, out_tangent: FloatVec2) -> Self {
+        SpatialPoint {
+            point,
+            in_tangent,
+            out_tangent,
+        }
+    }
+}
+
+/// Moves along the curve between the points, `t` is the fraction of its arc length.
+impl Interpolate for SpatialPoint {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        SpatialPoint::new(
+            PathSegment::between(self, other).point_at_distance(t),
+            (0.0, 0.0),
+            (0.0, 0.0),
+        )
+    }
+
+    fn accumulate(&self, from: &Self, to: &Self, times: f32) -> Self {
+        SpatialPoint {
+            point: self.point.accumulate(&from.point, &to.point, times),
+            ..*self
+        }
+    }
+
+    fn difference(&self, other: &Self) -> Option<f32> {
+        Some(PathSegment::between(self, other).length())
+    }
+}
+
+/// Spatial keyframes connected by cubic bezier segments. The keyframes are evaluated like any
+/// other keyframes, the eased progress of a segment is how far along its arc length the value is.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MotionPath {
+    pub keyframes: Keyframes<SpatialPoint>,
+}
+
+/// A single cubic bezier segment of a motion path.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PathSegment {
+    pub start: FloatVec2,
+    pub start_control: FloatVec2,
+    pub end_control: FloatVec2,
+    pub end: FloatVec2,
+}
+
+impl PathSegment {
+    pub fn between(start: &SpatialPoint, end: &SpatialPoint) -> Self {
+        PathSegment {
+            start: start.point,
+            start_control: add(start.point, start.out_tangent),
+            end_control: add(end.point, end.in_tangent),
+            end: end.point,
+        }
+    }
+
+    pub fn point(&self, t: f32) -> FloatVec2 {
+        let mt = 1.0 - t;
+        let a = mt * mt * mt;
+        let b = 3.0 * mt * mt * t;
+        let c = 3.0 * mt * t * t;
+        let d = t * t * t;
+
+        (
+            a * self.start.0 + b * self.start_control.0 + c * self.end_control.0 + d * self.end.0,
+            a * self.start.1 + b * self.start_control.1 + c * self.end_control.1 + d * self.end.1,
+        )
+    }
+
+    pub fn derivative(&self, t: f32) -> FloatVec2 {
+        let mt = 1.0 - t;
+        let a = 3.0 * mt * mt;
+        let b = 6.0 * mt * t;
+        let c = 3.0 * t * t;
+
+        (
+            a * (self.start_control.0 - self.start.0)
+                + b * (self.end_control.0 - self.start_control.0)
+                + c * (self.end.0 - self.end_control.0),
+            a * (self.start_control.1 - self.start.1)
+                + b * (self.end_control.1 - self.start_control.1)
+                + c * (self.end.1 - self.end_control.1),
+        )
+    }
+
+    /// Direction of the curve at the given parameter, falls back to the chord for degenerate handles.
+    pub fn direction(&self, t: f32) -> FloatVec2 {
+        let derivative = self.derivative(t);
+
+        normalize(derivative)
+            .unwrap_or_else(|| normalize(sub(self.end, self.start)).unwrap_or((0.0, 0.0)))
+    }
+
+    /// Cumulative arc lengths at evenly spaced curve parameters.
+    fn arc_lengths(&self) -> [f32; ARC_LENGTH_SAMPLES + 1] {
+        let mut lengths = [0.0; ARC_LENGTH_SAMPLES + 1];
+        let mut previous_point = self.start;
+
+        for (index, length) in lengths.iter_mut().enumerate().skip(1) {
+            let point = self.point(index as f32 / ARC_LENGTH_SAMPLES as f32);
+            *length = length_of(sub(point, previous_point));
+            previous_point = point;
+        }
+
+        for index in 1..lengths.len() {
+            lengths[index] += lengths[index - 1];
+        }
+
+        lengths
+    }
+
+    pub fn length(&self) -> f32 {
+        self.arc_lengths()[ARC_LENGTH_SAMPLES]
+    }
+
+    /// Curve parameter at the given fraction of the arc length.
+    pub fn parameter_at_distance(&self, fraction: f32) -> f32 {
+        let lengths = self.arc_lengths();
+        let total_length = lengths[ARC_LENGTH_SAMPLES];
+
+        if total_length <= f32::EPSILON {
+            return fraction.clamp(0.0, 1.0);
+        }
+
+        let distance = fraction.clamp(0.0, 1.0) * total_length;
+        let index = lengths
+            .partition_point(|length| *length < distance)
+            .clamp(1, ARC_LENGTH_SAMPLES);
+
+        let segment_start = lengths[index - 1];
+        let segment_length = lengths[index] - segment_start;
+        let segment_progress = if segment_length > 0.0 {
+            (distance - segment_start) / segment_length
+        } else {
+            0.0
+        };
+
+        (index as f32 - 1.0 + segment_progress) / ARC_LENGTH_SAMPLES as f32
+    }
+
+    /// Point at the given fraction of the arc length, values outside of 0..1 continue
+    /// along the tangent of the closest end.
+    pub fn point_at_distance(&self, fraction: f32) -> FloatVec2 {
+        if (0.0..=1.0).contains(&fraction) {
+            return self.point(self.parameter_at_distance(fraction));
+        }
+
+        let overshoot = if fraction < 0.0 {
+            fraction
+        } else {
+            fraction - 1.0
+        };
+        let t = fraction.clamp(0.0, 1.0);
+        let direction = self.direction(t);
+        let distance = overshoot * self.length();
+        let point = self.point(t);
+
+        (
+            point.0 + direction.0 * distance,
+            point.1 + direction.1 * distance,
+        )
+    }
+}
+
+impl MotionPath {
+    pub fn new(keyframes: Vec<Keyframe<SpatialPoint>>) -> Self {
+        MotionPath {
+            keyframes: Keyframes::new(keyframes),
+        }
+    }
+
+    pub fn sort(&mut self) {
+        self.keyframes.sort();
+    }
+
+    /// See [`Keyframes::quantize`].
+    pub fn quantize(&mut self, quantize: &dyn Fn(f32) -> f32) {
+        self.keyframes.quantize(quantize);
+    }
+
+    pub fn get_value_at_time(&self, time: f32, animation_data: &AnimationData) -> FloatVec2 {
+        self.keyframes.get_value_at_time(time, animation_data).point
+    }
+
+    /// Velocity along the path in units per second, at a keyframe the segment leaving it is used.
+    pub fn get_velocity_at_time(&self, time: f32, animation_data: &AnimationData) -> FloatVec2 {
+        let Some((segment, progress)) = self.segment_at_time(time, animation_data) else {
+            return (0.0, 0.0);
+        };
+
+        let direction = segment.direction(segment.parameter_at_distance(progress.progress));
+        let speed = segment.length() * progress.velocity;
+
+        (direction.0 * speed, direction.1 * speed)
+    }
+
+    /// Angle of the path's tangent in degrees, used to orient a layer along its motion path.
+    /// Outside of the keyframes the tangent of the closest segment is used.
+    pub fn get_orientation_at_time(&self, time: f32, animation_data: &AnimationData) -> f32 {
+        let direction = self
+            .segment_at_time(time, animation_data)
+            .map(|(segment, progress)| {
+                segment.direction(segment.parameter_at_distance(progress.progress))
+            })
+            .unwrap_or((0.0, 0.0));
+
+        direction.1.atan2(direction.0).to_degrees()
+    }
+
+    /// Curve of the segment the time falls into with the eased distance along it.
+    fn segment_at_time(
+        &self,
+        time: f32,
+        animation_data: &AnimationData,
+    ) -> Option<(PathSegment, SegmentProgress)> {
+        let progress = self.keyframes.segment_at_time(time, animation_data)?;
+        let (from, to) = (
+            &self.keyframes.values()[progress.index],
+            &self.keyframes.values()[progress.index + 1],
+        );
+
+        Some((PathSegment::between(&from.value, &to.value), progress))
+    }
+}
+
+fn add(a: FloatVec2, b: FloatVec2) -> FloatVec2 {
+    (a.0 + b.0, a.1 + b.1)
+}
+
+fn sub(a: FloatVec2, b: FloatVec2) -> FloatVec2 {
+    (a.0 - b.0, a.1 - b.1)
+}
+
+fn length_of(a: FloatVec2) -> f32 {
+    a.0.hypot(a.1)
+}
+
+fn normalize(a: FloatVec2) -> Option<FloatVec2> {
+    let length = length_of(a);
+
+    if length > f32::EPSILON {
+        Some((a.0 / length, a.1 / length))
+    } else {
+        None
+    }
+}
@@ -1,6 +1,15 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+use super::{
+    entities::common::AnimationData,
+    values::{
+        animated_color::AnimatedColor,
+        animated_values::{AnimatedFloat, AnimatedValue},
+    },
+};
+
+/// Defaults to transparent black.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct Color {
     value: (u8, u8, u8, f32),
 }
@@ -11,6 +20,182 @@ impl Color {
             value: (red, green, blue, alpha),
         }
     }
+
+    pub fn value(&self) -> (u8, u8, u8, f32) {
+        self.value
+    }
+
+    /// Mixes towards the other color, the channels are interpolated in the given color space
+    /// while alpha is always interpolated linearly. Values of `t` outside of 0..1 extrapolate.
+    pub fn mix(&self, other: &Color, t: f32, color_space: ColorSpace) -> Color {
+        let from = self.to_srgb();
+        let to = other.to_srgb();
+
+        let mixed = match color_space {
+            ColorSpace::Srgb => lerp3(from, to, t),
+            ColorSpace::LinearRgb => {
+                linear_to_srgb(lerp3(srgb_to_linear(from), srgb_to_linear(to), t))
+            }
+            ColorSpace::Hsl => hsl_to_srgb(mix_hsl(srgb_to_hsl(from), srgb_to_hsl(to), t)),
+            ColorSpace::Oklab => linear_to_srgb(oklab_to_linear(lerp3(
+                linear_to_oklab(srgb_to_linear(from)),
+                linear_to_oklab(srgb_to_linear(to)),
+                t,
+            ))),
+        };
+
+        let alpha = self.value.3 + (other.value.3 - self.value.3) * t;
+
+        Color::new(
+            to_channel(mixed[0]),
+            to_channel(mixed[1]),
+            to_channel(mixed[2]),
+            alpha.clamp(0.0, 1.0),
+        )
+    }
+
+    fn to_srgb(&self) -> [f32; 3] {
+        [
+            self.value.0 as f32 / 255.0,
+            self.value.1 as f32 / 255.0,
+            self.value.2 as f32 / 255.0,
+        ]
+    }
+}
+
+/// The color space two colors are mixed in.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum ColorSpace {
+    /// Mixes the gamma encoded channels, matches what CSS and most design tools do.
+    #[default]
+    Srgb,
+    /// Mixes physical light intensities, mixtures look brighter than in sRGB.
+    LinearRgb,
+    /// Mixes hue, saturation and lightness, taking the shorter way around the hue circle.
+    Hsl,
+    /// Perceptually uniform, keeps the perceived lightness and saturation of the mixtures even.
+    Oklab,
+}
+
+fn to_channel(value: f32) -> u8 {
+    (value * 255.0).round().clamp(0.0, 255.0) as u8
+}
+
+fn lerp3(from: [f32; 3], to: [f32; 3], t: f32) -> [f32; 3] {
+    [
+        from[0] + (to[0] - from[0]) * t,
+        from[1] + (to[1] - from[1]) * t,
+        from[2] + (to[2] - from[2]) * t,
+    ]
+}
+
+fn srgb_to_linear(srgb: [f32; 3]) -> [f32; 3] {
+    srgb.map(|channel| {
+        if channel <= 0.04045 {
+            channel / 12.92
+        } else {
+            ((channel + 0.055) / 1.055).powf(2.4)
+        }
+    })
+}
+
+fn linear_to_srgb(linear: [f32; 3]) -> [f32; 3] {
+    linear.map(|channel| {
+        if channel <= 0.0031308 {
+            channel * 12.92
+        } else {
+            1.055 * channel.powf(1.0 / 2.4) - 0.055
+        }
+    })
+}
+
+// Matrices as published with OKLab, kept at full precision
+#[allow(clippy::excessive_precision)]
+fn linear_to_oklab([r, g, b]: [f32; 3]) -> [f32; 3] {
+    let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+    let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+    let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+
+    [
+        0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+        1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+        0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+    ]
+}
+
+#[allow(clippy::excessive_precision)]
+fn oklab_to_linear([lightness, a, b]: [f32; 3]) -> [f32; 3] {
+    let l = (lightness + 0.3963377774 * a + 0.2158037573 * b).powi(3);
+    let m = (lightness - 0.1055613458 * a - 0.0638541728 * b).powi(3);
+    let s = (lightness - 0.0894841775 * a - 1.2914855480 * b).powi(3);
+
+    [
+        4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s,
+        -1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s,
+        -0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s,
+    ]
+}
+
+/// Converts to hue in degrees, saturation and lightness.
+fn srgb_to_hsl([r, g, b]: [f32; 3]) -> [f32; 3] {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let lightness = (max + min) / 2.0;
+    let delta = max - min;
+
+    if delta <= f32::EPSILON {
+        return [0.0, 0.0, lightness];
+    }
+
+    let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs());
+    let hue = if max == r {
+        ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        (b - r) / delta + 2.0
+    } else {
+        (r - g) / delta + 4.0
+    };
+
+    [hue * 60.0, saturation, lightness]
+}
+
+fn hsl_to_srgb([hue, saturation, lightness]: [f32; 3]) -> [f32; 3] {
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let sector = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (sector.rem_euclid(2.0) - 1.0).abs());
+    let m = lightness - chroma / 2.0;
+
+    let (r, g, b) = match sector as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+
+    [r + m, g + m, b + m]
+}
+
+/// Mixes two HSL colors along the shorter hue arc, grays take the hue of the other color.
+fn mix_hsl(from: [f32; 3], to: [f32; 3], t: f32) -> [f32; 3] {
+    let from_hue = if from[1] <= f32::EPSILON {
+        to[0]
+    } else {
+        from[0]
+    };
+    let to_hue = if to[1] <= f32::EPSILON {
+        from[0]
+    } else {
+        to[0]
+    };
+    let hue_difference = (to_hue - from_hue + 180.0).rem_euclid(360.0) - 180.0;
+
+    [
+        from_hue + hue_difference * t,
+        from[1] + (to[1] - from[1]) * t,
+        from[2] + (to[2] - from[2]) * t,
+    ]
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -50,6 +235,210 @@ pub struct StrokeAndFillStyle {
 pub struct FillStyle {
     pub color: Color,
 }
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum AnimatedPaintStyle {
+    Fill(AnimatedFillStyle),
+    Stroke(AnimatedStrokeStyle),
+    StrokeAndFill(AnimatedStrokeAndFillStyle),
+}
+
+/// A paint whose colors can change over time, static paints deserialize into it as well.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnimatedPaint {
+    pub style: AnimatedPaintStyle,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnimatedTextPaint {
+    pub style: AnimatedPaintStyle,
+    pub align: TextAlign,
+    pub font_name: String,
+    pub size: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnimatedStrokeStyle {
+    pub color: AnimatedColor,
+    pub width: AnimatedFloat,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnimatedStrokeAndFillStyle {
+    pub stroke: AnimatedStrokeStyle,
+    pub fill: AnimatedFillStyle,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnimatedFillStyle {
+    pub color: AnimatedColor,
+}
+
+impl AnimatedPaintStyle {
+    pub fn sort_keyframes(&mut self) {
+        match self {
+            AnimatedPaintStyle::Fill(fill) => fill.sort_keyframes(),
+            AnimatedPaintStyle::Stroke(stroke) => stroke.sort_keyframes(),
+            AnimatedPaintStyle::StrokeAndFill(stroke_and_fill) => {
+                stroke_and_fill.stroke.sort_keyframes();
+                stroke_and_fill.fill.sort_keyframes();
+            }
+        }
+    }
+
+    pub fn quantize_keyframes(&mut self, quantize: &dyn Fn(f32) -> f32) {
+        match self {
+            AnimatedPaintStyle::Fill(fill) => fill.quantize_keyframes(quantize),
+            AnimatedPaintStyle::Stroke(stroke) => stroke.quantize_keyframes(quantize),
+            AnimatedPaintStyle::StrokeAndFill(stroke_and_fill) => {
+                stroke_and_fill.stroke.quantize_keyframes(quantize);
+                stroke_and_fill.fill.quantize_keyframes(quantize);
+            }
+        }
+    }
+
+    pub fn calculate_at_time(&self, time: f32, animation_data: &AnimationData) -> PaintStyle {
+        match self {
+            AnimatedPaintStyle::Fill(fill) => {
+                PaintStyle::Fill(fill.calculate_at_time(time, animation_data))
+            }
+            AnimatedPaintStyle::Stroke(stroke) => {
+                PaintStyle::Stroke(stroke.calculate_at_time(time, animation_data))
+            }
+            AnimatedPaintStyle::StrokeAndFill(stroke_and_fill) => {
+                PaintStyle::StrokeAndFill(StrokeAndFillStyle {
+                    stroke: stroke_and_fill
+                        .stroke
+                        .calculate_at_time(time, animation_data),
+                    fill: stroke_and_fill.fill.calculate_at_time(time, animation_data),
+                })
+            }
+        }
+    }
+}
+
+impl AnimatedPaint {
+    pub fn sort_keyframes(&mut self) {
+        self.style.sort_keyframes();
+    }
+
+    pub fn quantize_keyframes(&mut self, quantize: &dyn Fn(f32) -> f32) {
+        self.style.quantize_keyframes(quantize);
+    }
+
+    pub fn calculate_at_time(&self, time: f32, animation_data: &AnimationData) -> Paint {
+        Paint {
+            style: self.style.calculate_at_time(time, animation_data),
+        }
+    }
+}
+
+impl AnimatedTextPaint {
+    pub fn sort_keyframes(&mut self) {
+        self.style.sort_keyframes();
+    }
+
+    pub fn quantize_keyframes(&mut self, quantize: &dyn Fn(f32) -> f32) {
+        self.style.quantize_keyframes(quantize);
+    }
+
+    pub fn calculate_at_time(&self, time: f32, animation_data: &AnimationData) -> TextPaint {
+        TextPaint {
+            style: self.style.calculate_at_time(time, animation_data),
+            align: self.align.clone(),
+            font_name: self.font_name.clone(),
+            size: self.size,
+        }
+    }
+}
+
+impl AnimatedStrokeStyle {
+    pub fn sort_keyframes(&mut self) {
+        self.color.sort_keyframes();
+        self.width.sort_keyframes();
+    }
+
+    pub fn quantize_keyframes(&mut self, quantize: &dyn Fn(f32) -> f32) {
+        self.color.quantize_keyframes(quantize);
+        self.width.quantize_keyframes(quantize);
+    }
+
+    pub fn calculate_at_time(&self, time: f32, animation_data: &AnimationData) -> StrokeStyle {
+        StrokeStyle {
+            color: self.color.get_value_at_time(time, animation_data),
+            width: self.width.get_value_at_time(time, animation_data).max(0.0),
+        }
+    }
+}
+
+impl AnimatedFillStyle {
+    pub fn sort_keyframes(&mut self) {
+        self.color.sort_keyframes();
+    }
+
+    pub fn quantize_keyframes(&mut self, quantize: &dyn Fn(f32) -> f32) {
+        self.color.quantize_keyframes(quantize);
+    }
+
+    pub fn calculate_at_time(&self, time: f32, animation_data: &AnimationData) -> FillStyle {
+        FillStyle {
+            color: self.color.get_value_at_time(time, animation_data),
+        }
+    }
+}
+
+impl From<PaintStyle> for AnimatedPaintStyle {
+    fn from(style: PaintStyle) -> Self {
+        match style {
+            PaintStyle::Fill(fill) => AnimatedPaintStyle::Fill(fill.into()),
+            PaintStyle::Stroke(stroke) => AnimatedPaintStyle::Stroke(stroke.into()),
+            PaintStyle::StrokeAndFill(stroke_and_fill) => {
+                AnimatedPaintStyle::StrokeAndFill(AnimatedStrokeAndFillStyle {
+                    stroke: stroke_and_fill.stroke.into(),
+                    fill: stroke_and_fill.fill.into(),
+                })
+            }
+        }
+    }
+}
+
+impl From<Paint> for AnimatedPaint {
+    fn from(paint: Paint) -> Self {
+        AnimatedPaint {
+            style: paint.style.into(),
+        }
+    }
+}
+
+impl From<TextPaint> for AnimatedTextPaint {
+    fn from(paint: TextPaint) -> Self {
+        AnimatedTextPaint {
+            style: paint.style.into(),
+            align: paint.align,
+            font_name: paint.font_name,
+            size: paint.size,
+        }
+    }
+}
+
+impl From<StrokeStyle> for AnimatedStrokeStyle {
+    fn from(stroke: StrokeStyle) -> Self {
+        AnimatedStrokeStyle {
+            color: stroke.color.into(),
+            width: stroke.width.into(),
+        }
+    }
+}
+
+impl From<FillStyle> for AnimatedFillStyle {
+    fn from(fill: FillStyle) -> Self {
+        AnimatedFillStyle {
+            color: fill.color.into(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum TextAlign {
     Left,
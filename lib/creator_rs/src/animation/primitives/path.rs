@@ -0,0 +1,474 @@
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    entities::common::AnimationData,
+    values::{
+        animated_values::{AnimatedFloatVec2, AnimatedValue},
+        values::FloatVec2,
+    },
+};
+use crate::error::{CreatorError, CreatorResult};
+
+/// A single drawing command of a path, all points are absolute.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type")]
+pub enum PathCommand {
+    MoveTo {
+        point: FloatVec2,
+    },
+    LineTo {
+        point: FloatVec2,
+    },
+    CubicTo {
+        control1: FloatVec2,
+        control2: FloatVec2,
+        point: FloatVec2,
+    },
+    QuadTo {
+        control: FloatVec2,
+        point: FloatVec2,
+    },
+    /// Draws a line back to the start of the current sub path.
+    Close,
+}
+
+/// A path command whose points are animated independently, so single vertices of a shape can
+/// move over time.
+// Cubic commands are the common case, boxing the points wouldn't save any memory
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type")]
+pub enum AnimatedPathCommand {
+    MoveTo {
+        point: AnimatedFloatVec2,
+    },
+    LineTo {
+        point: AnimatedFloatVec2,
+    },
+    CubicTo {
+        control1: AnimatedFloatVec2,
+        control2: AnimatedFloatVec2,
+        point: AnimatedFloatVec2,
+    },
+    QuadTo {
+        control: AnimatedFloatVec2,
+        point: AnimatedFloatVec2,
+    },
+    Close,
+}
+
+/// Parses SVG path data into commands with static points, ready to be animated.
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub fn parse_svg_path(path: String) -> CreatorResult<Vec<AnimatedPathCommand>> {
+    Ok(PathCommand::parse_svg(&path)?
+        .into_iter()
+        .map(AnimatedPathCommand::from)
+        .collect())
+}
+
+impl From<PathCommand> for AnimatedPathCommand {
+    fn from(command: PathCommand) -> Self {
+        let animated = |(x, y): FloatVec2| AnimatedFloatVec2::new(x, y);
+
+        match command {
+            PathCommand::MoveTo { point } => AnimatedPathCommand::MoveTo {
+                point: animated(point),
+            },
+            PathCommand::LineTo { point } => AnimatedPathCommand::LineTo {
+                point: animated(point),
+            },
+            PathCommand::CubicTo {
+                control1,
+                control2,
+                point,
+            } => AnimatedPathCommand::CubicTo {
+                control1: animated(control1),
+                control2: animated(control2),
+                point: animated(point),
+            },
+            PathCommand::QuadTo { control, point } => AnimatedPathCommand::QuadTo {
+                control: animated(control),
+                point: animated(point),
+            },
+            PathCommand::Close => AnimatedPathCommand::Close,
+        }
+    }
+}
+
+impl AnimatedPathCommand {
+    /// Every animated point of the command with the name of its field, control points come
+    /// before the end point.
+    pub fn points(&self) -> Vec<(&'static str, &AnimatedFloatVec2)> {
+        match self {
+            AnimatedPathCommand::MoveTo { point } | AnimatedPathCommand::LineTo { point } => {
+                vec![("point", point)]
+            }
+            AnimatedPathCommand::CubicTo {
+                control1,
+                control2,
+                point,
+            } => vec![
+                ("control1", control1),
+                ("control2", control2),
+                ("point", point),
+            ],
+            AnimatedPathCommand::QuadTo { control, point } => {
+                vec![("control", control), ("point", point)]
+            }
+            AnimatedPathCommand::Close => Vec::new(),
+        }
+    }
+
+    pub fn points_mut(&mut self) -> Vec<&mut AnimatedFloatVec2> {
+        match self {
+            AnimatedPathCommand::MoveTo { point } | AnimatedPathCommand::LineTo { point } => {
+                vec![point]
+            }
+            AnimatedPathCommand::CubicTo {
+                control1,
+                control2,
+                point,
+            } => vec![control1, control2, point],
+            AnimatedPathCommand::QuadTo { control, point } => vec![control, point],
+            AnimatedPathCommand::Close => Vec::new(),
+        }
+    }
+
+    pub fn sort_keyframes(&mut self) {
+        for point in self.points_mut() {
+            point.sort_keyframes();
+        }
+    }
+
+    pub fn quantize_keyframes(&mut self, quantize: &dyn Fn(f32) -> f32) {
+        for point in self.points_mut() {
+            point.quantize_keyframes(quantize);
+        }
+    }
+
+    pub fn calculate_at_time(&self, time: f32, animation_data: &AnimationData) -> PathCommand {
+        let value = |point: &AnimatedFloatVec2| point.get_value_at_time(time, animation_data);
+
+        match self {
+            AnimatedPathCommand::MoveTo { point } => PathCommand::MoveTo {
+                point: value(point),
+            },
+            AnimatedPathCommand::LineTo { point } => PathCommand::LineTo {
+                point: value(point),
+            },
+            AnimatedPathCommand::CubicTo {
+                control1,
+                control2,
+                point,
+            } => PathCommand::CubicTo {
+                control1: value(control1),
+                control2: value(control2),
+                point: value(point),
+            },
+            AnimatedPathCommand::QuadTo { control, point } => PathCommand::QuadTo {
+                control: value(control),
+                point: value(point),
+            },
+            AnimatedPathCommand::Close => PathCommand::Close,
+        }
+    }
+}
+
+impl PathCommand {
+    /// Parses SVG path data like `M 10 10 L 20 20 Z`. Relative commands and the shorthands
+    /// H, V, S and T are converted to absolute move, line, cubic and quadratic commands, arcs
+    /// are approximated with a cubic command per quarter turn.
+    pub fn parse_svg(path: &str) -> CreatorResult<Vec<PathCommand>> {
+        let mut parser = PathParser { path, position: 0 };
+        let mut commands = Vec::new();
+
+        let mut current = (0.0, 0.0);
+        let mut subpath_start = (0.0, 0.0);
+        let mut previous: Option<PathCommand> = None;
+        let mut letter: Option<char> = None;
+
+        loop {
+            parser.skip_separators();
+
+            if parser.is_at_end() {
+                break;
+            }
+
+            // Numbers without a command repeat the previous one, after a move they are lines
+            let command = match (parser.command(), letter) {
+                (Some(command), _) => command,
+                (None, Some('M')) => 'L',
+                (None, Some('m')) => 'l',
+                (None, Some(previous)) if !matches!(previous, 'Z' | 'z') => previous,
+                _ => return Err(parser.error("Expected a command")),
+            };
+            letter = Some(command);
+
+            let relative = command.is_ascii_lowercase();
+            let base = if relative { current } else { (0.0, 0.0) };
+
+            let parsed = match command.to_ascii_uppercase() {
+                'M' => {
+                    let point = parser.point(base)?;
+                    subpath_start = point;
+                    PathCommand::MoveTo { point }
+                }
+                'L' => PathCommand::LineTo {
+                    point: parser.point(base)?,
+                },
+                'H' => PathCommand::LineTo {
+                    point: (parser.number()? + base.0, current.1),
+                },
+                'V' => PathCommand::LineTo {
+                    point: (current.0, parser.number()? + base.1),
+                },
+                'C' => PathCommand::CubicTo {
+                    control1: parser.point(base)?,
+                    control2: parser.point(base)?,
+                    point: parser.point(base)?,
+                },
+                'S' => PathCommand::CubicTo {
+                    control1: match previous {
+                        Some(PathCommand::CubicTo { control2, .. }) => reflect(control2, current),
+                        _ => current,
+                    },
+                    control2: parser.point(base)?,
+                    point: parser.point(base)?,
+                },
+                'Q' => PathCommand::QuadTo {
+                    control: parser.point(base)?,
+                    point: parser.point(base)?,
+                },
+                'T' => PathCommand::QuadTo {
+                    control: match previous {
+                        Some(PathCommand::QuadTo { control, .. }) => reflect(control, current),
+                        _ => current,
+                    },
+                    point: parser.point(base)?,
+                },
+                'Z' => PathCommand::Close,
+                'A' => {
+                    let radii = (parser.number()?, parser.number()?);
+                    let rotation = parser.number()?;
+                    let large_arc = parser.flag()?;
+                    let sweep = parser.flag()?;
+                    let point = parser.point(base)?;
+
+                    let mut arc = arc_to_cubics(current, radii, rotation, large_arc, sweep, point);
+                    let Some(last) = arc.pop() else {
+                        // Arcs that end where they start are left out
+                        previous = None;
+                        continue;
+                    };
+                    commands.extend(arc);
+
+                    last
+                }
+                _ => return Err(parser.error(&format!("Unknown command {command}"))),
+            };
+
+            current = match parsed {
+                PathCommand::MoveTo { point }
+                | PathCommand::LineTo { point }
+                | PathCommand::CubicTo { point, .. }
+                | PathCommand::QuadTo { point, .. } => point,
+                PathCommand::Close => subpath_start,
+            };
+            // Only cubic and quadratic commands are reflected, not the cubics of an arc
+            previous = (!matches!(command, 'A' | 'a')).then_some(parsed);
+            commands.push(parsed);
+        }
+
+        Ok(commands)
+    }
+}
+
+/// Mirrors the control point at the given point, used by the S and T shorthands.
+fn reflect(control: FloatVec2, point: FloatVec2) -> FloatVec2 {
+    (2.0 * point.0 - control.0, 2.0 * point.1 - control.1)
+}
+
+/// Converts an SVG arc from the current point to cubic commands, following the endpoint to
+/// center conversion of the SVG specification. Radii that are too small are scaled up until the
+/// arc reaches the point, without a radius the arc is a line.
+fn arc_to_cubics(
+    from: FloatVec2,
+    radii: FloatVec2,
+    rotation: f32,
+    large_arc: bool,
+    sweep: bool,
+    to: FloatVec2,
+) -> Vec<PathCommand> {
+    if from == to {
+        return Vec::new();
+    }
+
+    let (mut rx, mut ry) = (radii.0.abs(), radii.1.abs());
+
+    if rx == 0.0 || ry == 0.0 {
+        return vec![PathCommand::LineTo { point: to }];
+    }
+
+    let (sin, cos) = rotation.to_radians().sin_cos();
+
+    // Start point in the coordinate system of the ellipse, relative to the middle of the chord
+    let half = ((from.0 - to.0) / 2.0, (from.1 - to.1) / 2.0);
+    let x1 = cos * half.0 + sin * half.1;
+    let y1 = -sin * half.0 + cos * half.1;
+
+    let lambda = (x1 / rx).powi(2) + (y1 / ry).powi(2);
+    if lambda > 1.0 {
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+    }
+
+    let numerator = (rx * ry).powi(2) - (rx * y1).powi(2) - (ry * x1).powi(2);
+    let denominator = (rx * y1).powi(2) + (ry * x1).powi(2);
+    let sign = if large_arc == sweep { -1.0 } else { 1.0 };
+    let coefficient = sign * (numerator / denominator).max(0.0).sqrt();
+    let center = (coefficient * rx * y1 / ry, -coefficient * ry * x1 / rx);
+
+    let angle = |x: f32, y: f32| y.atan2(x);
+    let start_angle = angle((x1 - center.0) / rx, (y1 - center.1) / ry);
+    let end_angle = angle((-x1 - center.0) / rx, (-y1 - center.1) / ry);
+
+    let mut sweep_angle = end_angle - start_angle;
+    if sweep && sweep_angle < 0.0 {
+        sweep_angle += TAU;
+    } else if !sweep && sweep_angle > 0.0 {
+        sweep_angle -= TAU;
+    }
+
+    // Maps a point on the unit circle back to the rotated ellipse
+    let middle = ((from.0 + to.0) / 2.0, (from.1 + to.1) / 2.0);
+    let map = |x: f32, y: f32| {
+        let (x, y) = (rx * x + center.0, ry * y + center.1);
+        (cos * x - sin * y + middle.0, sin * x + cos * y + middle.1)
+    };
+
+    let segments = (sweep_angle.abs() / FRAC_PI_2).ceil().max(1.0) as usize;
+    let step = sweep_angle / segments as f32;
+    // Length of the control points that approximates a circular arc of the step
+    let handle = 4.0 / 3.0 * (step / 4.0).tan();
+
+    (0..segments)
+        .map(|index| {
+            let (sin1, cos1) = (start_angle + step * index as f32).sin_cos();
+            let (sin2, cos2) = (start_angle + step * (index + 1) as f32).sin_cos();
+
+            PathCommand::CubicTo {
+                control1: map(cos1 - handle * sin1, sin1 + handle * cos1),
+                control2: map(cos2 + handle * sin2, sin2 - handle * cos2),
+                point: if index + 1 == segments {
+                    to
+                } else {
+                    map(cos2, sin2)
+                },
+            }
+        })
+        .collect()
+}
+
+struct PathParser<'a> {
+    path: &'a str,
+    position: usize,
+}
+
+impl PathParser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.path[self.position..].chars().next()
+    }
+
+    fn is_at_end(&self) -> bool {
+        self.position >= self.path.len()
+    }
+
+    fn skip_separators(&mut self) {
+        while let Some(c) = self.peek() {
+            if !c.is_whitespace() && c != ',' {
+                break;
+            }
+
+            self.position += c.len_utf8();
+        }
+    }
+
+    fn command(&mut self) -> Option<char> {
+        let c = self.peek().filter(|c| c.is_ascii_alphabetic())?;
+        self.position += 1;
+
+        Some(c)
+    }
+
+    /// Numbers can follow each other without a separator, like `10-5` or `.5.5`.
+    fn number(&mut self) -> CreatorResult<f32> {
+        self.skip_separators();
+
+        let bytes = self.path.as_bytes();
+        let start = self.position;
+        let mut end = start;
+        let digits = |end: &mut usize| {
+            while bytes.get(*end).is_some_and(|b| b.is_ascii_digit()) {
+                *end += 1;
+            }
+        };
+
+        if matches!(bytes.get(end), Some(b'+' | b'-')) {
+            end += 1;
+        }
+
+        digits(&mut end);
+
+        if bytes.get(end) == Some(&b'.') {
+            end += 1;
+            digits(&mut end);
+        }
+
+        if matches!(bytes.get(end), Some(b'e' | b'E')) {
+            let mut exponent = end + 1;
+
+            if matches!(bytes.get(exponent), Some(b'+' | b'-')) {
+                exponent += 1;
+            }
+
+            if bytes.get(exponent).is_some_and(|b| b.is_ascii_digit()) {
+                end = exponent;
+                digits(&mut end);
+            }
+        }
+
+        let number = self.path[start..end]
+            .parse::<f32>()
+            .map_err(|_| self.error("Expected a number"))?;
+        self.position = end;
+
+        Ok(number)
+    }
+
+    /// Flags of arcs are a single 0 or 1, which doesn't need a separator like `a1 1 0 011 1`.
+    fn flag(&mut self) -> CreatorResult<bool> {
+        self.skip_separators();
+
+        let flag = match self.peek() {
+            Some('0') => false,
+            Some('1') => true,
+            _ => return Err(self.error("Expected a flag")),
+        };
+        self.position += 1;
+
+        Ok(flag)
+    }
+
+    fn point(&mut self, base: FloatVec2) -> CreatorResult<FloatVec2> {
+        Ok((self.number()? + base.0, self.number()? + base.1))
+    }
+
+    fn error(&self, message: &str) -> CreatorError {
+        CreatorError::InvalidSvgPath {
+            message: message.to_string(),
+            position: self.position,
+        }
+    }
+}
@@ -1,9 +1,10 @@
 #[cfg(test)]
 use crate::animation::primitives::{
     entities::common::AnimationData,
-    interpolations::{calculate_spring_value, SpringProperties},
+    frame_rate::FrameRate,
+    interpolations::{calculate_spring_value, calculate_spring_velocity, SpringProperties},
     keyframe::{Keyframe, Keyframes},
-    utils::timestamp_to_frame,
+    utils::{frame_to_timestamp, timestamp_to_frame},
 };
 
 #[test]
@@ -18,66 +19,82 @@ fn interpolates_the_input() {
         offset: 0.0,
         duration: 3.0,
         visible: true,
+        ..Default::default()
     };
 
-    let fps = 60;
+    let fps = FrameRate::new(60, 1).unwrap();
 
-    let keyframes1 = Keyframes {
-        values: vec![
-            Keyframe {
-                id: "1".into(),
-                value: 0.0,
-                offset: 0.0,
-                interpolation: None,
-            },
-            Keyframe {
-                id: "2".into(),
-                value: 100.0,
-                offset: 1.0,
-                interpolation: None,
-            },
-            Keyframe {
-                id: "3".into(),
-                value: 300.0,
-                offset: 3.0,
-                interpolation: None,
-            },
-        ],
-    };
+    let keyframes1 = Keyframes::new(vec![
+        Keyframe {
+            id: "1".into(),
+            value: 0.0,
+            offset: 0.0,
+            interpolation: None,
+            temporal_ease: None,
+        },
+        Keyframe {
+            id: "2".into(),
+            value: 100.0,
+            offset: 1.0,
+            interpolation: None,
+            temporal_ease: None,
+        },
+        Keyframe {
+            id: "3".into(),
+            value: 300.0,
+            offset: 3.0,
+            interpolation: None,
+            temporal_ease: None,
+        },
+    ]);
 
-    let keyframes2 = Keyframes {
-        values: vec![
-            Keyframe {
-                id: "4".into(),
-                value: -100.0,
-                offset: 0.0,
-                interpolation: None,
-            },
-            Keyframe {
-                id: "5".into(),
-                value: 0.0,
-                offset: 1.0,
-                interpolation: None,
-            },
-        ],
-    };
+    let keyframes2 = Keyframes::new(vec![
+        Keyframe {
+            id: "4".into(),
+            value: -100.0,
+            offset: 0.0,
+            interpolation: None,
+            temporal_ease: None,
+        },
+        Keyframe {
+            id: "5".into(),
+            value: 0.0,
+            offset: 1.0,
+            interpolation: None,
+            temporal_ease: None,
+        },
+    ]);
 
     let rendered_keyframes1: Vec<RenderedKeyframe> = keyframes1
-        .values
-        .into_iter()
+        .values()
+        .iter()
+        .cloned()
         .enumerate()
         .map(|(index, keyframe)| {
-            let rendered_keyframe = render_keyframe(keyframe, &animation_data, index, 120, 60);
+            let rendered_keyframe = render_keyframe(
+                keyframe,
+                &animation_data,
+                index,
+                120,
+                FrameRate::new(60, 1).unwrap(),
+            );
             rendered_keyframe
         })
         .collect();
 
     let rendered_keyframes2: Vec<RenderedKeyframe> = keyframes2
-        .values
-        .into_iter()
+        .values()
+        .iter()
+        .cloned()
         .enumerate()
         .map(|(index, keyframe)| {
-            let rendered_keyframe = render_keyframe(keyframe, &animation_data, index, 120, 60);
+            let rendered_keyframe = render_keyframe(
+                keyframe,
+                &animation_data,
+                index,
+                120,
+                FrameRate::new(60, 1).unwrap(),
+            );
             rendered_keyframe
         })
         .collect();
@@ -104,33 +121,89 @@ fn interpolates_the_input() {
 
 #[test]
 fn calculates_the_spring_value() {
-    let _fps = 60;
     let previous_value = 0.0;
     let next_value = 500.0;
 
-    let mut spring_props = SpringProperties {
-        mass: 1.0,        // Mass of the object attached to the spring
-        stiffness: 100.0, // Stiffness of the spring
-        damping: 10.0,    // Damping factor of the spring
+    // Under-, critically- and over-damped springs compared to a finely stepped simulation
+    for spring_props in [
+        SpringProperties::new(1.0, 10.0, 100.0),
+        SpringProperties::new(1.0, 20.0, 100.0),
+        SpringProperties::new(2.0, 60.0, 100.0),
+    ] {
+        let step = 0.000_01;
+        let mut position = previous_value as f64;
+        let mut velocity = 0.0;
+
+        for i in 1..=100_000 {
+            let acceleration = (-spring_props.stiffness as f64 * (position - next_value as f64)
+                - spring_props.damping as f64 * velocity)
+                / spring_props.mass as f64;
+            velocity += acceleration * step;
+            position += velocity * step;
+
+            if i % 25_000 == 0 {
+                let time = i as f32 * step as f32;
+                let value = calculate_spring_value(time, previous_value, next_value, &spring_props);
+
+                assert!((value - position as f32).abs() < 0.05, "{value} {position}");
+            }
+        }
+    }
+
+    let spring_props = SpringProperties::new(1.0, 10.0, 100.0);
+
+    assert_eq!(
+        calculate_spring_value(0.0, previous_value, next_value, &spring_props),
+        previous_value
+    );
+    assert!(
+        (calculate_spring_value(10.0, previous_value, next_value, &spring_props) - next_value)
+            .abs()
+            < 0.01
+    );
+}
+
+#[test]
+fn evaluates_springs_independent_of_fps() {
+    use crate::animation::primitives::interpolations::InterpolationType;
+
+    let animation_data = AnimationData {
+        offset: 0.0,
+        duration: 2.0,
+        visible: true,
+        ..Default::default()
     };
 
-    let value1 =
-        calculate_spring_value(100, previous_value, next_value, 100, 300, &mut spring_props);
-    let value2 =
-        calculate_spring_value(150, previous_value, next_value, 100, 300, &mut spring_props);
-    let value3 =
-        calculate_spring_value(200, previous_value, next_value, 100, 300, &mut spring_props);
+    let keyframes = Keyframes::new(vec![
+        Keyframe::new(
+            0.0,
+            0.0,
+            "1".into(),
+            Some(InterpolationType::Spring(SpringProperties::new(
+                1.0, 12.0, 180.0,
+            ))),
+        ),
+        Keyframe::new(100.0, 2.0, "2".into(), None),
+    ]);
+
+    for frame in 0..60 {
+        let value_30 =
+            keyframes.get_value_at_frame(frame, &animation_data, FrameRate::new(30, 1).unwrap());
+        let value_60 = keyframes.get_value_at_frame(
+            frame * 2,
+            &animation_data,
+            FrameRate::new(60, 1).unwrap(),
+        );
 
-    println!("{value1}");
-    println!("{value2}");
-    println!("{value3}");
+        assert!((value_30 - value_60).abs() < 1e-3);
+    }
 }
 
 #[test]
 fn converts_timestamp_to_frame() {
-    let frame1 = timestamp_to_frame(0.0, 60);
-    let frame2 = timestamp_to_frame(1.0, 60);
-    let frame3 = timestamp_to_frame(1.5, 60);
+    let frame1 = timestamp_to_frame(0.0, FrameRate::new(60, 1).unwrap());
+    let frame2 = timestamp_to_frame(1.0, FrameRate::new(60, 1).unwrap());
+    let frame3 = timestamp_to_frame(1.5, FrameRate::new(60, 1).unwrap());
 
     assert_eq!(frame1, 0);
     assert_eq!(frame2, 60);
@@ -143,41 +216,1997 @@ fn gets_value_at_frame() {
         offset: 0.0,
         duration: 5.0,
         visible: true,
+        ..Default::default()
+    };
+
+    let fps = FrameRate::new(60, 1).unwrap();
+
+    let keyframes = Keyframes::new(vec![
+        Keyframe {
+            id: "1".into(),
+            value: 0.0,
+            offset: 0.0,
+            interpolation: None,
+            temporal_ease: None,
+        },
+        Keyframe {
+            id: "2".into(),
+            value: 100.0,
+            offset: 1.0,
+            interpolation: None,
+            temporal_ease: None,
+        },
+        Keyframe {
+            id: "3".into(),
+            value: 300.0,
+            offset: 3.0,
+            interpolation: None,
+            temporal_ease: None,
+        },
+    ]);
+
+    let value1 = keyframes.get_value_at_frame(50, &animation_data, fps);
+    let value2 = keyframes.get_value_at_frame(90, &animation_data, fps);
+    let value3 = keyframes.get_value_at_frame(120, &animation_data, fps);
+    let value4 = keyframes.get_value_at_frame(180, &animation_data, fps);
+    let value5 = keyframes.get_value_at_frame(220, &animation_data, fps);
+
+    assert!((value1 - 83.333_33).abs() < 1e-3);
+    assert_eq!(value2, 150.0);
+    assert_eq!(value3, 200.0);
+    assert_eq!(value4, 300.0);
+    assert_eq!(value5, 300.0);
+}
+
+#[test]
+fn keeps_keyframes_sorted() {
+    let mut keyframes: Keyframes = serde_json::from_str(
+        r#"{"values":[
+            {"id":"3","value":300.0,"offset":3.0,"interpolation":null},
+            {"id":"1","value":0.0,"offset":0.0,"interpolation":null},
+            {"id":"2","value":100.0,"offset":1.0,"interpolation":null}
+        ]}"#,
+    )
+    .unwrap();
+
+    keyframes.insert(Keyframe::new(200.0, 2.0, "4".into(), None));
+
+    let ids: Vec<&str> = keyframes.values().iter().map(|k| k.id.as_ref()).collect();
+
+    assert_eq!(ids, vec!["1", "2", "4", "3"]);
+
+    // Changing the keyframes in place sorts them again
+    let mut moved = keyframes.clone();
+    moved.values_mut()[0].offset = 2.5;
+    let ids: Vec<&str> = moved.values().iter().map(|k| k.id.as_ref()).collect();
+    assert_eq!(ids, vec!["2", "4", "1", "3"]);
+    assert_eq!(moved.remove(2).id.as_ref(), "1");
+    assert_ne!(moved, keyframes);
+
+    let animation_data = AnimationData {
+        offset: 1.0,
+        duration: 5.0,
+        visible: true,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        keyframes.get_value_at_frame(0, &animation_data, FrameRate::new(60, 1).unwrap()),
+        0.0
+    );
+    assert_eq!(
+        keyframes.get_value_at_frame(150, &animation_data, FrameRate::new(60, 1).unwrap()),
+        150.0
+    );
+    assert_eq!(
+        keyframes.get_value_at_frame(240, &animation_data, FrameRate::new(60, 1).unwrap()),
+        300.0
+    );
+    assert_eq!(
+        keyframes.get_value_at_frame(600, &animation_data, FrameRate::new(60, 1).unwrap()),
+        300.0
+    );
+}
+
+#[test]
+fn eases_with_cubic_bezier() {
+    use crate::animation::primitives::interpolations::{CubicBezier, InterpolationType};
+
+    let linear = CubicBezier::new(0.0, 0.0, 1.0, 1.0);
+    let ease = CubicBezier::new(0.25, 0.1, 0.25, 1.0);
+
+    assert!((linear.ease(0.3) - 0.3).abs() < 1e-5);
+    assert!((ease.ease(0.5) - 0.802_403_4).abs() < 1e-5);
+    assert_eq!(ease.ease(0.0), 0.0);
+    assert_eq!(ease.ease(1.0), 1.0);
+
+    let interpolation: InterpolationType =
+        serde_json::from_str(r#"{"type":"CubicBezier","x1":0.2,"y1":0.8,"x2":0.1,"y2":1.0}"#)
+            .unwrap();
+
+    assert_eq!(
+        interpolation,
+        InterpolationType::CubicBezier(CubicBezier::new(0.2, 0.8, 0.1, 1.0))
+    );
+}
+
+#[test]
+fn holds_and_steps_between_keyframes() {
+    use crate::animation::primitives::interpolations::{InterpolationType, StepJump, Steps};
+
+    let animation_data = AnimationData {
+        offset: 0.0,
+        duration: 2.0,
+        visible: true,
+        ..Default::default()
+    };
+
+    let keyframes = |interpolation| {
+        Keyframes::new(vec![
+            Keyframe::new(0.0, 0.0, "1".into(), Some(interpolation)),
+            Keyframe::new(100.0, 1.0, "2".into(), None),
+        ])
+    };
+
+    let hold = keyframes(InterpolationType::Hold);
+
+    assert_eq!(
+        hold.get_value_at_frame(0, &animation_data, FrameRate::new(60, 1).unwrap()),
+        0.0
+    );
+    assert_eq!(
+        hold.get_value_at_frame(59, &animation_data, FrameRate::new(60, 1).unwrap()),
+        0.0
+    );
+    assert_eq!(
+        hold.get_value_at_frame(60, &animation_data, FrameRate::new(60, 1).unwrap()),
+        100.0
+    );
+
+    let steps = keyframes(InterpolationType::Steps(Steps::new(4, StepJump::End)));
+
+    assert_eq!(
+        steps.get_value_at_frame(10, &animation_data, FrameRate::new(60, 1).unwrap()),
+        0.0
+    );
+    assert_eq!(
+        steps.get_value_at_frame(15, &animation_data, FrameRate::new(60, 1).unwrap()),
+        25.0
+    );
+    assert_eq!(
+        steps.get_value_at_frame(50, &animation_data, FrameRate::new(60, 1).unwrap()),
+        75.0
+    );
+
+    assert_eq!(Steps::new(4, StepJump::Start).ease(0.0), 0.25);
+    assert_eq!(Steps::new(3, StepJump::None).ease(0.7), 1.0);
+    assert_eq!(Steps::new(3, StepJump::Both).ease(0.5), 0.5);
+}
+
+#[test]
+fn gets_value_at_time() {
+    let animation_data = AnimationData {
+        offset: 0.5,
+        duration: 2.0,
+        visible: true,
+        ..Default::default()
     };
 
-    let fps = 60;
+    let fps = FrameRate::new(30, 1).unwrap();
+
+    let keyframes = Keyframes::new(vec![
+        Keyframe::new(0.0, 0.0, "1".into(), None),
+        Keyframe::new(100.0, 1.01, "2".into(), None),
+    ]);
 
-    let keyframes = Keyframes {
-        values: vec![
-            Keyframe {
-                id: "1".into(),
-                value: 0.0,
+    // Keyframe offsets aren't rounded to frames anymore
+    assert_eq!(
+        keyframes.get_value_at_time(
+            1.01,
+            &AnimationData {
                 offset: 0.0,
-                interpolation: None,
+                ..animation_data.clone()
+            }
+        ),
+        100.0
+    );
+
+    let value = keyframes.get_value_at_time(0.5 + 0.505, &animation_data);
+    assert!((value - 50.0).abs() < 1e-3);
+
+    // Sub frame evaluation, frame 12.25 at 30 fps
+    let sub_frame_value =
+        keyframes.get_value_at_time(frame_to_timestamp(12.25, fps) + 0.5, &animation_data);
+    let expected = 12.25 / 30.0 / 1.01 * 100.0;
+    assert!((sub_frame_value - expected).abs() < 1e-3);
+
+    assert_eq!(keyframes.get_value_at_frame(15, &animation_data, fps), 0.0);
+    assert_eq!(keyframes.get_value_at_time(10.0, &animation_data), 100.0);
+}
+
+#[test]
+fn moves_along_motion_path() {
+    use crate::animation::primitives::{
+        keyframe::Extrapolation,
+        motion_path::{MotionPath, SpatialPoint},
+        values::animated_values::{AnimatedFloat, AnimatedFloatVec2, AnimatedValue},
+    };
+
+    let animation_data = AnimationData {
+        offset: 0.0,
+        duration: 1.0,
+        visible: true,
+        ..Default::default()
+    };
+
+    let mut position = AnimatedFloatVec2::new(0.0, 0.0);
+    position.motion_path = Some(MotionPath::new(vec![
+        Keyframe::new(
+            SpatialPoint::new((0.0, 0.0), (0.0, 0.0), (0.0, 50.0)),
+            0.0,
+            "1".into(),
+            None,
+        ),
+        Keyframe::new(
+            SpatialPoint::new((100.0, 0.0), (0.0, 50.0), (0.0, 0.0)),
+            1.0,
+            "2".into(),
+            None,
+        ),
+    ]));
+
+    let middle = position.get_value_at_time(0.5, &animation_data);
+
+    assert!((middle.0 - 50.0).abs() < 1e-2);
+    assert!((middle.1 - 37.5).abs() < 1e-2);
+
+    // Linear temporal interpolation travels with a constant speed along the arc
+    let points =
+        position.get_values_at_frame_range(0, 61, &animation_data, FrameRate::new(60, 1).unwrap());
+    let distances: Vec<f32> = points
+        .windows(2)
+        .map(|pair| (pair[1].0 - pair[0].0).hypot(pair[1].1 - pair[0].1))
+        .collect();
+    let average = distances.iter().sum::<f32>() / distances.len() as f32;
+
+    assert!(distances
+        .iter()
+        .all(|d| (d - average).abs() < average * 0.02));
+    assert_eq!(points[60], (100.0, 0.0));
+
+    let json = serde_json::to_string(&AnimatedFloatVec2::new(1.0, 2.0)).unwrap();
+    assert!(!json.contains("motion_path"));
+
+    // Keyframes of the axes are ignored while there is a path, but survive a round trip
+    let mut keyed = position.clone();
+    keyed.keyframes = (AnimatedFloat::new(5.0), AnimatedFloat::new(7.0));
+    let mut loaded: AnimatedFloatVec2 =
+        serde_json::from_str(&serde_json::to_string(&keyed).unwrap()).unwrap();
+    assert_eq!(loaded, keyed);
+    assert_eq!(loaded.keyframes.0.keyframes.values().len(), 1);
+    assert_eq!(loaded.get_value_at_time(0.5, &animation_data), middle);
+    loaded.motion_path = None;
+    assert_eq!(loaded.get_value_at_time(0.5, &animation_data), (5.0, 7.0));
+
+    // Extrapolation continues along the tangent at the end of the path
+    if let Some(motion_path) = &mut position.motion_path {
+        motion_path.keyframes.post_extrapolation = Extrapolation::Linear;
+    }
+    let extrapolated = position.get_value_at_time(2.0, &animation_data);
+    assert!((extrapolated.0 - 100.0).abs() < 1e-2);
+    assert!(extrapolated.1 < -50.0);
+}
+
+#[test]
+fn extrapolates_outside_of_keyframes() {
+    use crate::animation::primitives::{
+        interpolations::{EasingFunction, InterpolationType},
+        keyframe::Extrapolation,
+    };
+
+    let animation_data = AnimationData {
+        offset: 1.0,
+        duration: 10.0,
+        visible: true,
+        ..Default::default()
+    };
+
+    let keyframes = |pre_extrapolation, post_extrapolation| {
+        Keyframes::new(vec![
+            Keyframe::new(0.0, 0.0, "1".into(), None),
+            Keyframe::new(360.0, 1.0, "2".into(), None),
+        ])
+        .with_extrapolation(pre_extrapolation, post_extrapolation)
+    };
+
+    let constant = keyframes(Extrapolation::Constant, Extrapolation::Constant);
+    assert_eq!(constant.get_value_at_time(0.0, &animation_data), 0.0);
+    assert_eq!(constant.get_value_at_time(3.0, &animation_data), 360.0);
+
+    let linear = keyframes(Extrapolation::Linear, Extrapolation::Linear);
+    assert_eq!(linear.get_value_at_time(0.5, &animation_data), -180.0);
+    assert_eq!(linear.get_value_at_time(3.0, &animation_data), 720.0);
+
+    let cycle = keyframes(Extrapolation::Cycle, Extrapolation::Cycle);
+    assert_eq!(cycle.get_value_at_time(2.25, &animation_data), 90.0);
+    assert_eq!(cycle.get_value_at_time(0.75, &animation_data), 270.0);
+
+    let ping_pong = keyframes(Extrapolation::PingPong, Extrapolation::PingPong);
+    assert_eq!(ping_pong.get_value_at_time(2.25, &animation_data), 270.0);
+    assert_eq!(ping_pong.get_value_at_time(3.25, &animation_data), 90.0);
+
+    let cycle_with_offset = keyframes(Extrapolation::Constant, Extrapolation::CycleWithOffset);
+    assert_eq!(
+        cycle_with_offset.get_value_at_time(3.5, &animation_data),
+        900.0
+    );
+    assert_eq!(
+        cycle_with_offset.get_value_at_frame(180, &animation_data, FrameRate::new(60, 1).unwrap()),
+        720.0
+    );
+
+    // Linear extrapolation continues with the velocity at the outermost keyframes
+    let eased = Keyframes::new(vec![
+        Keyframe::new(
+            0.0,
+            0.0,
+            "1".into(),
+            Some(InterpolationType::EasingFunction(EasingFunction::QuadIn)),
+        ),
+        Keyframe::new(360.0, 1.0, "2".into(), None),
+    ])
+    .with_extrapolation(Extrapolation::Linear, Extrapolation::Linear);
+    assert_eq!(eased.get_value_at_time(0.5, &animation_data), 0.0);
+    assert_eq!(eased.get_value_at_time(2.5, &animation_data), 720.0);
+    assert_eq!(eased.get_velocity_at_time(2.5, &animation_data), 720.0);
+
+    let deserialized: Keyframes =
+        serde_json::from_str(r#"{"values":[],"post_extrapolation":"PingPong"}"#).unwrap();
+    assert_eq!(deserialized.pre_extrapolation, Extrapolation::Constant);
+    assert_eq!(deserialized.post_extrapolation, Extrapolation::PingPong);
+}
+
+#[test]
+fn gets_velocity_at_time() {
+    use crate::animation::primitives::{
+        interpolations::{CubicBezier, EasingFunction, InterpolationType},
+        keyframe::Extrapolation,
+        motion_path::{MotionPath, SpatialPoint},
+        values::animated_values::{AnimatedFloat, AnimatedFloatVec2, AnimatedValue},
+    };
+
+    let animation_data = AnimationData {
+        offset: 0.5,
+        duration: 4.0,
+        visible: true,
+        ..Default::default()
+    };
+
+    let interpolations = [
+        InterpolationType::Linear,
+        InterpolationType::EasingFunction(EasingFunction::QuintInOut),
+        InterpolationType::EasingFunction(EasingFunction::CircOut),
+        InterpolationType::EasingFunction(EasingFunction::ExpoIn),
+        InterpolationType::CubicBezier(CubicBezier::new(0.25, 0.1, 0.25, 1.0)),
+        InterpolationType::Spring(SpringProperties::new(1.0, 10.0, 100.0)),
+    ];
+
+    let step = 1e-3;
+
+    for interpolation in interpolations {
+        let keyframes = Keyframes::new(vec![
+            Keyframe::new(10.0, 0.0, "1".into(), Some(interpolation)),
+            Keyframe::new(110.0, 2.0, "2".into(), None),
+        ])
+        .with_extrapolation(Extrapolation::Constant, Extrapolation::PingPong);
+
+        for time in [0.7, 1.1, 1.5, 2.2, 3.1] {
+            let velocity = keyframes.get_velocity_at_time(time, &animation_data);
+            let numeric = (keyframes.get_value_at_time(time + step, &animation_data)
+                - keyframes.get_value_at_time(time - step, &animation_data))
+                / (2.0 * step);
+
+            assert!(
+                (velocity - numeric).abs() < 1.0,
+                "{:?} at {}: {} != {}",
+                interpolation,
+                time,
+                velocity,
+                numeric
+            );
+        }
+    }
+
+    let linear = Keyframes::new(vec![
+        Keyframe::new(0.0, 0.0, "1".into(), None),
+        Keyframe::new(100.0, 2.0, "2".into(), None),
+    ]);
+    assert_eq!(linear.get_velocity_at_time(0.0, &animation_data), 0.0);
+    assert_eq!(linear.get_velocity_at_time(0.5, &animation_data), 50.0);
+    assert_eq!(linear.get_velocity_at_time(3.0, &animation_data), 0.0);
+
+    let mut position = AnimatedFloatVec2::new(0.0, 0.0);
+    position.motion_path = Some(MotionPath::new(vec![
+        Keyframe::new(
+            SpatialPoint::new((0.0, 0.0), (0.0, 0.0), (0.0, 50.0)),
+            0.0,
+            "1".into(),
+            None,
+        ),
+        Keyframe::new(
+            SpatialPoint::new((100.0, 0.0), (0.0, 50.0), (0.0, 0.0)),
+            1.0,
+            "2".into(),
+            None,
+        ),
+    ]));
+
+    let velocity = position.get_velocity_at_time(1.0, &animation_data);
+    let before = position.get_value_at_time(1.0 - step, &animation_data);
+    let after = position.get_value_at_time(1.0 + step, &animation_data);
+
+    assert!((velocity.0 - (after.0 - before.0) / (2.0 * step)).abs() < 1.0);
+    assert!((velocity.1 - (after.1 - before.1) / (2.0 * step)).abs() < 1.0);
+    assert!((position.get_orientation_at_time(0.5, &animation_data) - 90.0).abs() < 1e-3);
+    assert!(position.get_orientation_at_time(1.0, &animation_data).abs() < 1e-3);
+    assert!((position.get_orientation_at_time(2.0, &animation_data) + 90.0).abs() < 1e-3);
+
+    // At rest the direction of the closest motion is kept
+    let eased = |from: f32, to: f32| AnimatedFloat {
+        keyframes: Keyframes::new(vec![
+            Keyframe::new(
+                from,
+                0.0,
+                "1".into(),
+                Some(InterpolationType::EasingFunction(
+                    EasingFunction::QuintInOut,
+                )),
+            ),
+            Keyframe::new(to, 1.0, "2".into(), None),
+        ]),
+        drivers: Vec::new(),
+    };
+    let diagonal = AnimatedFloatVec2 {
+        keyframes: (eased(0.0, 100.0), eased(0.0, -100.0)),
+        motion_path: None,
+    };
+
+    for time in [0.0, 0.5, 1.0, 1.5, 3.0] {
+        let orientation = diagonal.get_orientation_at_time(time, &animation_data);
+
+        assert!((orientation + 45.0).abs() < 1e-3, "{time}: {orientation}");
+    }
+}
+
+#[test]
+fn bakes_and_simplifies_keyframes() {
+    use crate::animation::primitives::interpolations::{EasingFunction, InterpolationType};
+
+    let animation_data = AnimationData {
+        offset: 0.0,
+        duration: 2.0,
+        visible: true,
+        ..Default::default()
+    };
+
+    let spring = Keyframes::new(vec![
+        Keyframe::new(
+            0.0,
+            0.0,
+            "1".into(),
+            Some(InterpolationType::Spring(SpringProperties::new(
+                1.0, 8.0, 120.0,
+            ))),
+        ),
+        Keyframe::new(100.0, 2.0, "2".into(), None),
+    ]);
+
+    let baked = spring.bake(0, 121, &animation_data, FrameRate::new(60, 1).unwrap());
+
+    // The end frame is exclusive, like the frame ranges of values
+    assert_eq!(baked.values().len(), 121);
+    for frame in 0..=120 {
+        assert_eq!(
+            baked.get_value_at_frame(frame, &animation_data, FrameRate::new(60, 1).unwrap()),
+            spring.get_value_at_frame(frame, &animation_data, FrameRate::new(60, 1).unwrap())
+        );
+    }
+
+    let eased = Keyframes::new(vec![
+        Keyframe::new(
+            0.0,
+            0.0,
+            "1".into(),
+            Some(InterpolationType::EasingFunction(
+                EasingFunction::CubicInOut,
+            )),
+        ),
+        Keyframe::new(100.0, 2.0, "2".into(), None),
+    ])
+    .bake(0, 121, &animation_data, FrameRate::new(60, 1).unwrap());
+
+    let tolerance = 0.5;
+    let simplified = eased.simplify(tolerance);
+
+    assert!(
+        simplified.values().len() <= 4,
+        "{:?}",
+        simplified
+            .values()
+            .iter()
+            .map(|k| (k.offset, k.interpolation))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        simplified.values().first().unwrap().id,
+        eased.values()[0].id
+    );
+    for keyframe in eased.values() {
+        let value = simplified.get_value_at_time(keyframe.offset, &animation_data);
+        assert!((value - keyframe.value).abs() <= tolerance);
+    }
+
+    let linear = Keyframes::new(
+        (0..=10)
+            .map(|i| Keyframe::new(i as f32 * 3.0, i as f32, i.to_string().into(), None))
+            .collect(),
+    );
+    assert_eq!(linear.simplify(0.01).values().len(), 2);
+
+    // Spans longer than the fitted samples still collapse to a single segment
+    let ramp = Keyframes::new(vec![
+        Keyframe::new(0.0, 0.0, "1".into(), None),
+        Keyframe::new(2000.0, 2000.0 / 60.0, "2".into(), None),
+    ])
+    .bake(0, 2001, &animation_data, FrameRate::new(60, 1).unwrap());
+    assert_eq!(ramp.values().len(), 2001);
+    assert_eq!(ramp.simplify(0.01).values().len(), 2);
+
+    let springy = spring
+        .bake(0, 121, &animation_data, FrameRate::new(60, 1).unwrap())
+        .simplify(tolerance);
+    assert!(springy.values().len() < 40);
+    for frame in 0..=120 {
+        let value =
+            springy.get_value_at_frame(frame, &animation_data, FrameRate::new(60, 1).unwrap());
+        let expected =
+            spring.get_value_at_frame(frame, &animation_data, FrameRate::new(60, 1).unwrap());
+        assert!((value - expected).abs() <= tolerance);
+    }
+}
+
+#[test]
+fn eases_with_extended_easing_functions() {
+    use crate::animation::primitives::interpolations::{EasingFunction, InterpolationType};
+
+    let easing_functions = [
+        EasingFunction::SineIn,
+        EasingFunction::SineOut,
+        EasingFunction::SineInOut,
+        EasingFunction::BackIn { overshoot: 1.70158 },
+        EasingFunction::BackOut { overshoot: 1.70158 },
+        EasingFunction::BackInOut { overshoot: 1.70158 },
+        EasingFunction::ElasticIn {
+            amplitude: 1.0,
+            period: 0.3,
+        },
+        EasingFunction::ElasticOut {
+            amplitude: 1.5,
+            period: 0.4,
+        },
+        EasingFunction::ElasticInOut {
+            amplitude: 1.0,
+            period: 0.45,
+        },
+        EasingFunction::BounceIn,
+        EasingFunction::BounceOut,
+        EasingFunction::BounceInOut,
+    ];
+
+    let step = 1e-3;
+
+    for easing_function in easing_functions {
+        let interpolation = InterpolationType::EasingFunction(easing_function);
+
+        assert!(interpolation.ease(0.0, 0.0).abs() < 1e-5);
+        assert!((interpolation.ease(1.0, 1.0) - 1.0).abs() < 1e-5);
+
+        for progress in [0.1, 0.3, 0.45, 0.6, 0.85] {
+            let numeric = (interpolation.ease(progress + step, 0.0)
+                - interpolation.ease(progress - step, 0.0))
+                / (2.0 * step);
+
+            assert!(
+                (easing_function.derivative(progress) - numeric).abs() < 1e-2,
+                "{:?} at {}",
+                easing_function,
+                progress
+            );
+        }
+    }
+
+    assert!((EasingFunction::BounceOut.derivative(0.0)).abs() < 1e-6);
+
+    // The parametrized curves match simple_easing with the default parameters
+    type EasingCurve = fn(f32) -> f32;
+    let defaults: [(EasingFunction, EasingCurve); 5] = [
+        (
+            EasingFunction::BackIn { overshoot: 1.70158 },
+            simple_easing::back_in,
+        ),
+        (
+            EasingFunction::BackOut { overshoot: 1.70158 },
+            simple_easing::back_out,
+        ),
+        (
+            EasingFunction::BackInOut { overshoot: 1.70158 },
+            simple_easing::back_in_out,
+        ),
+        (
+            EasingFunction::ElasticOut {
+                amplitude: 1.0,
+                period: 0.3,
             },
-            Keyframe {
-                id: "2".into(),
-                value: 100.0,
-                offset: 1.0,
-                interpolation: None,
+            simple_easing::elastic_out,
+        ),
+        (
+            EasingFunction::ElasticInOut {
+                amplitude: 1.0,
+                period: 0.45,
             },
-            Keyframe {
-                id: "3".into(),
-                value: 300.0,
-                offset: 3.0,
-                interpolation: None,
+            simple_easing::elastic_in_out,
+        ),
+    ];
+
+    for (easing_function, expected) in defaults {
+        let interpolation = InterpolationType::EasingFunction(easing_function);
+
+        for progress in [0.1, 0.3, 0.45, 0.6, 0.85] {
+            assert!(
+                (interpolation.ease(progress, 0.0) - expected(progress)).abs() < 1e-4,
+                "{:?} at {}",
+                easing_function,
+                progress
+            );
+        }
+    }
+    assert!(
+        InterpolationType::EasingFunction(EasingFunction::BackOut { overshoot: 1.70158 })
+            .ease(0.6, 0.0)
+            > 1.0
+    );
+
+    let json = r#"{"type":"EasingFunction","easing_function":"ElasticOut","period":0.5}"#;
+    let deserialized: InterpolationType = serde_json::from_str(json).unwrap();
+
+    assert_eq!(
+        deserialized,
+        InterpolationType::EasingFunction(EasingFunction::ElasticOut {
+            amplitude: 1.0,
+            period: 0.5
+        })
+    );
+    assert_eq!(
+        serde_json::to_string(&deserialized).unwrap(),
+        r#"{"type":"EasingFunction","easing_function":"ElasticOut","amplitude":1.0,"period":0.5}"#
+    );
+
+    let deserialized: InterpolationType =
+        serde_json::from_str(r#"{"type":"EasingFunction","easing_function":"CircOut"}"#).unwrap();
+    assert_eq!(
+        deserialized,
+        InterpolationType::EasingFunction(EasingFunction::CircOut)
+    );
+}
+
+#[test]
+fn animates_colors_in_color_spaces() {
+    use crate::animation::primitives::{
+        paint::{AnimatedPaint, Color, ColorSpace, PaintStyle},
+        values::animated_color::{AnimatedColor, ColorKeyframe},
+    };
+
+    let animation_data = AnimationData {
+        offset: 0.0,
+        duration: 1.0,
+        visible: true,
+        ..Default::default()
+    };
+
+    let red = Color::new(255, 0, 0, 1.0);
+    let blue = Color::new(0, 0, 255, 0.0);
+
+    let color = |color_space| {
+        AnimatedColor::with_keyframes(
+            vec![
+                ColorKeyframe::new(blue.clone(), 1.0, "2".into(), None),
+                ColorKeyframe::new(red.clone(), 0.0, "1".into(), None),
+            ],
+            color_space,
+        )
+    };
+
+    let srgb = color(ColorSpace::Srgb);
+    assert_eq!(srgb.get_value_at_time(0.0, &animation_data), red);
+    assert_eq!(srgb.get_value_at_time(1.0, &animation_data), blue);
+    assert_eq!(
+        srgb.get_value_at_time(0.5, &animation_data).value(),
+        (128, 0, 128, 0.5)
+    );
+    assert_eq!(
+        color(ColorSpace::LinearRgb)
+            .get_value_at_frame(30, &animation_data, FrameRate::new(60, 1).unwrap())
+            .value(),
+        (188, 0, 188, 0.5)
+    );
+    assert_eq!(
+        color(ColorSpace::Hsl)
+            .get_value_at_time(0.5, &animation_data)
+            .value(),
+        (255, 0, 255, 0.5)
+    );
+
+    let oklab = color(ColorSpace::Oklab)
+        .get_value_at_time(0.5, &animation_data)
+        .value();
+    assert!(oklab.0 > 128 && oklab.2 > 128 && oklab.1 < 128);
+
+    let white = Color::new(255, 255, 255, 1.0);
+    for color_space in [
+        ColorSpace::Srgb,
+        ColorSpace::LinearRgb,
+        ColorSpace::Hsl,
+        ColorSpace::Oklab,
+    ] {
+        assert_eq!(white.mix(&white, 0.5, color_space), white);
+    }
+
+    let json = serde_json::to_value(color(ColorSpace::Oklab)).unwrap();
+    assert!(json["keyframes"]["values"].is_array());
+    assert_eq!(json["color_space"], "Oklab");
+    assert_eq!(
+        serde_json::from_value::<AnimatedColor>(json).unwrap(),
+        color(ColorSpace::Oklab)
+    );
+
+    let paint: AnimatedPaint =
+        serde_json::from_str(r#"{"style":{"type":"Fill","color":{"value":[10,20,30,1.0]}}}"#)
+            .unwrap();
+
+    match paint.calculate_at_time(0.5, &animation_data).style {
+        PaintStyle::Fill(fill) => assert_eq!(fill.color, Color::new(10, 20, 30, 1.0)),
+        _ => panic!("Expected a fill"),
+    }
+}
+
+#[test]
+fn resolves_animated_paint_and_opacity() {
+    use crate::animation::{
+        primitives::{entities::common::Entity, paint::PaintStyle},
+        timeline::Timeline,
+    };
+
+    let vec2 = r#"{"keyframes":[{"keyframes":{"values":[{"id":"a","value":0.0,"offset":0.0}]}},{"keyframes":{"values":[{"id":"b","value":0.0,"offset":0.0}]}}]}"#;
+    let fade = r#"{"keyframes":{"values":[{"id":"c","value":0.0,"offset":0.0},{"id":"d","value":1.0,"offset":1.0}]}}"#;
+    let animation_data = r#"{"offset":0.0,"duration":2.0,"visible":true}"#;
+
+    let timeline_json = format!(
+        r#"{{"render_state":{{"curr_frame":30}},"duration":2.0,"fps":60,"size":[100,100],"entities":[
+            {{"type":"Rect","id":"1","cache":{{"valid":false}},"position":{vec2},"size":{vec2},"origin":{vec2},"animation_data":{animation_data},"transform":null,
+              "paint":{{"style":{{"type":"Stroke","color":{{"value":[0,0,0,1.0]}},"width":{{"keyframes":{{"values":[{{"id":"e","value":0.0,"offset":0.0}},{{"id":"f","value":20.0,"offset":1.0}}]}}}}}}}},
+              "opacity":{fade}}},
+            {{"type":"Ellipse","id":"2","cache":{{"valid":false}},"radius":{vec2},"position":{vec2},"origin":{vec2},"animation_data":{animation_data},"transform":null,
+              "paint":{{"style":{{"type":"Stroke","color":{{"value":[0,0,0,1.0]}},"width":4.0}}}}}}
+        ]}}"#
+    );
+
+    let timeline: Timeline = serde_json::from_str(&timeline_json).unwrap();
+    let entities = timeline.calculate_at_time(0.5);
+
+    match &entities[0] {
+        Entity::Rect(rect) => {
+            assert_eq!(rect.opacity, 0.5);
+            match &rect.paint.style {
+                PaintStyle::Stroke(stroke) => assert_eq!(stroke.width, 10.0),
+                _ => panic!("Expected a stroke"),
+            }
+        }
+        _ => panic!("Expected a rect"),
+    }
+
+    match &entities[1] {
+        Entity::Ellipse(ellipse) => {
+            assert_eq!(ellipse.opacity, 1.0);
+            match &ellipse.paint.style {
+                PaintStyle::Stroke(stroke) => assert_eq!(stroke.width, 4.0),
+                _ => panic!("Expected a stroke"),
+            }
+        }
+        _ => panic!("Expected an ellipse"),
+    }
+}
+
+#[test]
+fn animates_any_interpolatable_value() {
+    use crate::animation::primitives::{
+        keyframe::Extrapolation,
+        paint::Color,
+        values::{
+            animated::Animated,
+            animated_values::{AnimatedFloatVec2, AnimatedValue},
+        },
+    };
+
+    let animation_data = AnimationData {
+        offset: 0.0,
+        duration: 2.0,
+        visible: true,
+        ..Default::default()
+    };
+
+    fn keyframes<T>(first: T, second: T) -> Keyframes<T> {
+        Keyframes::new(vec![
+            Keyframe::new(first, 0.0, "1".into(), None),
+            Keyframe::new(second, 1.0, "2".into(), None),
+        ])
+    }
+
+    let visible = Animated {
+        keyframes: keyframes(false, true),
+    };
+    assert!(!visible.get_value_at_time(0.99, &animation_data));
+    assert!(visible.get_value_at_time(1.0, &animation_data));
+
+    let label = Animated {
+        keyframes: keyframes(String::from("Hello"), String::from("World")),
+    };
+    assert_eq!(label.get_value_at_time(0.5, &animation_data), "Hello");
+    assert_eq!(label.get_value_at_time(1.5, &animation_data), "World");
+
+    let position = Animated {
+        keyframes: keyframes((0.0, 10.0), (10.0, 0.0))
+            .with_extrapolation(Extrapolation::Constant, Extrapolation::CycleWithOffset),
+    };
+    assert_eq!(position.get_value_at_time(0.5, &animation_data), (5.0, 5.0));
+    assert_eq!(
+        position.get_value_at_time(1.5, &animation_data),
+        (15.0, -5.0)
+    );
+    assert_eq!(
+        position.get_values_at_frame_range(0, 3, &animation_data, FrameRate::new(2, 1).unwrap()),
+        vec![(0.0, 10.0), (5.0, 5.0), (10.0, 0.0)]
+    );
+
+    let color = Animated {
+        keyframes: keyframes(Color::new(0, 0, 0, 0.0), Color::new(255, 255, 255, 1.0)),
+    };
+    assert_eq!(
+        color.get_value_at_time(0.5, &animation_data).value(),
+        (128, 128, 128, 0.5)
+    );
+
+    let empty: Animated<bool> = Animated {
+        keyframes: Keyframes::new(vec![]),
+    };
+    assert!(!empty.get_value_at_time(0.0, &animation_data));
+
+    // Static values and the per axis vectors saved so far still load
+    let label: Animated<String> = serde_json::from_str(r#""Static""#).unwrap();
+    assert_eq!(label.get_value_at_time(1.0, &animation_data), "Static");
+
+    let vec2: AnimatedFloatVec2 = serde_json::from_str(
+        r#"{"keyframes":[
+            {"keyframes":{"values":[{"id":"1","value":0.0,"offset":0.0},{"id":"2","value":4.0,"offset":1.0}]}},
+            3.0
+        ]}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        vec2.get_values_at_frame_range(0, 3, &animation_data, FrameRate::new(2, 1).unwrap()),
+        vec![(0.0, 3.0), (2.0, 3.0), (4.0, 3.0)]
+    );
+}
+
+#[test]
+fn interpolates_rotation_with_quaternions() {
+    use crate::animation::primitives::{
+        transform::{AnimatedTransform, RotationMode, Transform},
+        values::{
+            animated_values::{AnimatedFloat, AnimatedFloatVec2, AnimatedFloatVec3},
+            quaternion::Quaternion,
+        },
+    };
+
+    let animation_data = AnimationData {
+        offset: 0.0,
+        duration: 1.0,
+        visible: true,
+        ..Default::default()
+    };
+
+    let axis = |from: f32, to: f32| AnimatedFloat {
+        keyframes: Keyframes::new(vec![
+            Keyframe::new(from, 0.0, "1".into(), None),
+            Keyframe::new(to, 1.0, "2".into(), None),
+        ]),
+        drivers: Vec::new(),
+    };
+
+    let mut transform = AnimatedTransform {
+        translate: AnimatedFloatVec2::new(0.0, 0.0),
+        scale: AnimatedFloatVec2::new(1.0, 1.0),
+        skew: AnimatedFloatVec2::new(0.0, 0.0),
+        rotate: AnimatedFloatVec3 {
+            keyframes: (axis(0.0, 90.0), AnimatedFloat::new(0.0), axis(0.0, 90.0)),
+        },
+        auto_orient: false,
+        rotation_mode: RotationMode::Quaternion,
+    };
+
+    let expected = Quaternion::IDENTITY
+        .slerp(&Quaternion::from_euler((90.0, 0.0, 90.0)), 0.5)
+        .to_euler();
+    let rotate = transform.calculate_at_time(0.5, &animation_data).rotate;
+    assert_eq!(rotate, expected);
+    // Independent axes would pass through (45, 0, 45)
+    assert!(rotate.1 > 1.0);
+
+    let end = transform.calculate_at_time(1.0, &animation_data).rotate;
+    assert!((end.0 - 90.0).abs() < 1e-3 && end.1.abs() < 1e-3 && (end.2 - 90.0).abs() < 1e-3);
+
+    // Takes the shortest way around instead of turning almost a full circle
+    transform.rotate = AnimatedFloatVec3 {
+        keyframes: (
+            AnimatedFloat::new(0.0),
+            AnimatedFloat::new(0.0),
+            axis(170.0, -170.0),
+        ),
+    };
+    let rotate = transform.calculate_at_time(0.5, &animation_data).rotate;
+    assert!((rotate.2.abs() - 180.0).abs() < 1e-2);
+
+    transform.rotation_mode = RotationMode::Euler;
+    assert_eq!(
+        transform.calculate_at_time(0.5, &animation_data).rotate.2,
+        0.0
+    );
+
+    let matrix = Transform {
+        translate: (10.0, 20.0),
+        scale: (2.0, 1.0),
+        skew: (0.0, 0.0),
+        rotate: (0.0, 0.0, 90.0),
+    }
+    .to_matrix();
+
+    // (1, 0) is scaled to (2, 0), rotated to (0, 2) and then translated
+    let x = matrix[0] + matrix[3];
+    let y = matrix[4] + matrix[7];
+    assert!((x - 10.0).abs() < 1e-5 && (y - 22.0).abs() < 1e-5);
+    assert_eq!(&matrix[12..], &[0.0, 0.0, 0.0, 1.0]);
+}
+
+#[test]
+fn remaps_entity_time() {
+    use crate::animation::{
+        primitives::{entities::common::Entity, values::animated_values::AnimatedFloat},
+        timeline::Timeline,
+        validation::IssueKind,
+    };
+
+    let mut animation_data = AnimationData {
+        offset: 1.0,
+        duration: 2.0,
+        visible: true,
+        speed: 0.5,
+        ..Default::default()
+    };
+    assert_eq!(animation_data.source_time(2.0), 1.5);
+
+    animation_data.reverse = true;
+    assert_eq!(animation_data.source_time(1.5), 1.75);
+
+    // Freezes the first second and plays the rest at double speed
+    animation_data.reverse = false;
+    animation_data.speed = 1.0;
+    animation_data.time_remap = Some(AnimatedFloat {
+        keyframes: Keyframes::new(vec![
+            Keyframe::new(0.0, 0.0, "1".into(), None),
+            Keyframe::new(0.0, 1.0, "2".into(), None),
+            Keyframe::new(2.0, 2.0, "3".into(), None),
+        ]),
+        drivers: Vec::new(),
+    });
+    assert_eq!(animation_data.source_time(1.5), 1.0);
+    assert_eq!(animation_data.source_time(2.5), 2.0);
+
+    let zero = r#"{"keyframes":{"values":[{"id":"a","value":0.0,"offset":0.0}]}}"#;
+    let slide = r#"{"keyframes":{"values":[{"id":"b","value":0.0,"offset":0.0},{"id":"c","value":100.0,"offset":1.0}]}}"#;
+    let position = format!(r#"{{"keyframes":[{slide},{zero}]}}"#);
+    let transform = format!(
+        r#"{{"translate":{position},"scale":{{"keyframes":[1.0,1.0]}},"skew":{{"keyframes":[{zero},{zero}]}},"rotate":{{"keyframes":[{zero},{zero},{zero}]}}}}"#
+    );
+    let paint = r#"{"style":{"type":"Fill","color":{"value":[0,0,0,1.0]}},"align":"Left","font_name":"Arial","size":12.0}"#;
+    let animation_data =
+        r#"{"offset":0.0,"duration":2.0,"visible":true,"speed":0.5,"reverse":false}"#;
+
+    let timeline_json = format!(
+        r#"{{"render_state":{{"curr_frame":0}},"duration":2.0,"fps":60,"size":[100,100],"entities":[
+            {{"type":"Rect","id":"1","cache":{{"valid":false}},"position":{position},"size":{position},"origin":{position},"animation_data":{animation_data},"transform":null,
+              "paint":{{"style":{{"type":"Fill","color":{{"value":[0,0,0,1.0]}}}}}}}},
+            {{"type":"StaggeredText","id":"2","cache":{{"valid":false}},"text":"ab","stagger":0.25,"origin":{position},"animation_data":{animation_data},"transform":null,
+              "letter":{{"transform":{transform},"paint":{paint}}}}}
+        ]}}"#
+    );
+
+    let timeline: Timeline = serde_json::from_str(&timeline_json).unwrap();
+    let entities = timeline.calculate_at_time(1.0);
+
+    match &entities[0] {
+        Entity::Rect(rect) => assert_eq!(rect.position, (50.0, 0.0)),
+        _ => panic!("Expected a rect"),
+    }
+
+    match &entities[1] {
+        Entity::StaggeredText(text) => {
+            let letters = text.letter.transform.as_ref().unwrap();
+            assert_eq!(letters[0].translate, (50.0, 0.0));
+            assert_eq!(letters[1].translate, (25.0, 0.0));
+        }
+        _ => panic!("Expected a staggered text"),
+    }
+
+    // At half speed the second letter finishes after 2.5 seconds, reversed it finishes first
+    let stagger_end = |timeline: &Timeline| {
+        timeline
+            .validate()
+            .issues
+            .iter()
+            .find_map(|issue| match issue.kind {
+                IssueKind::StaggerOverrunsTimeline { end } => Some(end),
+                _ => None,
+            })
+    };
+    assert_eq!(stagger_end(&timeline), Some(2.5));
+
+    let reversed: Timeline =
+        serde_json::from_str(&timeline_json.replace(r#""reverse":false"#, r#""reverse":true"#))
+            .unwrap();
+    assert_eq!(stagger_end(&reversed), None);
+
+    // Remapped to play 1.5 times faster, the second letter finishes after 1.25 / 0.75 seconds
+    let remap = r#""reverse":false,"time_remap":{"keyframes":{"values":[{"id":"r1","value":0.0,"offset":0.0},{"id":"r2","value":3.0,"offset":2.0}]}}"#;
+    let remapped: Timeline = serde_json::from_str(
+        &timeline_json
+            .replace(r#""reverse":false"#, remap)
+            .replace(r#""duration":2.0,"fps""#, r#""duration":1.5,"fps""#),
+    )
+    .unwrap();
+    let end = stagger_end(&remapped).unwrap();
+    assert!((end - 1.25 / 0.75).abs() < 1e-3);
+}
+
+#[test]
+fn eases_keyframes_with_influence_and_speed() {
+    use crate::animation::primitives::interpolations::{
+        EasingFunction, InterpolationType, KeyframeEase, TemporalEase,
+    };
+
+    let animation_data = AnimationData {
+        offset: 0.0,
+        duration: 2.0,
+        visible: true,
+        ..Default::default()
+    };
+
+    let ease = |speed, influence| TemporalEase {
+        ease_in: KeyframeEase { speed, influence },
+        ease_out: KeyframeEase { speed, influence },
+    };
+
+    let easy_ease = Keyframes::new(vec![
+        Keyframe::new(0.0, 0.0, "1".into(), None).with_temporal_ease(ease(0.0, 1.0 / 3.0)),
+        Keyframe::new(100.0, 1.0, "2".into(), None).with_temporal_ease(ease(0.0, 1.0 / 3.0)),
+    ]);
+    assert!((easy_ease.get_value_at_time(0.5, &animation_data) - 50.0).abs() < 1e-3);
+    assert!(easy_ease.get_value_at_time(0.1, &animation_data) < 10.0);
+    assert!(easy_ease.get_velocity_at_time(0.0, &animation_data).abs() < 1e-3);
+
+    // Leaves with the given speed and arrives with the average speed without an ease
+    let fast_start = Keyframes::new(vec![
+        Keyframe::new(0.0, 0.0, "1".into(), None).with_temporal_ease(ease(300.0, 0.5)),
+        Keyframe::new(100.0, 1.0, "2".into(), None),
+    ]);
+    assert!((fast_start.get_velocity_at_time(0.0, &animation_data) - 300.0).abs() < 1e-2);
+
+    // The speed at a keyframe is the same on both sides, even when the direction changes
+    let overshoot = Keyframes::new(vec![
+        Keyframe::new(0.0, 0.0, "1".into(), None),
+        Keyframe::new(100.0, 1.0, "2".into(), None).with_temporal_ease(ease(50.0, 0.3)),
+        Keyframe::new(0.0, 2.0, "3".into(), None),
+    ]);
+    assert!((overshoot.get_velocity_at_time(1.0, &animation_data) - 50.0).abs() < 1e-2);
+    assert!((overshoot.get_velocity_at_time(0.999, &animation_data) - 50.0).abs() < 1.0);
+    assert!(overshoot.get_value_at_time(1.05, &animation_data) > 100.0);
+
+    // Segments without temporal eases keep their interpolation
+    let keyframes = Keyframes::new(vec![
+        Keyframe::new(
+            0.0,
+            0.0,
+            "1".into(),
+            Some(InterpolationType::EasingFunction(EasingFunction::QuadIn)),
+        ),
+        Keyframe::new(100.0, 1.0, "2".into(), None),
+    ]);
+    assert_eq!(keyframes.get_value_at_time(0.5, &animation_data), 25.0);
+}
+
+#[test]
+fn settles_springs_and_runs_them_to_rest() {
+    use crate::animation::primitives::interpolations::InterpolationType;
+
+    let animation_data = AnimationData {
+        offset: 0.0,
+        duration: 5.0,
+        visible: true,
+        ..Default::default()
+    };
+
+    let bouncy = SpringProperties::new(1.0, 10.0, 200.0);
+    let settle_time = bouncy.settle_time(0.001);
+    assert!(settle_time > 0.5 && settle_time < 2.0);
+    assert!((0..100).all(|step| {
+        let time = settle_time + step as f32 * 0.01;
+        (calculate_spring_value(time, 1.0, 0.0, &bouncy)).abs() <= 0.001
+    }));
+
+    let critical = SpringProperties::from_duration_and_bounce(0.5, 0.0);
+    assert!((critical.damping_ratio() - 1.0).abs() < 1e-4);
+    let settle_time = critical.settle_time(0.01);
+    assert!((calculate_spring_value(settle_time, 1.0, 0.0, &critical) - 0.01).abs() < 1e-4);
+
+    assert!(
+        (SpringProperties::from_duration_and_bounce(0.5, 0.3).damping_ratio() - 0.7).abs() < 1e-4
+    );
+    assert!(SpringProperties::from_duration_and_bounce(0.5, -0.5).damping_ratio() > 1.0);
+    assert_eq!(
+        SpringProperties::new(1.0, 0.0, 100.0).settle_time(0.01),
+        f32::INFINITY
+    );
+
+    let keyframes = |run_to_rest| {
+        let spring = SpringProperties {
+            run_to_rest,
+            ..bouncy
+        };
+
+        Keyframes::new(vec![
+            Keyframe::new(
+                0.0,
+                0.0,
+                "1".into(),
+                Some(InterpolationType::Spring(spring)),
+            ),
+            Keyframe::new(100.0, 0.1, "2".into(), None),
+            Keyframe::new(200.0, 1.1, "3".into(), None),
+        ])
+    };
+
+    let cut_off = keyframes(false);
+    assert_eq!(cut_off.get_value_at_time(0.1, &animation_data), 100.0);
+
+    let running = keyframes(true);
+    let expected = calculate_spring_value(0.1, 0.0, 100.0, &bouncy);
+    assert!((running.get_value_at_time(0.1, &animation_data) - expected).abs() < 1e-3);
+
+    // The remaining motion is added on top of the next segment and after the last keyframe
+    let expected = 150.0 + calculate_spring_value(0.6, 0.0, 100.0, &bouncy) - 100.0;
+    assert!((running.get_value_at_time(0.6, &animation_data) - expected).abs() < 1e-3);
+    assert!((running.get_value_at_time(5.0, &animation_data) - 200.0).abs() < 1e-3);
+
+    let expected_velocity = 100.0 + calculate_spring_velocity(0.6, 0.0, 100.0, &bouncy);
+    assert!((running.get_velocity_at_time(0.6, &animation_data) - expected_velocity).abs() < 1e-2);
+}
+
+#[test]
+fn chains_springs_with_continuous_velocity() {
+    use crate::animation::primitives::interpolations::InterpolationType;
+
+    let animation_data = AnimationData {
+        offset: 0.0,
+        duration: 3.0,
+        visible: true,
+        ..Default::default()
+    };
+
+    let spring = |inherit_velocity| {
+        Some(InterpolationType::Spring(SpringProperties {
+            inherit_velocity,
+            ..SpringProperties::new(1.0, 15.0, 200.0)
+        }))
+    };
+
+    let keyframes = |inherit_velocity| {
+        Keyframes::new(vec![
+            Keyframe::new(0.0, 0.0, "1".into(), Some(InterpolationType::Linear)),
+            Keyframe::new(100.0, 1.0, "2".into(), spring(inherit_velocity)),
+            Keyframe::new(50.0, 1.2, "3".into(), spring(inherit_velocity)),
+            Keyframe::new(200.0, 2.0, "4".into(), None),
+        ])
+    };
+
+    let at_rest = keyframes(false);
+    assert!(at_rest.get_velocity_at_time(1.0, &animation_data).abs() < 1e-3);
+
+    // Leaves the keyframe with the speed of the linear segment before it
+    let chained = keyframes(true);
+    assert!((chained.get_velocity_at_time(1.0, &animation_data) - 100.0).abs() < 1e-2);
+    assert!(chained.get_value_at_time(1.005, &animation_data) > 100.0);
+
+    // Carries the velocity over from one spring to the next
+    let incoming = chained.get_velocity_at_time(1.1999, &animation_data);
+    let outgoing = chained.get_velocity_at_time(1.2, &animation_data);
+    assert!(incoming.abs() > 1.0);
+    assert!((incoming - outgoing).abs() < 1.0);
+
+    // A spring running to rest already keeps moving, the next one doesn't add its velocity again
+    let mut running = chained.clone();
+    running.values_mut()[1].interpolation = Some(InterpolationType::Spring(SpringProperties {
+        run_to_rest: true,
+        ..SpringProperties::new(1.0, 15.0, 200.0)
+    }));
+    let incoming = running.get_velocity_at_time(1.1999, &animation_data);
+    let outgoing = running.get_velocity_at_time(1.2, &animation_data);
+    assert!((incoming - outgoing).abs() < 1.0);
+}
+
+#[test]
+fn drives_floats_with_seeded_noise() {
+    use crate::animation::primitives::{
+        drivers::{fractal_noise, Driver, DriverBlend, DriverSource, NoiseDriver},
+        values::animated_values::{AnimatedFloat, AnimatedValue},
+    };
+
+    let animation_data = AnimationData {
+        offset: 0.0,
+        duration: 10.0,
+        visible: true,
+        ..Default::default()
+    };
+
+    // The same seed gives bit identical noise everywhere
+    assert_eq!(fractal_noise(7, 1.37, 3.0).to_bits(), 3195356595);
+    assert_eq!(fractal_noise(7, 1.37, 3.0), fractal_noise(7, 1.37, 3.0));
+    assert_ne!(fractal_noise(7, 1.37, 3.0), fractal_noise(8, 1.37, 3.0));
+
+    // Octaves are shifted, so whole phases don't all land on 0
+    assert!((0..10).any(|phase| fractal_noise(7, phase as f32, 3.0) != 0.0));
+
+    let driver = |blend| Driver {
+        blend,
+        source: DriverSource::Noise(NoiseDriver::new(7, 2.0, 10.0, 2.5)),
+    };
+
+    let mut added = AnimatedFloat::new(100.0);
+    added.drivers.push(driver(DriverBlend::Add));
+    let mut replaced = AnimatedFloat::new(100.0);
+    replaced.drivers.push(driver(DriverBlend::Replace));
+
+    let mut previous = added.get_value_at_time(0.0, &animation_data);
+    for frame in 1..600 {
+        let time = frame_to_timestamp(frame as f32, FrameRate::new(60, 1).unwrap());
+        let value = added.get_value_at_time(time, &animation_data);
+
+        assert!((value - 100.0).abs() <= 10.0);
+        assert!((value - previous).abs() < 1.0);
+        let noise = replaced.get_value_at_time(time, &animation_data);
+        assert!((value - 100.0 - noise).abs() < 1e-4);
+        previous = value;
+    }
+
+    // Animating the frequency speeds the noise up without jumping
+    let mut noise = NoiseDriver::new(7, 2.0, 10.0, 2.5);
+    noise.frequency = AnimatedFloat {
+        keyframes: Keyframes::new(vec![
+            Keyframe::new(1.0, 1.0, "1".into(), None),
+            Keyframe::new(5.0, 2.0, "2".into(), None),
+        ]),
+        drivers: Vec::new(),
+    };
+    let mut speeding_up = AnimatedFloat::new(100.0);
+    speeding_up.drivers.push(Driver {
+        blend: DriverBlend::Add,
+        source: DriverSource::Noise(noise),
+    });
+    let before = speeding_up.get_value_at_time(0.9999, &animation_data);
+    let after = speeding_up.get_value_at_time(1.0001, &animation_data);
+    assert!((before - after).abs() < 0.05);
+
+    let json = r#"{"keyframes":{"values":[{"id":"1","value":5.0,"offset":0.0}]},"drivers":[{"source":{"type":"Noise","seed":7,"frequency":2.0,"amplitude":10.0,"octaves":2.5}}]}"#;
+    let loaded: AnimatedFloat = serde_json::from_str(json).unwrap();
+    let loaded_noise = loaded.get_value_at_time(3.3, &animation_data) - 5.0;
+    let added_noise = added.get_value_at_time(3.3, &animation_data) - 100.0;
+    assert!((loaded_noise - added_noise).abs() < 1e-4);
+}
+
+#[test]
+fn drives_floats_with_oscillators() {
+    use crate::animation::primitives::{
+        drivers::{Driver, DriverBlend, DriverSource, OscillatorDriver, Waveform},
+        values::animated_values::{AnimatedFloat, AnimatedValue},
+    };
+
+    let animation_data = AnimationData {
+        offset: 1.0,
+        duration: 10.0,
+        visible: true,
+        ..Default::default()
+    };
+
+    let oscillate = |waveform, blend| {
+        let mut value = AnimatedFloat::new(10.0);
+        value.drivers.push(Driver {
+            blend,
+            source: DriverSource::Oscillator(OscillatorDriver::new(waveform, 2.0, 4.0)),
+        });
+        value
+    };
+
+    // Two cycles per second, so a quarter cycle is reached after 0.125 seconds
+    let sine = oscillate(Waveform::Sine, DriverBlend::Add);
+    assert!((sine.get_value_at_time(1.0, &animation_data) - 10.0).abs() < 1e-4);
+    assert!((sine.get_value_at_time(1.125, &animation_data) - 14.0).abs() < 1e-4);
+    assert!((sine.get_value_at_time(1.375, &animation_data) - 6.0).abs() < 1e-4);
+
+    let triangle = oscillate(Waveform::Triangle, DriverBlend::Add);
+    assert!((triangle.get_value_at_time(1.0625, &animation_data) - 12.0).abs() < 1e-4);
+    assert!((triangle.get_value_at_time(1.125, &animation_data) - 14.0).abs() < 1e-4);
+
+    let square = oscillate(Waveform::Square, DriverBlend::Add);
+    assert_eq!(square.get_value_at_time(1.1, &animation_data), 14.0);
+    assert_eq!(square.get_value_at_time(1.4, &animation_data), 6.0);
+
+    let sawtooth = oscillate(Waveform::Sawtooth, DriverBlend::Add);
+    assert!((sawtooth.get_value_at_time(1.125, &animation_data) - 12.0).abs() < 1e-4);
+    assert!((sawtooth.get_value_at_time(1.375, &animation_data) - 8.0).abs() < 1e-4);
+
+    // Pulses between 0 and twice the keyframed value
+    let mut pulse = AnimatedFloat::new(10.0);
+    let mut oscillator = OscillatorDriver::new(Waveform::Sine, 2.0, 0.5);
+    oscillator.offset = AnimatedFloat::new(1.0);
+    oscillator.phase = AnimatedFloat::new(0.25);
+    pulse.drivers.push(Driver {
+        blend: DriverBlend::Multiply,
+        source: DriverSource::Oscillator(oscillator),
+    });
+    assert!((pulse.get_value_at_time(1.0, &animation_data) - 15.0).abs() < 1e-4);
+    assert!((pulse.get_value_at_time(1.25, &animation_data) - 5.0).abs() < 1e-4);
+
+    // Speeding up from 1 to 3 cycles per second completes t + t² / 2 cycles in the first two
+    // seconds, then 3 per second, sampling out of order reuses the same phases
+    let mut oscillator = OscillatorDriver::new(Waveform::Sine, 1.0, 1.0);
+    oscillator.frequency = AnimatedFloat {
+        keyframes: Keyframes::new(vec![
+            Keyframe::new(1.0, 0.0, "1".into(), None),
+            Keyframe::new(3.0, 2.0, "2".into(), None),
+        ]),
+        drivers: Vec::new(),
+    };
+    let mut accelerating = AnimatedFloat::new(0.0);
+    accelerating.drivers.push(Driver {
+        blend: DriverBlend::Add,
+        source: DriverSource::Oscillator(oscillator),
+    });
+    for (time, cycles) in [(3.5_f32, 5.5_f32), (2.5, 2.625), (4.1, 7.3), (2.5, 2.625)] {
+        let value = accelerating.get_value_at_time(time, &animation_data);
+        assert!((value - (cycles * std::f32::consts::TAU).sin()).abs() < 1e-3);
+    }
+
+    let json = r#"{"keyframes":{"values":[{"id":"1","value":10.0,"offset":0.0}]},"drivers":[{"blend":"Multiply","source":{"type":"Oscillator","waveform":"Sine","frequency":2.0,"amplitude":0.5,"phase":0.25,"offset":1.0}}]}"#;
+    let loaded: AnimatedFloat = serde_json::from_str(json).unwrap();
+    assert_eq!(
+        loaded.get_value_at_time(1.3, &animation_data),
+        pulse.get_value_at_time(1.3, &animation_data)
+    );
+}
+
+#[test]
+fn analyzes_wav_audio() {
+    use crate::animation::primitives::{
+        audio::{AudioClip, FrequencyBand},
+        values::animated_values::{AnimatedFloat, AnimatedValue},
+    };
+    use std::f32::consts::TAU;
+
+    fn wav(sample_rate: u32, channels: u16, samples: &[i16]) -> Vec<u8> {
+        let data_size = samples.len() as u32 * 2;
+        let mut bytes = Vec::new();
+
+        bytes.extend_from_slice(b"RIFF");
+        bytes.extend_from_slice(&(36 + data_size).to_le_bytes());
+        bytes.extend_from_slice(b"WAVEfmt ");
+        bytes.extend_from_slice(&16_u32.to_le_bytes());
+        bytes.extend_from_slice(&1_u16.to_le_bytes());
+        bytes.extend_from_slice(&channels.to_le_bytes());
+        bytes.extend_from_slice(&sample_rate.to_le_bytes());
+        bytes.extend_from_slice(&(sample_rate * channels as u32 * 2).to_le_bytes());
+        bytes.extend_from_slice(&(channels * 2).to_le_bytes());
+        bytes.extend_from_slice(&16_u16.to_le_bytes());
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&data_size.to_le_bytes());
+
+        for sample in samples {
+            bytes.extend_from_slice(&sample.to_le_bytes());
+        }
+
+        bytes
+    }
+
+    // Half a second of silence followed by half a second of a 440 Hz tone
+    let sample_rate = 8000;
+    let samples: Vec<i16> = (0..sample_rate)
+        .map(|index| {
+            if index < sample_rate / 2 {
+                return 0;
+            }
+
+            ((TAU * 440.0 * index as f32 / sample_rate as f32).sin() * 16000.0) as i16
+        })
+        .collect();
+
+    let clip = AudioClip::from_wav(&wav(sample_rate, 1, &samples)).unwrap();
+    assert_eq!(clip.sample_rate, sample_rate);
+    assert_eq!(clip.duration(), 1.0);
+
+    // Both channels are mixed down
+    let stereo: Vec<i16> = samples.iter().flat_map(|sample| [*sample, 0]).collect();
+    let mixed = AudioClip::from_wav(&wav(sample_rate, 2, &stereo)).unwrap();
+    assert!((mixed.samples[5000] * 2.0 - clip.samples[5000]).abs() < 1e-6);
+
+    assert!(AudioClip::from_wav(b"not a wav file").is_err());
+
+    // Streaming encoders leave the size of the data chunk at its maximum
+    let mut streamed = wav(sample_rate, 1, &samples);
+    streamed[40..44].copy_from_slice(&u32::MAX.to_le_bytes());
+    let streamed = AudioClip::from_wav(&streamed).unwrap();
+    assert_eq!(streamed.samples, clip.samples);
+
+    let bands = [
+        FrequencyBand {
+            low: 0.0,
+            high: 1000.0,
+        },
+        FrequencyBand {
+            low: 2000.0,
+            high: 4000.0,
+        },
+    ];
+    let analysis = clip.analyze(FrameRate::new(10, 1).unwrap(), &bands);
+
+    assert_eq!(analysis.envelope.len(), 10);
+    assert!(analysis.envelope[..5].iter().all(|level| *level == 0.0));
+    assert!(analysis.envelope[5..].iter().all(|level| *level > 0.99));
+    assert!(analysis.bands[0][7] > 0.9);
+    assert!(analysis.bands[1][7] < 0.01);
+
+    let animation_data = AnimationData {
+        offset: 2.0,
+        duration: 1.0,
+        visible: true,
+        ..Default::default()
+    };
+
+    // Scales up to 1.5 while the tone plays and stays at 1 before and after the clip
+    let mut scale = AnimatedFloat::new(1.0);
+    scale.drivers.push(analysis.envelope_driver(0.5));
+    assert_eq!(scale.get_value_at_time(1.8, &animation_data), 1.0);
+    assert_eq!(scale.get_value_at_time(2.2, &animation_data), 1.0);
+    assert!((scale.get_value_at_time(2.75, &animation_data) - 1.5).abs() < 0.01);
+    assert_eq!(scale.get_value_at_time(3.5, &animation_data), 1.0);
+}
+
+#[test]
+fn reports_structured_errors() {
+    use crate::{
+        animation::{
+            primitives::values::animated_values::AnimatedFloat,
+            timeline::{calculate_timeline_json_at_curr_frame, Timeline},
+        },
+        error::CreatorError,
+    };
+
+    match Timeline::from_json("{\n  \"fps\": 60,") {
+        Err(CreatorError::InvalidJson { line, column, .. }) => {
+            assert_eq!(line, 2);
+            assert!(column > 0);
+        }
+        other => panic!("expected invalid JSON, got {:?}", other),
+    }
+
+    let error = calculate_timeline_json_at_curr_frame("[]").unwrap_err();
+    let json = serde_json::to_value(&error).unwrap();
+    assert_eq!(json["type"], "InvalidJson");
+    assert!(json["message"]
+        .as_str()
+        .unwrap()
+        .contains("expected struct Timeline"));
+
+    let animation_data = AnimationData::default();
+    let empty = AnimatedFloat {
+        keyframes: Keyframes::new(Vec::new()),
+        drivers: Vec::new(),
+    };
+    assert_eq!(
+        empty.try_get_value_at_time(0.0, &animation_data),
+        Err(CreatorError::EmptyKeyframes)
+    );
+    assert_eq!(
+        AnimatedFloat::new(3.0).try_get_value_at_time(0.0, &animation_data),
+        Ok(3.0)
+    );
+}
+
+#[test]
+fn validates_timelines() {
+    use crate::animation::{
+        primitives::{
+            entities::{common::AnimatedEntity, common::Cache, rect::AnimatedRectEntity},
+            interpolations::InterpolationType,
+            motion_path::{MotionPath, SpatialPoint},
+            paint::{Color, FillStyle, Paint, PaintStyle},
+            values::animated_values::{AnimatedFloat, AnimatedFloatVec2},
+        },
+        validation::{validate_timeline, IssueKind, Severity},
+    };
+
+    fn rect(id: &str, offset: f32, opacity: AnimatedFloat) -> AnimatedEntity {
+        AnimatedEntity::Rect(AnimatedRectEntity {
+            id: id.to_string(),
+            cache: Cache { valid: false },
+            position: AnimatedFloatVec2::new(0.0, 0.0),
+            size: AnimatedFloatVec2::new(100.0, 100.0),
+            origin: AnimatedFloatVec2::new(0.0, 0.0),
+            paint: Paint {
+                style: PaintStyle::Fill(FillStyle {
+                    color: Color::new(255, 0, 0, 1.0),
+                }),
+            }
+            .into(),
+            opacity,
+            animation_data: AnimationData {
+                offset,
+                duration: 2.0,
+                visible: true,
+                ..Default::default()
             },
+            transform: None,
+        })
+    }
+
+    let valid = rect("a", 0.0, AnimatedFloat::new(1.0));
+    assert!(validate_timeline(std::slice::from_ref(&valid), 5.0)
+        .issues
+        .is_empty());
+
+    let broken = AnimatedFloat {
+        keyframes: Keyframes::new(vec![
+            Keyframe::new(f32::NAN, 0.0, "k".into(), None),
+            Keyframe::new(
+                1.0,
+                3.0,
+                "k".into(),
+                Some(InterpolationType::Spring(SpringProperties::new(
+                    0.0, 10.0, 100.0,
+                ))),
+            ),
+        ]),
+        drivers: Vec::new(),
+    };
+
+    let report = validate_timeline(
+        &[
+            valid.clone(),
+            rect("a", -1.0, broken),
+            rect("b", 8.0, AnimatedFloat::new(1.0)),
         ],
+        5.0,
+    );
+    let kinds: Vec<&IssueKind> = report.issues.iter().map(|issue| &issue.kind).collect();
+
+    assert!(report.has_errors());
+    assert!(kinds.contains(&&IssueKind::NonFiniteValue));
+    assert!(kinds.contains(&&IssueKind::NegativeOffset { offset: -1.0 }));
+    assert!(kinds.contains(&&IssueKind::DuplicateKeyframeId { id: "k".into() }));
+    assert!(kinds.contains(&&IssueKind::DuplicateEntityId { id: "a".into() }));
+    assert!(kinds.contains(&&IssueKind::InvalidSpringMass { mass: 0.0 }));
+    assert!(kinds.contains(&&IssueKind::KeyframeBeyondDuration {
+        offset: 3.0,
+        duration: 2.0
+    }));
+    assert!(kinds.contains(&&IssueKind::EntityOutsideTimeline {
+        start: 8.0,
+        end: 10.0
+    }));
+
+    let outside = report
+        .issues
+        .iter()
+        .find(|issue| matches!(issue.kind, IssueKind::EntityOutsideTimeline { .. }))
+        .unwrap();
+    assert_eq!(outside.path, "entities[2].animation_data");
+    assert_eq!(outside.severity, Severity::Warning);
+
+    let nan = report
+        .issues
+        .iter()
+        .find(|issue| issue.kind == IssueKind::NonFiniteValue)
+        .unwrap();
+    assert_eq!(nan.path, "entities[1].opacity.keyframes[0]");
+
+    let json = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["issues"][0]["kind"]["type"], "NegativeOffset");
+
+    // Empty keyframes are reported, unless a motion path replaces them
+    let empty = AnimatedFloat {
+        keyframes: Keyframes::new(Vec::new()),
+        drivers: Vec::new(),
     };
+    let mut on_path = rect("c", 0.0, AnimatedFloat::new(1.0));
+    if let AnimatedEntity::Rect(rect) = &mut on_path {
+        rect.position = AnimatedFloatVec2 {
+            keyframes: (empty.clone(), empty.clone()),
+            motion_path: Some(MotionPath::new(vec![Keyframe::new(
+                SpatialPoint::default(),
+                0.0,
+                "p".into(),
+                None,
+            )])),
+        };
+    }
+    let report = validate_timeline(&[rect("a", 0.0, empty), on_path], 5.0);
+    assert!(report.has_errors());
+    assert_eq!(report.issues.len(), 1);
+    assert_eq!(report.issues[0].path, "entities[0].opacity.keyframes");
+    assert_eq!(report.issues[0].kind, IssueKind::EmptyKeyframes);
+}
 
-    let value1 = keyframes.get_value_at_frame(50, &animation_data, fps);
-    let value2 = keyframes.get_value_at_frame(90, &animation_data, fps);
-    let value3 = keyframes.get_value_at_frame(120, &animation_data, fps);
-    let value4 = keyframes.get_value_at_frame(180, &animation_data, fps);
-    let value5 = keyframes.get_value_at_frame(220, &animation_data, fps);
-    println!("value1: {0}", value1);
-    println!("value2: {0}", value2);
-    println!("value3: {0}", value3);
-    println!("value4: {0}", value4);
-    println!("value5: {0}", value5);
+#[test]
+fn converts_frame_rates_and_timecode() {
+    use crate::animation::{
+        primitives::{
+            entities::{common::AnimatedEntity, common::Cache, rect::AnimatedRectEntity},
+            paint::{Color, FillStyle, Paint, PaintStyle},
+            timecode::Timecode,
+            values::animated_values::{AnimatedFloat, AnimatedFloatVec2},
+        },
+        timeline::Timeline,
+    };
+    use crate::error::CreatorError;
+
+    assert_eq!(FrameRate::from_decimal(29.97).unwrap(), FrameRate::NTSC);
+    assert_eq!(
+        FrameRate::from_decimal(23.976).unwrap(),
+        FrameRate::NTSC_FILM
+    );
+    assert_eq!(
+        FrameRate::new(60, 2).unwrap(),
+        FrameRate::new(30, 1).unwrap()
+    );
+    assert!(FrameRate::new(30, 0).is_err());
+    assert_eq!(
+        FrameRate::try_from(24_i16).unwrap(),
+        FrameRate::new(24, 1).unwrap()
+    );
+    assert!(matches!(
+        FrameRate::try_from(-30_i16),
+        Err(CreatorError::InvalidFrameRate { .. })
+    ));
+    assert!(FrameRate::try_from(0_i16).is_err());
+
+    let parsed: FrameRate = serde_json::from_str("59.94").unwrap();
+    assert_eq!(parsed, FrameRate::NTSC_HIGH);
+    assert_eq!(
+        serde_json::to_string(&FrameRate::NTSC).unwrap(),
+        r#"{"numerator":30000,"denominator":1001}"#
+    );
+    assert_eq!(
+        serde_json::to_string(&FrameRate::new(25, 1).unwrap()).unwrap(),
+        "25"
+    );
+
+    let fps = FrameRate::new(30, 1).unwrap();
+    assert_eq!(FrameRate::new(60, 1).unwrap().convert_frame(45, fps), 90);
+
+    // Large valid fractions don't overflow, frames beyond i32 are clamped
+    let fine = FrameRate::new(u32::MAX, u32::MAX - 1).unwrap();
+    assert_eq!(fine.convert_frame(1_000_000, fine), 1_000_000);
+    assert_eq!(
+        FrameRate::new(u32::MAX, 1)
+            .unwrap()
+            .convert_frame(i32::MAX, FrameRate::new(1, u32::MAX).unwrap()),
+        i32::MAX
+    );
+    assert_eq!(fps.convert_frame(91, FrameRate::new(60, 1).unwrap()), 46);
+    assert_eq!(FrameRate::NTSC.convert_frame(3000, fps), 2997);
+
+    let entity = AnimatedEntity::Rect(AnimatedRectEntity {
+        id: "1".to_string(),
+        cache: Cache { valid: false },
+        position: AnimatedFloatVec2::new(0.0, 0.0),
+        size: AnimatedFloatVec2::new(100.0, 100.0),
+        origin: AnimatedFloatVec2::new(0.0, 0.0),
+        paint: Paint {
+            style: PaintStyle::Fill(FillStyle {
+                color: Color::new(255, 0, 0, 1.0),
+            }),
+        }
+        .into(),
+        transform: None,
+        animation_data: AnimationData {
+            offset: 0.51,
+            duration: 2.0,
+            ..Default::default()
+        },
+        opacity: AnimatedFloat {
+            keyframes: Keyframes::new(vec![
+                Keyframe::new(0.0, 0.0, "1".into(), None),
+                Keyframe::new(0.5, 0.49, "2".into(), None),
+                Keyframe::new(1.0, 0.5, "3".into(), None),
+            ]),
+            drivers: Vec::new(),
+        },
+    });
+
+    let mut timeline = Timeline::from_json(
+        &serde_json::json!({
+            "entities": [entity],
+            "render_state": { "curr_frame": 45 },
+            "duration": 5.01,
+            "fps": 60,
+            "size": [1280, 720],
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    timeline.convert_frame_rate(fps);
+
+    assert_eq!(timeline.fps, fps);
+    assert_eq!(timeline.render_state.curr_frame, 23);
+    assert!((timeline.duration - 5.0).abs() < 0.0001);
+
+    let json = serde_json::to_value(&timeline).unwrap();
+    let rect = &json["entities"][0];
+    let keyframes = rect["opacity"]["keyframes"]["values"].as_array().unwrap();
+
+    assert!((rect["animation_data"]["offset"].as_f64().unwrap() - 0.5).abs() < 0.0001);
+    assert!((rect["animation_data"]["duration"].as_f64().unwrap() - 2.0).abs() < 0.0001);
+    // The last two keyframes land on the same frame, the earlier one is kept
+    assert_eq!(keyframes.len(), 2);
+    assert_eq!(keyframes[1]["id"], "2");
+    assert!((keyframes[1]["offset"].as_f64().unwrap() - 0.5).abs() < 0.0001);
+
+    let ntsc = FrameRate::NTSC;
+    let timecode = |frame| Timecode::from_frame(frame, ntsc, true).unwrap().to_string();
+
+    assert_eq!(timecode(1799), "00:00:59;29");
+    assert_eq!(timecode(1800), "00:01:00;02");
+    assert_eq!(timecode(17982), "00:10:00;00");
+
+    for frame in [0, 1799, 1800, 17981, 17982, 107892] {
+        let parsed: Timecode = timecode(frame).parse().unwrap();
+        assert_eq!(parsed.to_frame(ntsc).unwrap(), frame);
+    }
+
+    let dropped: Timecode = "00:01:00;01".parse().unwrap();
+    assert!(dropped.to_frame(ntsc).is_err());
+
+    // Some tools separate every field with semicolons
+    let semicolons: Timecode = "00;01;00;02".parse().unwrap();
+    assert_eq!(semicolons, "00:01:00;02".parse().unwrap());
+    assert_eq!(semicolons.to_frame(ntsc).unwrap(), 1800);
+    assert_eq!(
+        Timecode::from_frame(3600, FrameRate::NTSC_HIGH, true)
+            .unwrap()
+            .to_string(),
+        "00:01:00;04"
+    );
+
+    let pal = FrameRate::new(25, 1).unwrap();
+    assert_eq!(
+        Timecode::from_frame(90000, pal, false).unwrap().to_string(),
+        "01:00:00:00"
+    );
+    assert!(Timecode::from_frame(0, pal, true).is_err());
+    assert!("00:00:01".parse::<Timecode>().is_err());
+    assert_eq!(
+        "00:00:01:24"
+            .parse::<Timecode>()
+            .unwrap()
+            .to_frame(pal)
+            .unwrap(),
+        49
+    );
+    assert!("00:00:01:25"
+        .parse::<Timecode>()
+        .unwrap()
+        .to_frame(pal)
+        .is_err());
+}
+
+#[test]
+fn parses_and_animates_svg_paths() {
+    use crate::{
+        animation::{
+            primitives::{
+                entities::{
+                    common::{AnimatedEntity, Entity},
+                    path::AnimatedPathEntity,
+                },
+                paint::{Color, FillStyle, Paint, PaintStyle},
+                path::{AnimatedPathCommand, PathCommand},
+                values::animated_values::{AnimatedFloat, AnimatedFloatVec2},
+            },
+            timeline::Timeline,
+        },
+        error::CreatorError,
+    };
+
+    let commands = PathCommand::parse_svg(
+        "M10 10h20v20H10z m5-5 10 0 C30 0 40 0 40 10 S50 20 60 10 Q70 0 80 10T100 10 l.5.5L1e1-2",
+    )
+    .unwrap();
+
+    assert_eq!(
+        commands,
+        vec![
+            PathCommand::MoveTo {
+                point: (10.0, 10.0)
+            },
+            PathCommand::LineTo {
+                point: (30.0, 10.0)
+            },
+            PathCommand::LineTo {
+                point: (30.0, 30.0)
+            },
+            PathCommand::LineTo {
+                point: (10.0, 30.0)
+            },
+            PathCommand::Close,
+            // Relative to the start of the closed sub path
+            PathCommand::MoveTo { point: (15.0, 5.0) },
+            PathCommand::LineTo { point: (25.0, 5.0) },
+            PathCommand::CubicTo {
+                control1: (30.0, 0.0),
+                control2: (40.0, 0.0),
+                point: (40.0, 10.0),
+            },
+            PathCommand::CubicTo {
+                control1: (40.0, 20.0),
+                control2: (50.0, 20.0),
+                point: (60.0, 10.0),
+            },
+            PathCommand::QuadTo {
+                control: (70.0, 0.0),
+                point: (80.0, 10.0),
+            },
+            PathCommand::QuadTo {
+                control: (90.0, 20.0),
+                point: (100.0, 10.0),
+            },
+            PathCommand::LineTo {
+                point: (100.5, 10.5),
+            },
+            PathCommand::LineTo {
+                point: (10.0, -2.0),
+            },
+        ]
+    );
+
+    // A half circle becomes two quarter circles, compact flags and small radii are accepted
+    let half_circle = PathCommand::parse_svg("M0 0 A5 5 0 0 1 10 0").unwrap();
+    assert_eq!(
+        PathCommand::parse_svg("M0 0 a1 1 0 0110 0").unwrap(),
+        half_circle
+    );
+    assert_eq!(half_circle.len(), 3);
+    match (half_circle[1], half_circle[2]) {
+        (
+            PathCommand::CubicTo {
+                control1, point, ..
+            },
+            PathCommand::CubicTo { point: end, .. },
+        ) => {
+            assert!(control1.0.abs() < 1e-4 && (control1.1 + 2.7614).abs() < 1e-3);
+            assert!((point.0 - 5.0).abs() < 1e-4 && (point.1 + 5.0).abs() < 1e-4);
+            assert_eq!(end, (10.0, 0.0));
+        }
+        _ => panic!("Expected cubic commands"),
+    }
+    assert_eq!(
+        PathCommand::parse_svg("M0 0 A0 5 0 0 1 10 0").unwrap()[1],
+        PathCommand::LineTo { point: (10.0, 0.0) }
+    );
+    assert_eq!(
+        PathCommand::parse_svg("M0 0 A5 5 0 0 1 0 0").unwrap().len(),
+        1
+    );
+    assert!(matches!(
+        PathCommand::parse_svg("M0 0 A5 5 0 2 1 10 0"),
+        Err(CreatorError::InvalidSvgPath { .. })
+    ));
+    assert_eq!(
+        PathCommand::parse_svg("10 10"),
+        Err(CreatorError::InvalidSvgPath {
+            message: "Expected a command".to_string(),
+            position: 0,
+        })
+    );
+    assert!(PathCommand::parse_svg("M0").is_err());
+    assert!(PathCommand::parse_svg("M0 0 Z 5 5").is_err());
+
+    let mut entity = AnimatedPathEntity::from_svg(
+        "1".to_string(),
+        "M0 0 L10 0 L10 10 Z",
+        Paint {
+            style: PaintStyle::Fill(FillStyle {
+                color: Color::new(0, 0, 0, 1.0),
+            }),
+        }
+        .into(),
+        AnimationData {
+            duration: 2.0,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    entity.path[1] = AnimatedPathCommand::LineTo {
+        point: AnimatedFloatVec2 {
+            keyframes: (
+                AnimatedFloat {
+                    keyframes: Keyframes::new(vec![
+                        Keyframe::new(10.0, 0.0, "1".into(), None),
+                        Keyframe::new(20.0, 1.0, "2".into(), None),
+                    ]),
+                    drivers: Vec::new(),
+                },
+                AnimatedFloat::new(0.0),
+            ),
+            motion_path: None,
+        },
+    };
+
+    let timeline = Timeline::from_json(
+        &serde_json::json!({
+            "entities": [AnimatedEntity::Path(entity)],
+            "render_state": { "curr_frame": 0 },
+            "duration": 2.0,
+            "fps": 60,
+            "size": [1280, 720],
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    let entities = timeline.calculate_at_time(0.5);
+    let Entity::Path(path) = &entities[0] else {
+        panic!("Expected a path entity");
+    };
+
+    assert_eq!(path.path.len(), 4);
+    assert_eq!(path.path[3], PathCommand::Close);
+    match path.path[1] {
+        PathCommand::LineTo { point } => {
+            assert!((point.0 - 15.0).abs() < 0.001);
+            assert!(point.1.abs() < 0.001);
+        }
+        command => panic!("Expected a line, got {command:?}"),
+    }
+
+    let json = serde_json::to_value(&entities).unwrap();
+    assert_eq!(json[0]["type"], "Path");
+    assert_eq!(json[0]["path"][0]["type"], "MoveTo");
+    assert!(timeline.validate().issues.is_empty());
 }
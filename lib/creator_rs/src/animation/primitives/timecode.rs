@@ -0,0 +1,165 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::frame_rate::FrameRate;
+use crate::error::{CreatorError, CreatorResult};
+
+/// SMPTE timecode, counting whole frames at the nominal rate of the frame rate. Drop-frame
+/// timecode skips frame numbers so it stays in sync with the clock at NTSC rates.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Timecode {
+    pub hours: u32,
+    pub minutes: u32,
+    pub seconds: u32,
+    pub frames: u32,
+    pub drop_frame: bool,
+}
+
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub fn format_timecode(frame: i32, fps: FrameRate, drop_frame: bool) -> CreatorResult<String> {
+    Ok(Timecode::from_frame(frame, fps, drop_frame)?.to_string())
+}
+
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub fn parse_timecode(timecode: String, fps: FrameRate) -> CreatorResult<i32> {
+    timecode.parse::<Timecode>()?.to_frame(fps)
+}
+
+impl Timecode {
+    /// Frames dropped at the start of every minute that isn't a multiple of ten.
+    fn dropped_frames(fps: FrameRate) -> u32 {
+        fps.nominal() / 15
+    }
+
+    fn check_drop_frame(fps: FrameRate, timecode: &dyn fmt::Display) -> CreatorResult<()> {
+        if fps.supports_drop_frame() {
+            return Ok(());
+        }
+
+        Err(CreatorError::InvalidTimecode {
+            timecode: timecode.to_string(),
+            message: format!("Drop-frame timecode isn't defined for {fps} fps"),
+        })
+    }
+
+    pub fn from_frame(frame: i32, fps: FrameRate, drop_frame: bool) -> CreatorResult<Timecode> {
+        if frame < 0 {
+            return Err(CreatorError::InvalidTimecode {
+                timecode: frame.to_string(),
+                message: "Timecode can't be negative".to_string(),
+            });
+        }
+
+        let nominal = fps.nominal();
+        let mut frame = frame as u32;
+
+        if drop_frame {
+            Self::check_drop_frame(fps, &frame)?;
+
+            let dropped = Self::dropped_frames(fps);
+            let frames_per_minute = nominal * 60 - dropped;
+            let frames_per_ten_minutes = nominal * 600 - dropped * 9;
+
+            let ten_minutes = frame / frames_per_ten_minutes;
+            let remainder = frame % frames_per_ten_minutes;
+
+            // Adds back the frame numbers that were skipped so far
+            frame += dropped * 9 * ten_minutes;
+            if remainder > dropped {
+                frame += dropped * ((remainder - dropped) / frames_per_minute);
+            }
+        }
+
+        Ok(Timecode {
+            hours: frame / (nominal * 3600),
+            minutes: frame / (nominal * 60) % 60,
+            seconds: frame / nominal % 60,
+            frames: frame % nominal,
+            drop_frame,
+        })
+    }
+
+    pub fn to_frame(&self, fps: FrameRate) -> CreatorResult<i32> {
+        let nominal = fps.nominal();
+        let out_of_range = || CreatorError::InvalidTimecode {
+            timecode: self.to_string(),
+            message: format!("Out of range for {fps} fps"),
+        };
+
+        if self.minutes >= 60 || self.seconds >= 60 || self.frames >= nominal {
+            return Err(out_of_range());
+        }
+
+        let total_minutes = self.hours as u64 * 60 + self.minutes as u64;
+        let mut frame =
+            (total_minutes * 60 + self.seconds as u64) * nominal as u64 + self.frames as u64;
+
+        if self.drop_frame {
+            Self::check_drop_frame(fps, self)?;
+
+            let dropped = Self::dropped_frames(fps);
+
+            if self.seconds == 0 && self.frames < dropped && !self.minutes.is_multiple_of(10) {
+                return Err(CreatorError::InvalidTimecode {
+                    timecode: self.to_string(),
+                    message: "The frame is skipped in drop-frame timecode".to_string(),
+                });
+            }
+
+            frame -= dropped as u64 * (total_minutes - total_minutes / 10);
+        }
+
+        i32::try_from(frame).map_err(|_| out_of_range())
+    }
+}
+
+/// `HH:MM:SS:FF`, drop-frame timecode separates the frames with a semicolon.
+impl fmt::Display for Timecode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let separator = if self.drop_frame { ';' } else { ':' };
+
+        write!(
+            f,
+            "{:02}:{:02}:{:02}{}{:02}",
+            self.hours, self.minutes, self.seconds, separator, self.frames
+        )
+    }
+}
+
+/// Accepts `;`, `,` or `.` before the frames for drop-frame timecode. Any of them can separate
+/// the other fields as well, like in `00;01;00;02`.
+impl std::str::FromStr for Timecode {
+    type Err = CreatorError;
+
+    fn from_str(timecode: &str) -> Result<Self, Self::Err> {
+        let invalid = |message: &str| CreatorError::InvalidTimecode {
+            timecode: timecode.to_string(),
+            message: message.to_string(),
+        };
+
+        let separators = [':', ';', ',', '.'];
+        let timecode = timecode.trim();
+        let split = timecode
+            .rfind(separators)
+            .ok_or_else(|| invalid("Expected HH:MM:SS:FF"))?;
+        let drop_frame = !timecode[split..].starts_with(':');
+
+        let parts = timecode
+            .split(separators)
+            .map(|part| part.parse::<u32>())
+            .collect::<Result<Vec<u32>, _>>()
+            .map_err(|_| invalid("Expected numbers"))?;
+
+        match parts[..] {
+            [hours, minutes, seconds, frames] => Ok(Timecode {
+                hours,
+                minutes,
+                seconds,
+                frames,
+                drop_frame,
+            }),
+            _ => Err(invalid("Expected HH:MM:SS:FF")),
+        }
+    }
+}
@@ -1,6 +1,13 @@
 use super::{
     entities::common::AnimationData,
-    values::animated_values::{AnimatedFloatVec2, AnimatedFloatVec3, AnimatedValue},
+    interpolations::InterpolationType,
+    keyframe::Keyframe,
+    utils::{frame_to_timestamp, is_same_time},
+    values::{
+        animated_values::{AnimatedFloatVec2, AnimatedFloatVec3, AnimatedValue},
+        quaternion::Quaternion,
+        values::{FloatVec3, Matrix4},
+    },
 };
 use crate::animation::timeline::Timeline;
 use serde::{Deserialize, Serialize};
@@ -11,6 +18,24 @@ pub struct AnimatedTransform {
     pub scale: AnimatedFloatVec2,
     pub skew: AnimatedFloatVec2,
     pub rotate: AnimatedFloatVec3,
+    /// Rotates along the direction of travel of `translate` in addition to `rotate`.
+    #[serde(default)]
+    pub auto_orient: bool,
+    #[serde(default)]
+    pub rotation_mode: RotationMode,
+}
+
+/// How the angles of `rotate` are interpolated between keyframes.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+pub enum RotationMode {
+    /// Every axis is interpolated on its own.
+    #[default]
+    Euler,
+    /// The orientations at the keyframes of any axis are interpolated along the shortest path,
+    /// eased by the interpolation of the keyframe the segment starts at. A turn of more than 180
+    /// degrees between two keyframes takes the shorter way around in the other direction, so
+    /// spins need keyframes less than half a turn apart or `Euler`.
+    Quaternion,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -29,30 +54,29 @@ impl AnimatedTransform {
         self.translate.sort_keyframes();
     }
 
-    pub fn calculate(&mut self, timeline: &Timeline, animation_data: &AnimationData) -> Transform {
-        let skew = self.skew.get_value_at_frame(
-            timeline.render_state.curr_frame,
-            animation_data,
-            timeline.fps,
-        );
+    pub fn quantize_keyframes(&mut self, quantize: &dyn Fn(f32) -> f32) {
+        self.rotate.quantize_keyframes(quantize);
+        self.skew.quantize_keyframes(quantize);
+        self.scale.quantize_keyframes(quantize);
+        self.translate.quantize_keyframes(quantize);
+    }
 
-        let scale = self.scale.get_value_at_frame(
-            timeline.render_state.curr_frame,
+    pub fn calculate(&self, timeline: &Timeline, animation_data: &AnimationData) -> Transform {
+        self.calculate_at_time(
+            frame_to_timestamp(timeline.render_state.curr_frame as f32, timeline.fps),
             animation_data,
-            timeline.fps,
-        );
+        )
+    }
 
-        let translate = self.translate.get_value_at_frame(
-            timeline.render_state.curr_frame,
-            animation_data,
-            timeline.fps,
-        );
+    pub fn calculate_at_time(&self, time: f32, animation_data: &AnimationData) -> Transform {
+        let skew = self.skew.get_value_at_time(time, animation_data);
+        let scale = self.scale.get_value_at_time(time, animation_data);
+        let translate = self.translate.get_value_at_time(time, animation_data);
+        let mut rotate = self.rotation_at_time(time, animation_data);
 
-        let rotate = self.rotate.get_value_at_frame(
-            timeline.render_state.curr_frame,
-            animation_data,
-            timeline.fps,
-        );
+        if self.auto_orient {
+            rotate.2 += self.translate.get_orientation_at_time(time, animation_data);
+        }
 
         Transform {
             skew,
@@ -61,4 +85,107 @@ impl AnimatedTransform {
             rotate,
         }
     }
+
+    fn rotation_at_time(&self, time: f32, animation_data: &AnimationData) -> FloatVec3 {
+        match self.rotation_mode {
+            RotationMode::Euler => self.rotate.get_value_at_time(time, animation_data),
+            RotationMode::Quaternion => self.slerp_rotation_at_time(time, animation_data),
+        }
+    }
+
+    fn slerp_rotation_at_time(&self, time: f32, animation_data: &AnimationData) -> FloatVec3 {
+        let absolute_time = |keyframe: &Keyframe| animation_data.offset + keyframe.offset;
+
+        // Index of the first keyframe at or after the given time for every axis
+        let indices = self.rotate.axes().map(|axis| {
+            axis.keyframes.values().partition_point(|keyframe| {
+                let keyframe_time = absolute_time(keyframe);
+                keyframe_time < time && !is_same_time(keyframe_time, time)
+            })
+        });
+        let axes = self.rotate.axes().map(|axis| axis.keyframes.values());
+
+        let previous_keyframes = || {
+            axes.iter()
+                .zip(indices)
+                .filter_map(|(keyframes, index)| Some((keyframes, index.checked_sub(1)?)))
+        };
+
+        let start = previous_keyframes()
+            .map(|(keyframes, index)| absolute_time(&keyframes[index]))
+            .reduce(f32::max);
+        let end = axes
+            .iter()
+            .zip(indices)
+            .filter_map(|(keyframes, index)| keyframes.get(index))
+            .map(absolute_time)
+            .reduce(f32::min);
+
+        // At a keyframe or outside of the keyframes the axes are evaluated on their own
+        let (Some(start), Some(end)) = (start, end) else {
+            return self.rotate.get_value_at_time(time, animation_data);
+        };
+        if is_same_time(end, time) {
+            return self.rotate.get_value_at_time(time, animation_data);
+        }
+
+        let interpolation = previous_keyframes()
+            .filter(|(keyframes, index)| {
+                // The last keyframe of an axis doesn't start a segment
+                is_same_time(absolute_time(&keyframes[*index]), start)
+                    && index + 1 < keyframes.len()
+            })
+            .find_map(|(keyframes, index)| keyframes[index].interpolation)
+            .unwrap_or(InterpolationType::Linear);
+
+        let elapsed = time - start;
+        let progress = interpolation.ease(elapsed / (end - start), elapsed);
+
+        let from = Quaternion::from_euler(self.rotate.get_value_at_time(start, animation_data));
+        let to = Quaternion::from_euler(self.rotate.get_value_at_time(end, animation_data));
+
+        from.slerp(&to, progress).to_euler()
+    }
+}
+
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub fn get_transform_matrix(transform: Transform) -> Matrix4 {
+    transform.to_matrix()
+}
+
+impl Transform {
+    /// Row major matrix that scales, skews, rotates and then translates, so 2.5D effects like
+    /// card flips can be drawn with a perspective canvas.
+    #[rustfmt::skip]
+    pub fn to_matrix(&self) -> Matrix4 {
+        let scale = [
+            self.scale.0, 0.0, 0.0, 0.0,
+            0.0, self.scale.1, 0.0, 0.0,
+            0.0, 0.0, 1.0, 0.0,
+            0.0, 0.0, 0.0, 1.0,
+        ];
+        let skew = [
+            1.0, self.skew.0, 0.0, 0.0,
+            self.skew.1, 1.0, 0.0, 0.0,
+            0.0, 0.0, 1.0, 0.0,
+            0.0, 0.0, 0.0, 1.0,
+        ];
+        let rotate = Quaternion::from_euler(self.rotate).to_matrix();
+        let translate = [
+            1.0, 0.0, 0.0, self.translate.0,
+            0.0, 1.0, 0.0, self.translate.1,
+            0.0, 0.0, 1.0, 0.0,
+            0.0, 0.0, 0.0, 1.0,
+        ];
+
+        multiply(&translate, &multiply(&rotate, &multiply(&skew, &scale)))
+    }
+}
+
+fn multiply(a: &Matrix4, b: &Matrix4) -> Matrix4 {
+    std::array::from_fn(|i| {
+        let (row, column) = (i / 4, i % 4);
+
+        (0..4).map(|k| a[row * 4 + k] * b[k * 4 + column]).sum()
+    })
 }
@@ -1,10 +1,25 @@
 use super::{
     entities::common::AnimationData,
+    frame_rate::FrameRate,
     keyframe::{Keyframe, RenderedKeyframe},
 };
 
-pub fn timestamp_to_frame(timestamp: f32, fps: i16) -> i32 {
-    return (timestamp * fps as f32).round() as i32;
+/// See [`FrameRate::timestamp_to_frame`].
+pub fn timestamp_to_frame(timestamp: f32, fps: FrameRate) -> i32 {
+    fps.timestamp_to_frame(timestamp)
+}
+
+/// See [`FrameRate::frame_to_timestamp`].
+pub fn frame_to_timestamp(frame: f32, fps: FrameRate) -> f32 {
+    fps.frame_to_timestamp(frame)
+}
+
+/// Relative tolerance used when comparing timestamps, so keyframes are still hit exactly
+/// after converting frames to seconds.
+const TIME_EPSILON: f32 = 1e-6;
+
+pub fn is_same_time(a: f32, b: f32) -> bool {
+    (a - b).abs() <= TIME_EPSILON * a.abs().max(b.abs()).max(1.0)
 }
 
 pub fn render_keyframe(
@@ -12,7 +27,7 @@ pub fn render_keyframe(
     animation_data: &AnimationData,
     index: usize,
     curr_frame: i32,
-    fps: i16,
+    fps: FrameRate,
 ) -> RenderedKeyframe {
     let animation_start_frame = timestamp_to_frame(animation_data.offset, fps);
     let frame_offset = timestamp_to_frame(keyframe.offset, fps);
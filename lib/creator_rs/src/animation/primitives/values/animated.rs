@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    animation::primitives::{
+        entities::common::AnimationData,
+        frame_rate::FrameRate,
+        keyframe::{Keyframe, Keyframes},
+    },
+    error::CreatorResult,
+};
+
+use super::{interpolate::Interpolate, values::Float};
+
+/// Keyframes of any value that can be interpolated as a whole, such as colors, vectors, booleans
+/// or strings. A plain value deserializes into a single keyframe.
+///
+/// The other animated values build on it or on [`Keyframes`] directly: [`AnimatedColor`] picks
+/// the color space of the mix, [`AnimatedFloat`] adds drivers, and [`AnimatedFloatVec2`] and
+/// [`AnimatedFloatVec3`] keep an [`AnimatedFloat`] per axis, so every axis has its own keyframe
+/// times and drivers.
+///
+/// [`AnimatedColor`]: super::animated_color::AnimatedColor
+/// [`AnimatedFloat`]: super::animated_values::AnimatedFloat
+/// [`AnimatedFloatVec2`]: super::animated_values::AnimatedFloatVec2
+/// [`AnimatedFloatVec3`]: super::animated_values::AnimatedFloatVec3
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(from = "AnimatedRepr<T>")]
+pub struct Animated<T = Float> {
+    pub keyframes: Keyframes<T>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AnimatedRepr<T> {
+    Animated { keyframes: Keyframes<T> },
+    Static(T),
+}
+
+impl<T> From<AnimatedRepr<T>> for Animated<T> {
+    fn from(repr: AnimatedRepr<T>) -> Self {
+        match repr {
+            AnimatedRepr::Animated { keyframes } => Animated { keyframes },
+            AnimatedRepr::Static(value) => Animated::new(value),
+        }
+    }
+}
+
+impl<T> Animated<T> {
+    pub fn new(value: T) -> Animated<T> {
+        Animated {
+            keyframes: Keyframes::new(vec![Keyframe::new(
+                value,
+                0.0,
+                Uuid::new_v4().to_string().into(),
+                None,
+            )]),
+        }
+    }
+
+    pub fn sort_keyframes(&mut self) {
+        self.keyframes.sort();
+    }
+
+    pub fn quantize_keyframes(&mut self, quantize: &dyn Fn(f32) -> f32) {
+        self.keyframes.quantize(quantize);
+    }
+}
+
+impl<T: Interpolate> Animated<T> {
+    pub fn get_value_at_time(&self, time: f32, animation_data: &AnimationData) -> T {
+        self.keyframes.get_value_at_time(time, animation_data)
+    }
+
+    /// See [`Keyframes::get_value_at_time_with`].
+    pub fn get_value_at_time_with(
+        &self,
+        time: f32,
+        animation_data: &AnimationData,
+        interpolate: impl Fn(&T, &T, f32) -> T,
+    ) -> T {
+        self.keyframes
+            .get_value_at_time_with(time, animation_data, interpolate)
+    }
+
+    /// See [`Keyframes::try_get_value_at_time`].
+    pub fn try_get_value_at_time(
+        &self,
+        time: f32,
+        animation_data: &AnimationData,
+    ) -> CreatorResult<T> {
+        self.keyframes.try_get_value_at_time(time, animation_data)
+    }
+
+    pub fn get_value_at_frame(
+        &self,
+        curr_frame: i32,
+        animation_data: &AnimationData,
+        fps: FrameRate,
+    ) -> T {
+        self.keyframes
+            .get_value_at_frame(curr_frame, animation_data, fps)
+    }
+
+    pub fn get_values_at_frame_range(
+        &self,
+        start_frame: i32,
+        end_frame: i32,
+        animation_data: &AnimationData,
+        fps: FrameRate,
+    ) -> Vec<T> {
+        (start_frame..end_frame)
+            .map(|frame| self.get_value_at_frame(frame, animation_data, fps))
+            .collect()
+    }
+}
@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+
+use crate::animation::primitives::{
+    entities::common::AnimationData,
+    frame_rate::FrameRate,
+    keyframe::{Keyframe, Keyframes},
+    paint::{Color, ColorSpace},
+    utils::frame_to_timestamp,
+};
+
+use super::animated::Animated;
+
+pub type ColorKeyframe = Keyframe<Color>;
+
+/// A color that changes over time, the interpolation of a keyframe eases the mix towards
+/// the next keyframe which happens in `color_space`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(from = "AnimatedColorRepr")]
+pub struct AnimatedColor {
+    #[serde(flatten)]
+    pub animated: Animated<Color>,
+    pub color_space: ColorSpace,
+}
+
+/// Accepts a plain static color as well, so paints saved before colors were animatable still load.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AnimatedColorRepr {
+    Animated {
+        keyframes: Keyframes<Color>,
+        #[serde(default)]
+        color_space: ColorSpace,
+    },
+    Static(Color),
+}
+
+impl From<AnimatedColorRepr> for AnimatedColor {
+    fn from(repr: AnimatedColorRepr) -> Self {
+        match repr {
+            AnimatedColorRepr::Animated {
+                keyframes,
+                color_space,
+            } => AnimatedColor {
+                animated: Animated { keyframes },
+                color_space,
+            },
+            AnimatedColorRepr::Static(color) => color.into(),
+        }
+    }
+}
+
+impl From<Color> for AnimatedColor {
+    fn from(color: Color) -> Self {
+        AnimatedColor::new(color)
+    }
+}
+
+impl AnimatedColor {
+    pub fn new(color: Color) -> AnimatedColor {
+        AnimatedColor {
+            animated: Animated::new(color),
+            color_space: ColorSpace::default(),
+        }
+    }
+
+    pub fn with_keyframes(keyframes: Vec<ColorKeyframe>, color_space: ColorSpace) -> AnimatedColor {
+        AnimatedColor {
+            animated: Animated {
+                keyframes: Keyframes::new(keyframes),
+            },
+            color_space,
+        }
+    }
+
+    pub fn sort_keyframes(&mut self) {
+        self.animated.sort_keyframes();
+    }
+
+    pub fn quantize_keyframes(&mut self, quantize: &dyn Fn(f32) -> f32) {
+        self.animated.quantize_keyframes(quantize);
+    }
+
+    pub fn get_value_at_frame(
+        &self,
+        curr_frame: i32,
+        animation_data: &AnimationData,
+        fps: FrameRate,
+    ) -> Color {
+        self.get_value_at_time(frame_to_timestamp(curr_frame as f32, fps), animation_data)
+    }
+
+    pub fn get_value_at_time(&self, time: f32, animation_data: &AnimationData) -> Color {
+        self.animated
+            .get_value_at_time_with(time, animation_data, |from, to, t| {
+                from.mix(to, t, self.color_space)
+            })
+    }
+}
@@ -1,35 +1,127 @@
 use crate::animation::primitives::{
+    drivers::Driver,
     entities::common::AnimationData,
+    frame_rate::FrameRate,
     keyframe::{Keyframe, Keyframes},
+    motion_path::MotionPath,
+    utils::{frame_to_timestamp, is_same_time},
 };
+use crate::error::CreatorResult;
 #[cfg(feature = "parallelization")]
 use rayon::prelude::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use super::values::{Float, FloatVec2, FloatVec3};
+use super::{
+    animated::Animated,
+    values::{Float, FloatVec2, FloatVec3},
+};
+
+/// Time step in seconds of the central difference used for the velocity of driven values.
+const DRIVER_VELOCITY_STEP: f32 = 1e-3;
 
 pub trait AnimatedValue<T> {
     fn sort_keyframes(&mut self);
-    fn get_value_at_frame(&self, curr_frame: i32, animation_data: &AnimationData, fps: i16) -> T;
+    /// Moves the keyframes to the offsets `quantize` maps them to, see [`Keyframes::quantize`].
+    fn quantize_keyframes(&mut self, quantize: &dyn Fn(f32) -> f32);
+    fn get_value_at_time(&self, time: f32, animation_data: &AnimationData) -> T;
+    fn get_value_at_frame(
+        &self,
+        curr_frame: i32,
+        animation_data: &AnimationData,
+        fps: FrameRate,
+    ) -> T {
+        self.get_value_at_time(frame_to_timestamp(curr_frame as f32, fps), animation_data)
+    }
     fn get_values_at_frame_range(
         &self,
         start_frame: i32,
         end_frame: i32,
         animation_data: &AnimationData,
-        fps: i16,
+        fps: FrameRate,
     ) -> Vec<T>;
+    /// Instantaneous rate of change in units per second.
+    fn get_velocity_at_time(&self, time: f32, animation_data: &AnimationData) -> T;
+    fn get_velocity_at_frame(
+        &self,
+        curr_frame: i32,
+        animation_data: &AnimationData,
+        fps: FrameRate,
+    ) -> T {
+        self.get_velocity_at_time(frame_to_timestamp(curr_frame as f32, fps), animation_data)
+    }
+    fn get_velocities_at_frame_range(
+        &self,
+        start_frame: i32,
+        end_frame: i32,
+        animation_data: &AnimationData,
+        fps: FrameRate,
+    ) -> Vec<T> {
+        (start_frame..end_frame)
+            .map(|frame| self.get_velocity_at_frame(frame, animation_data, fps))
+            .collect()
+    }
 }
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(from = "AnimatedFloatRepr")]
 pub struct AnimatedFloat {
     pub keyframes: Keyframes,
+    /// Applied in order on top of the keyframed value.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub drivers: Vec<Driver>,
 }
 
+/// Accepts a plain number as well, so properties that used to be static still load.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AnimatedFloatRepr {
+    Animated {
+        keyframes: Keyframes,
+        #[serde(default)]
+        drivers: Vec<Driver>,
+    },
+    Static(Float),
+}
+
+impl From<AnimatedFloatRepr> for AnimatedFloat {
+    fn from(repr: AnimatedFloatRepr) -> Self {
+        match repr {
+            AnimatedFloatRepr::Animated { keyframes, drivers } => {
+                AnimatedFloat { keyframes, drivers }
+            }
+            AnimatedFloatRepr::Static(value) => AnimatedFloat::new(value),
+        }
+    }
+}
+
+impl From<Animated<Float>> for AnimatedFloat {
+    fn from(animated: Animated<Float>) -> Self {
+        AnimatedFloat {
+            keyframes: animated.keyframes,
+            drivers: Vec::new(),
+        }
+    }
+}
+
+impl From<Float> for AnimatedFloat {
+    fn from(value: Float) -> Self {
+        AnimatedFloat::new(value)
+    }
+}
+
+/// A vector animated per axis, each axis is an [`AnimatedFloat`] with its own keyframe times and
+/// drivers. Use [`Animated`] to animate a vector as a whole.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct AnimatedFloatVec2 {
     pub keyframes: (AnimatedFloat, AnimatedFloat),
+    /// Moves the value along a curved path instead of animating both axes independently. While
+    /// there is a path the keyframes of the axes are kept but ignored, only their drivers are
+    /// applied on top of it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub motion_path: Option<MotionPath>,
 }
 
+/// See [`AnimatedFloatVec2`].
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct AnimatedFloatVec3 {
     pub keyframes: (AnimatedFloat, AnimatedFloat, AnimatedFloat),
@@ -41,7 +133,7 @@ pub fn get_values_at_frame_range_from_animated_float(
     start_frame: i32,
     end_frame: i32,
     animation_data: AnimationData,
-    fps: i16,
+    fps: FrameRate,
 ) -> Vec<Float> {
     animated_value.get_values_at_frame_range(start_frame, end_frame, &animation_data, fps)
 }
@@ -52,7 +144,7 @@ pub fn get_values_at_frame_range_from_animated_float_vec2(
     start_frame: i32,
     end_frame: i32,
     animation_data: AnimationData,
-    fps: i16,
+    fps: FrameRate,
 ) -> Vec<FloatVec2> {
     animated_value.get_values_at_frame_range(start_frame, end_frame, &animation_data, fps)
 }
@@ -63,32 +155,191 @@ pub fn get_values_at_frame_range_from_animated_float_vec3(
     start_frame: i32,
     end_frame: i32,
     animation_data: AnimationData,
-    fps: i16,
+    fps: FrameRate,
 ) -> Vec<FloatVec3> {
     animated_value.get_values_at_frame_range(start_frame, end_frame, &animation_data, fps)
 }
 
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub fn get_velocities_at_frame_range_from_animated_float(
+    animated_value: AnimatedFloat,
+    start_frame: i32,
+    end_frame: i32,
+    animation_data: AnimationData,
+    fps: FrameRate,
+) -> Vec<Float> {
+    animated_value.get_velocities_at_frame_range(start_frame, end_frame, &animation_data, fps)
+}
+
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub fn get_velocities_at_frame_range_from_animated_float_vec2(
+    animated_value: AnimatedFloatVec2,
+    start_frame: i32,
+    end_frame: i32,
+    animation_data: AnimationData,
+    fps: FrameRate,
+) -> Vec<FloatVec2> {
+    animated_value.get_velocities_at_frame_range(start_frame, end_frame, &animation_data, fps)
+}
+
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub fn get_velocities_at_frame_range_from_animated_float_vec3(
+    animated_value: AnimatedFloatVec3,
+    start_frame: i32,
+    end_frame: i32,
+    animation_data: AnimationData,
+    fps: FrameRate,
+) -> Vec<FloatVec3> {
+    animated_value.get_velocities_at_frame_range(start_frame, end_frame, &animation_data, fps)
+}
+
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub fn bake_animated_float(
+    animated_value: AnimatedFloat,
+    start_frame: i32,
+    end_frame: i32,
+    animation_data: AnimationData,
+    fps: FrameRate,
+) -> AnimatedFloat {
+    animated_value.bake(start_frame, end_frame, &animation_data, fps)
+}
+
+/// Greedy simplification within the tolerance at the original keyframes, see
+/// [`Keyframes::simplify`].
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub fn simplify_animated_float(animated_value: AnimatedFloat, tolerance: f32) -> AnimatedFloat {
+    animated_value.simplify(tolerance)
+}
+
 impl AnimatedFloat {
+    /// Like [`AnimatedValue::get_value_at_time`], but fails when there are no keyframes.
+    pub fn try_get_value_at_time(
+        &self,
+        time: f32,
+        animation_data: &AnimationData,
+    ) -> CreatorResult<f32> {
+        let value = self.keyframes.try_get_value_at_time(time, animation_data)?;
+
+        Ok(self.apply_drivers(value, time, animation_data))
+    }
+
+    pub(crate) fn apply_drivers(
+        &self,
+        value: f32,
+        time: f32,
+        animation_data: &AnimationData,
+    ) -> f32 {
+        self.drivers.iter().fold(value, |value, driver| {
+            driver.apply(value, time, animation_data)
+        })
+    }
+
+    /// See [`Keyframes::bake`], drivers are sampled into the keyframes as well.
+    pub fn bake(
+        &self,
+        start_frame: i32,
+        end_frame: i32,
+        animation_data: &AnimationData,
+        fps: FrameRate,
+    ) -> AnimatedFloat {
+        AnimatedFloat {
+            keyframes: Keyframes::sample(start_frame, end_frame, animation_data, fps, |time| {
+                self.get_value_at_time(time, animation_data)
+            }),
+            drivers: Vec::new(),
+        }
+    }
+
+    /// See [`Keyframes::simplify`].
+    pub fn simplify(&self, tolerance: f32) -> AnimatedFloat {
+        AnimatedFloat {
+            keyframes: self.keyframes.simplify(tolerance),
+            drivers: Vec::new(),
+        }
+    }
+
     pub fn new(val: f32) -> AnimatedFloat {
         AnimatedFloat {
-            keyframes: Keyframes {
-                values: vec![Keyframe {
-                    id: Uuid::new_v4().to_string().into(),
-                    value: val,
-                    offset: 0.0,
-                    interpolation: None,
-                }],
-            },
+            keyframes: Keyframes::new(vec![Keyframe {
+                id: Uuid::new_v4().to_string().into(),
+                value: val,
+                offset: 0.0,
+                interpolation: None,
+                temporal_ease: None,
+            }]),
+            drivers: Vec::new(),
         }
     }
 }
 
 impl AnimatedFloatVec2 {
+    /// Angle of the direction of travel in degrees, follows the motion path when there is one.
+    /// While the value rests the direction it arrived from is kept, before it first moves the
+    /// direction it leaves in is used.
+    pub fn get_orientation_at_time(&self, time: f32, animation_data: &AnimationData) -> f32 {
+        if let Some(motion_path) = &self.motion_path {
+            return motion_path.get_orientation_at_time(time, animation_data);
+        }
+
+        let velocity = self.get_velocity_at_time(time, animation_data);
+        let (x, y) = if velocity.0.hypot(velocity.1) > f32::EPSILON {
+            velocity
+        } else {
+            self.resting_direction(time, animation_data)
+        };
+
+        y.atan2(x).to_degrees()
+    }
+
+    /// Change across the keyframes of either axis that ended last before the given time, or
+    /// across the next ones when nothing moved before.
+    fn resting_direction(&self, time: f32, animation_data: &AnimationData) -> FloatVec2 {
+        let mut times: Vec<f32> = self
+            .axes()
+            .iter()
+            .flat_map(|axis| axis.keyframes.values().iter())
+            .map(|keyframe| animation_data.offset + keyframe.offset)
+            .collect();
+        times.sort_by(f32::total_cmp);
+        times.dedup_by(|a, b| is_same_time(*a, *b));
+
+        let change = |from: usize| -> Option<FloatVec2> {
+            let (from, to) = (times.get(from)?, times.get(from + 1)?);
+            let (start, end) = (
+                self.get_value_at_time(*from, animation_data),
+                self.get_value_at_time(*to, animation_data),
+            );
+
+            Some((end.0 - start.0, end.1 - start.1)).filter(|(x, y)| x.hypot(*y) > f32::EPSILON)
+        };
+
+        // Number of keyframes at or before the given time
+        let passed = times.partition_point(|&keyframe_time| {
+            keyframe_time < time || is_same_time(keyframe_time, time)
+        });
+
+        passed
+            .checked_sub(2)
+            .and_then(change)
+            .or_else(|| change(passed.saturating_sub(1)))
+            .unwrap_or((0.0, 0.0))
+    }
+
     pub fn new(x: f32, y: f32) -> AnimatedFloatVec2 {
         AnimatedFloatVec2 {
             keyframes: (AnimatedFloat::new(x), AnimatedFloat::new(y)),
+            motion_path: None,
         }
     }
+
+    /// The x and y axis.
+    pub fn axes(&self) -> [&AnimatedFloat; 2] {
+        [&self.keyframes.0, &self.keyframes.1]
+    }
+
+    pub fn axes_mut(&mut self) -> [&mut AnimatedFloat; 2] {
+        [&mut self.keyframes.0, &mut self.keyframes.1]
+    }
 }
 
 impl AnimatedFloatVec3 {
@@ -101,16 +352,54 @@ impl AnimatedFloatVec3 {
             ),
         }
     }
+
+    /// The x, y and z axis.
+    pub fn axes(&self) -> [&AnimatedFloat; 3] {
+        [&self.keyframes.0, &self.keyframes.1, &self.keyframes.2]
+    }
+
+    pub fn axes_mut(&mut self) -> [&mut AnimatedFloat; 3] {
+        [
+            &mut self.keyframes.0,
+            &mut self.keyframes.1,
+            &mut self.keyframes.2,
+        ]
+    }
 }
 
 impl AnimatedValue<f32> for AnimatedFloat {
     fn sort_keyframes(&mut self) {
         self.keyframes.sort();
+
+        for driver in &mut self.drivers {
+            driver.sort_keyframes();
+        }
     }
 
-    fn get_value_at_frame(&self, curr_frame: i32, animation_data: &AnimationData, fps: i16) -> f32 {
-        self.keyframes
-            .get_value_at_frame(curr_frame, &animation_data, fps)
+    fn quantize_keyframes(&mut self, quantize: &dyn Fn(f32) -> f32) {
+        self.keyframes.quantize(quantize);
+
+        for driver in &mut self.drivers {
+            driver.quantize_keyframes(quantize);
+        }
+    }
+
+    fn get_value_at_time(&self, time: f32, animation_data: &AnimationData) -> f32 {
+        let value = self.keyframes.get_value_at_time(time, animation_data);
+
+        self.apply_drivers(value, time, animation_data)
+    }
+
+    fn get_velocity_at_time(&self, time: f32, animation_data: &AnimationData) -> f32 {
+        if self.drivers.is_empty() {
+            return self.keyframes.get_velocity_at_time(time, animation_data);
+        }
+
+        // Drivers have no analytic derivative, so the velocity is estimated
+        let before = self.get_value_at_time(time - DRIVER_VELOCITY_STEP, animation_data);
+        let after = self.get_value_at_time(time + DRIVER_VELOCITY_STEP, animation_data);
+
+        (after - before) / (2.0 * DRIVER_VELOCITY_STEP)
     }
 
     #[cfg(feature = "parallelization")]
@@ -119,7 +408,7 @@ impl AnimatedValue<f32> for AnimatedFloat {
         start_frame: i32,
         end_frame: i32,
         animation_data: &AnimationData,
-        fps: i16,
+        fps: FrameRate,
     ) -> Vec<f32> {
         let values = (start_frame..end_frame)
             .into_par_iter()
@@ -135,46 +424,41 @@ impl AnimatedValue<f32> for AnimatedFloat {
         start_frame: i32,
         end_frame: i32,
         animation_data: &AnimationData,
-        fps: i16,
+        fps: FrameRate,
     ) -> Vec<f32> {
-        let values = (start_frame..end_frame)
-            .into_iter()
+        (start_frame..end_frame)
             .map(|i| self.get_value_at_frame(i, animation_data, fps))
-            .collect();
-
-        values
+            .collect()
     }
 }
 
 impl AnimatedValue<(f32, f32, f32)> for AnimatedFloatVec3 {
     fn sort_keyframes(&mut self) {
-        self.keyframes.0.sort_keyframes();
-        self.keyframes.1.sort_keyframes();
-        self.keyframes.2.sort_keyframes();
+        for axis in self.axes_mut() {
+            axis.sort_keyframes();
+        }
     }
 
-    fn get_value_at_frame(
-        &self,
-        curr_frame: i32,
-        animation_data: &AnimationData,
-        fps: i16,
-    ) -> (f32, f32, f32) {
-        let x = self
-            .keyframes
-            .0
-            .get_value_at_frame(curr_frame, animation_data, fps);
+    fn quantize_keyframes(&mut self, quantize: &dyn Fn(f32) -> f32) {
+        for axis in self.axes_mut() {
+            axis.quantize_keyframes(quantize);
+        }
+    }
 
-        let y = self
-            .keyframes
-            .1
-            .get_value_at_frame(curr_frame, animation_data, fps);
+    fn get_value_at_time(&self, time: f32, animation_data: &AnimationData) -> (f32, f32, f32) {
+        let [x, y, z] = self
+            .axes()
+            .map(|axis| axis.get_value_at_time(time, animation_data));
 
-        let z = self
-            .keyframes
-            .2
-            .get_value_at_frame(curr_frame, animation_data, fps);
+        (x, y, z)
+    }
 
-        return (x, y, z);
+    fn get_velocity_at_time(&self, time: f32, animation_data: &AnimationData) -> (f32, f32, f32) {
+        let [x, y, z] = self
+            .axes()
+            .map(|axis| axis.get_velocity_at_time(time, animation_data));
+
+        (x, y, z)
     }
 
     fn get_values_at_frame_range(
@@ -182,59 +466,73 @@ impl AnimatedValue<(f32, f32, f32)> for AnimatedFloatVec3 {
         start_frame: i32,
         end_frame: i32,
         animation_data: &AnimationData,
-        fps: i16,
+        fps: FrameRate,
     ) -> Vec<(f32, f32, f32)> {
-        let x =
-            self.keyframes
-                .0
-                .get_values_at_frame_range(start_frame, end_frame, animation_data, fps);
-        let y =
-            self.keyframes
-                .1
-                .get_values_at_frame_range(start_frame, end_frame, animation_data, fps);
-        let z =
-            self.keyframes
-                .2
-                .get_values_at_frame_range(start_frame, end_frame, animation_data, fps);
-
-        let vectors: Vec<(f32, f32, f32)> = x
-            .into_iter()
-            .enumerate()
-            .map(|(index, val_x)| {
-                let val_y: f32 = *y.get(index).unwrap();
-                let val_z: f32 = *z.get(index).unwrap();
-
-                (val_x, val_y, val_z)
-            })
-            .collect();
-
-        vectors
+        zip_axes(self.axes(), start_frame, end_frame, animation_data, fps)
+            .map(|[x, y, z]| (x, y, z))
+            .collect()
     }
 }
 
 impl AnimatedValue<(f32, f32)> for AnimatedFloatVec2 {
     fn sort_keyframes(&mut self) {
-        self.keyframes.0.sort_keyframes();
-        self.keyframes.1.sort_keyframes();
+        for axis in self.axes_mut() {
+            axis.sort_keyframes();
+        }
+
+        if let Some(motion_path) = &mut self.motion_path {
+            motion_path.sort();
+        }
     }
 
-    fn get_value_at_frame(
-        &self,
-        curr_frame: i32,
-        animation_data: &AnimationData,
-        fps: i16,
-    ) -> (f32, f32) {
-        let x = self
-            .keyframes
-            .0
-            .get_value_at_frame(curr_frame, animation_data, fps);
+    fn quantize_keyframes(&mut self, quantize: &dyn Fn(f32) -> f32) {
+        for axis in self.axes_mut() {
+            axis.quantize_keyframes(quantize);
+        }
+
+        if let Some(motion_path) = &mut self.motion_path {
+            motion_path.quantize(quantize);
+        }
+    }
+
+    fn get_value_at_time(&self, time: f32, animation_data: &AnimationData) -> (f32, f32) {
+        if let Some(motion_path) = &self.motion_path {
+            let (x, y) = motion_path.get_value_at_time(time, animation_data);
 
-        let y = self
-            .keyframes
-            .1
-            .get_value_at_frame(curr_frame, animation_data, fps);
+            return (
+                self.keyframes.0.apply_drivers(x, time, animation_data),
+                self.keyframes.1.apply_drivers(y, time, animation_data),
+            );
+        }
+
+        let [x, y] = self
+            .axes()
+            .map(|axis| axis.get_value_at_time(time, animation_data));
+
+        (x, y)
+    }
+
+    fn get_velocity_at_time(&self, time: f32, animation_data: &AnimationData) -> (f32, f32) {
+        if let Some(motion_path) = &self.motion_path {
+            if self.axes().iter().all(|axis| axis.drivers.is_empty()) {
+                return motion_path.get_velocity_at_time(time, animation_data);
+            }
 
-        return (x, y);
+            // Drivers have no analytic derivative, so the velocity is estimated
+            let before = self.get_value_at_time(time - DRIVER_VELOCITY_STEP, animation_data);
+            let after = self.get_value_at_time(time + DRIVER_VELOCITY_STEP, animation_data);
+
+            return (
+                (after.0 - before.0) / (2.0 * DRIVER_VELOCITY_STEP),
+                (after.1 - before.1) / (2.0 * DRIVER_VELOCITY_STEP),
+            );
+        }
+
+        let [x, y] = self
+            .axes()
+            .map(|axis| axis.get_velocity_at_time(time, animation_data));
+
+        (x, y)
     }
 
     fn get_values_at_frame_range(
@@ -242,27 +540,40 @@ impl AnimatedValue<(f32, f32)> for AnimatedFloatVec2 {
         start_frame: i32,
         end_frame: i32,
         animation_data: &AnimationData,
-        fps: i16,
+        fps: FrameRate,
     ) -> Vec<(f32, f32)> {
-        let x =
-            self.keyframes
-                .0
-                .get_values_at_frame_range(start_frame, end_frame, animation_data, fps);
-        let y =
-            self.keyframes
-                .1
-                .get_values_at_frame_range(start_frame, end_frame, animation_data, fps);
-
-        let vectors: Vec<(f32, f32)> = x
+        if self.motion_path.is_some() {
+            return (start_frame..end_frame)
+                .map(|frame| self.get_value_at_frame(frame, animation_data, fps))
+                .collect();
+        }
+
+        zip_axes(self.axes(), start_frame, end_frame, animation_data, fps)
+            .map(|[x, y]| (x, y))
+            .collect()
+    }
+}
+
+/// Evaluates every axis over the frame range and combines the values frame by frame.
+fn zip_axes<const N: usize>(
+    axes: [&AnimatedFloat; N],
+    start_frame: i32,
+    end_frame: i32,
+    animation_data: &AnimationData,
+    fps: FrameRate,
+) -> impl Iterator<Item = [f32; N]> {
+    let mut axes = axes.map(|axis| {
+        axis.get_values_at_frame_range(start_frame, end_frame, animation_data, fps)
             .into_iter()
-            .enumerate()
-            .map(|(index, val_x)| {
-                let val_y: f32 = *y.get(index).unwrap();
+    });
 
-                (val_x, val_y)
-            })
-            .collect();
+    std::iter::from_fn(move || {
+        let mut values = [0.0; N];
 
-        vectors
-    }
+        for (value, axis) in values.iter_mut().zip(axes.iter_mut()) {
+            *value = axis.next()?;
+        }
+
+        Some(values)
+    })
 }
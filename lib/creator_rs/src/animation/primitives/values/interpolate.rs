@@ -0,0 +1,140 @@
+use crate::animation::primitives::paint::{Color, ColorSpace};
+
+use super::values::{Float, FloatVec2, FloatVec3, FloatVec4, Matrix4};
+
+/// A value that can be animated with keyframes, the default is used when there are no keyframes.
+pub trait Interpolate: Clone + Default {
+    /// Value at `t` between `self` at 0.0 and `other` at 1.0. Eased progress can leave 0..1,
+    /// continuous values extrapolate in that case.
+    fn interpolate(&self, other: &Self, t: f32) -> Self;
+
+    /// Adds the change from `from` to `to` the given number of times, used to offset repeated
+    /// keyframes. Values that can't be added repeat unchanged.
+    fn accumulate(&self, _from: &Self, _to: &Self, _times: f32) -> Self {
+        self.clone()
+    }
+
+    /// Change from `self` to `other` in units, used to convert speeds in units per second into
+    /// progress. Values that can't be measured return None.
+    fn difference(&self, _other: &Self) -> Option<f32> {
+        None
+    }
+}
+
+impl Interpolate for Float {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+
+    fn accumulate(&self, from: &Self, to: &Self, times: f32) -> Self {
+        self + (to - from) * times
+    }
+
+    fn difference(&self, other: &Self) -> Option<f32> {
+        Some(other - self)
+    }
+}
+
+impl Interpolate for FloatVec2 {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        (
+            self.0.interpolate(&other.0, t),
+            self.1.interpolate(&other.1, t),
+        )
+    }
+
+    fn accumulate(&self, from: &Self, to: &Self, times: f32) -> Self {
+        (
+            self.0.accumulate(&from.0, &to.0, times),
+            self.1.accumulate(&from.1, &to.1, times),
+        )
+    }
+
+    fn difference(&self, other: &Self) -> Option<f32> {
+        Some((other.0 - self.0).hypot(other.1 - self.1))
+    }
+}
+
+impl Interpolate for FloatVec3 {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        (
+            self.0.interpolate(&other.0, t),
+            self.1.interpolate(&other.1, t),
+            self.2.interpolate(&other.2, t),
+        )
+    }
+
+    fn accumulate(&self, from: &Self, to: &Self, times: f32) -> Self {
+        (
+            self.0.accumulate(&from.0, &to.0, times),
+            self.1.accumulate(&from.1, &to.1, times),
+            self.2.accumulate(&from.2, &to.2, times),
+        )
+    }
+
+    fn difference(&self, other: &Self) -> Option<f32> {
+        let (x, y, z) = (other.0 - self.0, other.1 - self.1, other.2 - self.2);
+
+        Some((x * x + y * y + z * z).sqrt())
+    }
+}
+
+impl Interpolate for FloatVec4 {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        (
+            self.0.interpolate(&other.0, t),
+            self.1.interpolate(&other.1, t),
+            self.2.interpolate(&other.2, t),
+            self.3.interpolate(&other.3, t),
+        )
+    }
+
+    fn accumulate(&self, from: &Self, to: &Self, times: f32) -> Self {
+        (
+            self.0.accumulate(&from.0, &to.0, times),
+            self.1.accumulate(&from.1, &to.1, times),
+            self.2.accumulate(&from.2, &to.2, times),
+            self.3.accumulate(&from.3, &to.3, times),
+        )
+    }
+}
+
+/// Interpolates every element, which only keeps the matrix rigid for small changes.
+impl Interpolate for Matrix4 {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        std::array::from_fn(|i| self[i].interpolate(&other[i], t))
+    }
+
+    fn accumulate(&self, from: &Self, to: &Self, times: f32) -> Self {
+        std::array::from_fn(|i| self[i].accumulate(&from[i], &to[i], times))
+    }
+}
+
+/// Mixes in sRGB, use an `AnimatedColor` to pick a different color space.
+impl Interpolate for Color {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        self.mix(other, t, ColorSpace::Srgb)
+    }
+}
+
+/// Holds the value until the next keyframe is reached.
+impl Interpolate for bool {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        if t >= 1.0 {
+            *other
+        } else {
+            *self
+        }
+    }
+}
+
+/// Holds the value until the next keyframe is reached.
+impl Interpolate for String {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        if t >= 1.0 {
+            other.clone()
+        } else {
+            self.clone()
+        }
+    }
+}
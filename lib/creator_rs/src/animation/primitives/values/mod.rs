@@ -1,2 +1,6 @@
+pub mod animated;
+pub mod animated_color;
 pub mod animated_values;
+pub mod interpolate;
+pub mod quaternion;
 pub mod values;
@@ -0,0 +1,137 @@
+use serde::{Deserialize, Serialize};
+
+use super::{
+    interpolate::Interpolate,
+    values::{FloatVec3, Matrix4},
+};
+
+/// Quaternions closer than this are treated as the same orientation when slerping.
+const SLERP_EPSILON: f32 = 1e-6;
+
+/// A rotation in 3D, interpolating it avoids the gimbal issues of animating Euler angles.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct Quaternion {
+    pub w: f32,
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+impl Default for Quaternion {
+    fn default() -> Self {
+        Quaternion::IDENTITY
+    }
+}
+
+impl Quaternion {
+    pub const IDENTITY: Quaternion = Quaternion {
+        w: 1.0,
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+    };
+
+    /// Euler angles in degrees, applied around x first, then y and then z.
+    pub fn from_euler(rotate: FloatVec3) -> Quaternion {
+        let (sx, cx) = (rotate.0.to_radians() / 2.0).sin_cos();
+        let (sy, cy) = (rotate.1.to_radians() / 2.0).sin_cos();
+        let (sz, cz) = (rotate.2.to_radians() / 2.0).sin_cos();
+
+        Quaternion {
+            w: cx * cy * cz + sx * sy * sz,
+            x: sx * cy * cz - cx * sy * sz,
+            y: cx * sy * cz + sx * cy * sz,
+            z: cx * cy * sz - sx * sy * cz,
+        }
+    }
+
+    /// Euler angles in degrees in the same order as [`Quaternion::from_euler`], every angle is
+    /// within -180 to 180 degrees and the y angle within -90 to 90 degrees.
+    pub fn to_euler(&self) -> FloatVec3 {
+        let Quaternion { w, x, y, z } = self.normalize();
+
+        let rotate_x = (2.0 * (w * x + y * z)).atan2(1.0 - 2.0 * (x * x + y * y));
+        let rotate_y = (2.0 * (w * y - z * x)).clamp(-1.0, 1.0).asin();
+        let rotate_z = (2.0 * (w * z + x * y)).atan2(1.0 - 2.0 * (y * y + z * z));
+
+        (
+            rotate_x.to_degrees(),
+            rotate_y.to_degrees(),
+            rotate_z.to_degrees(),
+        )
+    }
+
+    pub fn dot(&self, other: &Quaternion) -> f32 {
+        self.w * other.w + self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn normalize(&self) -> Quaternion {
+        let length = self.dot(self).sqrt();
+
+        if length <= SLERP_EPSILON {
+            return Quaternion::IDENTITY;
+        }
+
+        self.scale(1.0 / length)
+    }
+
+    fn scale(&self, factor: f32) -> Quaternion {
+        Quaternion {
+            w: self.w * factor,
+            x: self.x * factor,
+            y: self.y * factor,
+            z: self.z * factor,
+        }
+    }
+
+    fn add(&self, other: &Quaternion) -> Quaternion {
+        Quaternion {
+            w: self.w + other.w,
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z,
+        }
+    }
+
+    /// Spherical interpolation at a constant angular speed, always along the shortest path.
+    pub fn slerp(&self, other: &Quaternion, t: f32) -> Quaternion {
+        let from = self.normalize();
+        let mut to = other.normalize();
+        let mut cos_angle = from.dot(&to);
+
+        // q and -q are the same orientation, flipping one takes the shorter way around
+        if cos_angle < 0.0 {
+            to = to.scale(-1.0);
+            cos_angle = -cos_angle;
+        }
+
+        if cos_angle > 1.0 - SLERP_EPSILON {
+            return from.scale(1.0 - t).add(&to.scale(t)).normalize();
+        }
+
+        let angle = cos_angle.acos();
+        let sin_angle = angle.sin();
+
+        from.scale(((1.0 - t) * angle).sin() / sin_angle)
+            .add(&to.scale((t * angle).sin() / sin_angle))
+    }
+
+    /// Row major rotation matrix.
+    #[rustfmt::skip]
+    pub fn to_matrix(&self) -> Matrix4 {
+        let Quaternion { w, x, y, z } = self.normalize();
+
+        [
+            1.0 - 2.0 * (y * y + z * z), 2.0 * (x * y - w * z), 2.0 * (x * z + w * y), 0.0,
+            2.0 * (x * y + w * z), 1.0 - 2.0 * (x * x + z * z), 2.0 * (y * z - w * x), 0.0,
+            2.0 * (x * z - w * y), 2.0 * (y * z + w * x), 1.0 - 2.0 * (x * x + y * y), 0.0,
+            0.0, 0.0, 0.0, 1.0,
+        ]
+    }
+}
+
+impl Interpolate for Quaternion {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        self.slerp(other, t)
+    }
+}
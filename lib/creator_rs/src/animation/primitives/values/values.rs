@@ -2,3 +2,5 @@ pub type Float = f32;
 pub type FloatVec2 = (f32, f32);
 pub type FloatVec3 = (f32, f32, f32);
 pub type FloatVec4 = (f32, f32, f32, f32);
+/// Row major 4x4 matrix.
+pub type Matrix4 = [f32; 16];
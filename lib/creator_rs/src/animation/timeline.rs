@@ -4,13 +4,17 @@ use super::primitives::{
         rect::AnimatedRectEntity,
         text::AnimatedTextEntity,
     },
+    frame_rate::FrameRate,
     paint::{Color, FillStyle, Paint, PaintStyle, StrokeStyle, TextAlign, TextPaint},
     values::animated_values::{AnimatedFloat, AnimatedFloatVec2},
 };
+use super::validation::{validate_timeline, ValidationReport};
 use crate::animation::primitives::{
     interpolations::{EasingFunction, InterpolationType, SpringProperties},
     keyframe::{Keyframe, Keyframes},
+    utils::frame_to_timestamp,
 };
+use crate::error::{CreatorError, CreatorResult};
 #[cfg(feature = "parallelization")]
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -28,7 +32,7 @@ pub struct Timeline {
     entities: Vec<AnimatedEntity>,
     pub render_state: RenderState,
     pub duration: f32,
-    pub fps: i16,
+    pub fps: FrameRate,
     pub size: (i32, i32),
 }
 
@@ -38,43 +42,65 @@ pub struct RenderState {
 }
 
 impl Timeline {
-    #[cfg(feature = "parallelization")]
-    fn calculate(&self) -> Vec<Entity> {
-        let mut entities = self.entities.clone();
+    pub fn from_json(timeline_json: &str) -> CreatorResult<Timeline> {
+        Ok(serde_json::from_str(timeline_json)?)
+    }
 
-        let entities = entities
-            .par_iter_mut()
-            .map(|entity| entity.calculate(self))
-            .filter(|entity| entity.is_some())
-            .map(|entity| entity.unwrap())
-            .collect();
+    /// Checks the timeline for problems like duplicate ids, NaN values, values without keyframes
+    /// or entities that are never visible, so broken projects can be caught before rendering.
+    pub fn validate(&self) -> ValidationReport {
+        validate_timeline(&self.entities, self.duration)
+    }
 
-        return entities;
+    /// Switches to another frame rate. Entities, keyframes and the duration move to the
+    /// nearest frame of the new frame rate, keyframes that end up on the same frame keep
+    /// the earliest one. The current frame shows the same moment afterwards.
+    pub fn convert_frame_rate(&mut self, fps: FrameRate) {
+        for entity in &mut self.entities {
+            entity.quantize(fps);
+        }
+
+        self.render_state.curr_frame = fps.convert_frame(self.render_state.curr_frame, self.fps);
+        self.duration = fps.quantize(self.duration);
+        self.fps = fps;
     }
 
-    #[cfg(not(feature = "parallelization"))]
     fn calculate(&self) -> Vec<Entity> {
-        let mut entities = self.entities.clone();
+        self.calculate_at_time(frame_to_timestamp(
+            self.render_state.curr_frame as f32,
+            self.fps,
+        ))
+    }
 
-        let entities = entities
-            .iter_mut()
-            .map(|entity| entity.calculate(self))
-            .filter(|entity| entity.is_some())
-            .map(|entity| entity.unwrap())
-            .collect();
+    /// Calculates all entities at the given time in seconds, which doesn't have to fall on a frame.
+    #[cfg(feature = "parallelization")]
+    pub fn calculate_at_time(&self, time: f32) -> Vec<Entity> {
+        self.entities
+            .par_iter()
+            .filter_map(|entity| entity.calculate_at_time(self, time))
+            .collect()
+    }
 
-        return entities;
+    /// Calculates all entities at the given time in seconds, which doesn't have to fall on a frame.
+    #[cfg(not(feature = "parallelization"))]
+    pub fn calculate_at_time(&self, time: f32) -> Vec<Entity> {
+        self.entities
+            .iter()
+            .filter_map(|entity| entity.calculate_at_time(self, time))
+            .collect()
     }
 }
 
 fn build_bg(offset: f32, paint: Paint, size: (i32, i32)) -> AnimatedRectEntity {
     let bg_box = AnimatedRectEntity {
         id: String::from_str("1").unwrap(),
-        paint,
+        paint: paint.into(),
+        opacity: AnimatedFloat::new(1.0),
         animation_data: AnimationData {
             offset: 0.0 + offset,
             duration: 5.0,
             visible: true,
+            ..Default::default()
         },
         cache: Cache { valid: false },
         transform: None,
@@ -82,60 +108,63 @@ fn build_bg(offset: f32, paint: Paint, size: (i32, i32)) -> AnimatedRectEntity {
         position: AnimatedFloatVec2 {
             keyframes: (
                 AnimatedFloat {
-                    keyframes: Keyframes {
-                        values: vec![
-                            Keyframe {
-                                id: "1".into(),
-                                value: (size.0 * -1) as f32,
-                                offset: 0.0,
-                                interpolation: Some(InterpolationType::EasingFunction(
-                                    EasingFunction::QuintOut,
-                                )),
-                            },
-                            Keyframe {
-                                id: "2".into(),
-                                value: 0.0,
-                                offset: 5.0,
-                                interpolation: None,
-                            },
-                        ],
-                    },
-                },
-                AnimatedFloat {
-                    keyframes: Keyframes {
-                        values: vec![Keyframe {
-                            id: "3".into(),
-                            value: 0.0,
+                    keyframes: Keyframes::new(vec![
+                        Keyframe {
+                            id: "1".into(),
+                            value: (size.0 * -1) as f32,
                             offset: 0.0,
+                            interpolation: Some(InterpolationType::EasingFunction(
+                                EasingFunction::QuintOut,
+                            )),
+                            temporal_ease: None,
+                        },
+                        Keyframe {
+                            id: "2".into(),
+                            value: 0.0,
+                            offset: 5.0,
                             interpolation: None,
-                        }],
-                    },
+                            temporal_ease: None,
+                        },
+                    ]),
+                    drivers: Vec::new(),
+                },
+                AnimatedFloat {
+                    keyframes: Keyframes::new(vec![Keyframe {
+                        id: "3".into(),
+                        value: 0.0,
+                        offset: 0.0,
+                        interpolation: None,
+                        temporal_ease: None,
+                    }]),
+                    drivers: Vec::new(),
                 },
             ),
+            motion_path: None,
         },
         size: AnimatedFloatVec2 {
             keyframes: (
                 AnimatedFloat {
-                    keyframes: Keyframes {
-                        values: vec![Keyframe {
-                            id: "4".into(),
-                            interpolation: None,
-                            value: size.0 as f32,
-                            offset: 0.0,
-                        }],
-                    },
+                    keyframes: Keyframes::new(vec![Keyframe {
+                        id: "4".into(),
+                        interpolation: None,
+                        value: size.0 as f32,
+                        offset: 0.0,
+                        temporal_ease: None,
+                    }]),
+                    drivers: Vec::new(),
                 },
                 AnimatedFloat {
-                    keyframes: Keyframes {
-                        values: vec![Keyframe {
-                            id: "5".into(),
-                            value: size.1 as f32,
-                            offset: 0.0,
-                            interpolation: None,
-                        }],
-                    },
+                    keyframes: Keyframes::new(vec![Keyframe {
+                        id: "5".into(),
+                        value: size.1 as f32,
+                        offset: 0.0,
+                        interpolation: None,
+                        temporal_ease: None,
+                    }]),
+                    drivers: Vec::new(),
                 },
             ),
+            motion_path: None,
         },
     };
     return bg_box;
@@ -146,17 +175,50 @@ pub fn calculate_timeline_at_curr_frame(timeline: Timeline) -> Vec<Entity> {
     timeline.calculate()
 }
 
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub fn calculate_timeline_at_time(timeline: Timeline, time: f32) -> Vec<Entity> {
+    timeline.calculate_at_time(time)
+}
+
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub fn convert_timeline_frame_rate(mut timeline: Timeline, fps: FrameRate) -> Timeline {
+    timeline.convert_frame_rate(fps);
+    timeline
+}
+
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub fn validate_timeline_report(timeline: Timeline) -> ValidationReport {
+    timeline.validate()
+}
+
+/// Returns the JSON of the [`ValidationReport`], throws the JSON of a [`CreatorError`] when the
+/// timeline can't be parsed.
 #[wasm_bindgen]
-pub fn calculate_timeline_from_json_at_curr_frame(timeline_json: &str) -> String {
-    // TODO: Handle failure instead of unwrap
-    let timeline: Timeline = serde_json::from_str(timeline_json).unwrap();
+pub fn validate_timeline_from_json(timeline_json: &str) -> Result<String, JsValue> {
+    let report = Timeline::from_json(timeline_json)?.validate();
 
-    let entities = calculate_timeline_at_curr_frame(timeline);
+    serde_json::to_string(&report).map_err(|error| {
+        CreatorError::Serialization {
+            message: error.to_string(),
+        }
+        .into()
+    })
+}
+
+/// Throws the JSON of a [`CreatorError`] when the timeline can't be parsed.
+#[wasm_bindgen]
+pub fn calculate_timeline_from_json_at_curr_frame(timeline_json: &str) -> Result<String, JsValue> {
+    Ok(calculate_timeline_json_at_curr_frame(timeline_json)?)
+}
+
+pub fn calculate_timeline_json_at_curr_frame(timeline_json: &str) -> CreatorResult<String> {
+    let timeline = Timeline::from_json(timeline_json)?;
 
-    // TODO: Handle failure instead of unwrap
-    let entities_json = serde_json::to_string(&entities).unwrap();
+    let entities = calculate_timeline_at_curr_frame(timeline);
 
-    entities_json
+    serde_json::to_string(&entities).map_err(|error| CreatorError::Serialization {
+        message: error.to_string(),
+    })
 }
 
 pub fn test_timeline_entities_at_frame(
@@ -202,7 +264,7 @@ pub fn test_timeline_entities_at_frame(
     };
 
     let timeline = Timeline {
-        fps: 60,
+        fps: FrameRate::new(60, 1).unwrap(),
         duration: 5.0,
         size,
         entities: vec![
@@ -211,103 +273,115 @@ pub fn test_timeline_entities_at_frame(
             AnimatedEntity::Rect(build_bg(1.0, rect3_paint, size)),
             AnimatedEntity::Text(AnimatedTextEntity {
                 id: String::from_str("2").unwrap(),
-                paint: title_paint,
+                paint: title_paint.into(),
+                opacity: AnimatedFloat::new(1.0),
                 cache: Cache { valid: false },
                 text: input.title,
                 animation_data: AnimationData {
                     offset: 0.0,
                     duration: 6.0,
                     visible: true,
+                    ..Default::default()
                 },
                 transform: None,
                 origin: AnimatedFloatVec2 {
                     keyframes: (
                         AnimatedFloat {
-                            keyframes: Keyframes {
-                                values: vec![
-                                    Keyframe {
-                                        id: "1".into(),
-                                        value: 0.0,
-                                        offset: 0.0,
-                                        interpolation: Some(InterpolationType::Spring(
-                                            SpringProperties {
-                                                mass: 1.0,
-                                                damping: 20.0,
-                                                stiffness: 200.0,
-                                            },
-                                        )),
-                                    },
-                                    Keyframe {
-                                        id: "2".into(),
-                                        value: (size.0 / 2) as f32,
-                                        offset: 2.0,
-                                        interpolation: None,
-                                    },
-                                ],
-                            },
-                        },
-                        AnimatedFloat {
-                            keyframes: Keyframes {
-                                values: vec![Keyframe {
-                                    id: "3".into(),
-                                    value: (size.1 / 2) as f32,
+                            keyframes: Keyframes::new(vec![
+                                Keyframe {
+                                    id: "1".into(),
+                                    value: 0.0,
                                     offset: 0.0,
+                                    interpolation: Some(InterpolationType::Spring(
+                                        SpringProperties {
+                                            mass: 1.0,
+                                            damping: 20.0,
+                                            stiffness: 200.0,
+                                            run_to_rest: false,
+                                            inherit_velocity: false,
+                                        },
+                                    )),
+                                    temporal_ease: None,
+                                },
+                                Keyframe {
+                                    id: "2".into(),
+                                    value: (size.0 / 2) as f32,
+                                    offset: 2.0,
                                     interpolation: None,
-                                }],
-                            },
+                                    temporal_ease: None,
+                                },
+                            ]),
+                            drivers: Vec::new(),
+                        },
+                        AnimatedFloat {
+                            keyframes: Keyframes::new(vec![Keyframe {
+                                id: "3".into(),
+                                value: (size.1 / 2) as f32,
+                                offset: 0.0,
+                                interpolation: None,
+                                temporal_ease: None,
+                            }]),
+                            drivers: Vec::new(),
                         },
                     ),
+                    motion_path: None,
                 },
             }),
             AnimatedEntity::Text(AnimatedTextEntity {
                 id: String::from_str("3").unwrap(),
-                paint: sub_title_paint,
+                paint: sub_title_paint.into(),
+                opacity: AnimatedFloat::new(1.0),
                 text: input.sub_title,
                 cache: Cache { valid: false },
                 animation_data: AnimationData {
                     offset: 0.5,
                     duration: 6.0,
                     visible: true,
+                    ..Default::default()
                 },
                 transform: None,
                 origin: AnimatedFloatVec2 {
                     keyframes: (
                         AnimatedFloat {
-                            keyframes: Keyframes {
-                                values: vec![
-                                    Keyframe {
-                                        id: "5".into(),
-                                        value: 0.0,
-                                        offset: 0.0,
-                                        interpolation: Some(InterpolationType::Spring(
-                                            SpringProperties {
-                                                mass: 1.0,
-                                                damping: 20.0,
-                                                stiffness: 200.0,
-                                            },
-                                        )),
-                                    },
-                                    Keyframe {
-                                        id: "6".into(),
+                            keyframes: Keyframes::new(vec![
+                                Keyframe {
+                                    id: "5".into(),
+                                    value: 0.0,
+                                    offset: 0.0,
+                                    interpolation: Some(InterpolationType::Spring(
+                                        SpringProperties {
+                                            mass: 1.0,
+                                            damping: 20.0,
+                                            stiffness: 200.0,
+                                            run_to_rest: false,
+                                            inherit_velocity: false,
+                                        },
+                                    )),
+                                    temporal_ease: None,
+                                },
+                                Keyframe {
+                                    id: "6".into(),
 
-                                        value: (size.0 / 2) as f32,
-                                        offset: 2.0,
-                                        interpolation: None,
-                                    },
-                                ],
-                            },
+                                    value: (size.0 / 2) as f32,
+                                    offset: 2.0,
+                                    interpolation: None,
+                                    temporal_ease: None,
+                                },
+                            ]),
+                            drivers: Vec::new(),
                         },
                         AnimatedFloat {
-                            keyframes: Keyframes {
-                                values: vec![Keyframe {
-                                    id: "7".into(),
-                                    value: ((size.1 / 2) as f32) + 80.0,
-                                    offset: 0.0,
-                                    interpolation: None,
-                                }],
-                            },
+                            keyframes: Keyframes::new(vec![Keyframe {
+                                id: "7".into(),
+                                value: ((size.1 / 2) as f32) + 80.0,
+                                offset: 0.0,
+                                interpolation: None,
+                                temporal_ease: None,
+                            }]),
+                            drivers: Vec::new(),
                         },
                     ),
+                    motion_path: None,
                 },
             }),
         ],
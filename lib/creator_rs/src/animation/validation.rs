@@ -0,0 +1,634 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use super::primitives::{
+    drivers::{Driver, DriverSource},
+    entities::{
+        common::{AnimatedEntity, AnimationData},
+        staggered_text::AnimatedStaggeredTextEntity,
+    },
+    interpolations::InterpolationType,
+    keyframe::Keyframes,
+    paint::{AnimatedPaintStyle, AnimatedStrokeStyle},
+    transform::AnimatedTransform,
+    values::{
+        animated_color::AnimatedColor,
+        animated_values::{AnimatedFloat, AnimatedFloatVec2, AnimatedFloatVec3},
+    },
+};
+
+/// Problems found in a timeline, an empty report means the timeline is fine.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct ValidationReport {
+    pub issues: Vec<ValidationIssue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ValidationIssue {
+    pub severity: Severity,
+    /// Where the problem is, such as `entities[2].transform.rotate.x.keyframes[0]`.
+    pub path: String,
+    pub message: String,
+    pub kind: IssueKind,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Likely unintended, but the timeline still renders.
+    Warning,
+    /// The timeline renders wrongly or not at all.
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type")]
+pub enum IssueKind {
+    DuplicateEntityId {
+        id: String,
+    },
+    /// Keyframe ids have to be unique within an entity.
+    DuplicateKeyframeId {
+        id: String,
+    },
+    NonFiniteValue,
+    /// The value falls back to its default, since there is nothing to interpolate.
+    EmptyKeyframes,
+    NegativeOffset {
+        offset: f32,
+    },
+    InvalidSpringMass {
+        mass: f32,
+    },
+    KeyframeBeyondDuration {
+        offset: f32,
+        duration: f32,
+    },
+    EntityOutsideTimeline {
+        start: f32,
+        end: f32,
+    },
+    StaggerOverrunsTimeline {
+        end: f32,
+    },
+}
+
+impl IssueKind {
+    pub fn severity(&self) -> Severity {
+        match self {
+            IssueKind::DuplicateEntityId { .. }
+            | IssueKind::DuplicateKeyframeId { .. }
+            | IssueKind::NonFiniteValue
+            | IssueKind::EmptyKeyframes
+            | IssueKind::InvalidSpringMass { .. } => Severity::Error,
+            IssueKind::NegativeOffset { .. }
+            | IssueKind::KeyframeBeyondDuration { .. }
+            | IssueKind::EntityOutsideTimeline { .. }
+            | IssueKind::StaggerOverrunsTimeline { .. } => Severity::Warning,
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            IssueKind::DuplicateEntityId { id } => format!("Entity id {id} is used more than once"),
+            IssueKind::DuplicateKeyframeId { id } => {
+                format!("Keyframe id {id} is used more than once in the entity")
+            }
+            IssueKind::NonFiniteValue => "Value is NaN or infinite".to_string(),
+            IssueKind::EmptyKeyframes => "There are no keyframes to evaluate".to_string(),
+            IssueKind::NegativeOffset { offset } => format!("Offset {offset} is negative"),
+            IssueKind::InvalidSpringMass { mass } => {
+                format!("Spring mass {mass} has to be greater than 0")
+            }
+            IssueKind::KeyframeBeyondDuration { offset, duration } => {
+                format!("Keyframe at {offset}s is after the end of the entity at {duration}s")
+            }
+            IssueKind::EntityOutsideTimeline { start, end } => {
+                format!("Entity from {start}s to {end}s is never visible in the timeline")
+            }
+            IssueKind::StaggerOverrunsTimeline { end } => {
+                format!("The last letter finishes at {end}s, after the end of the timeline")
+            }
+        }
+    }
+}
+
+impl ValidationReport {
+    pub fn has_errors(&self) -> bool {
+        self.issues
+            .iter()
+            .any(|issue| issue.severity == Severity::Error)
+    }
+
+    fn push(&mut self, path: &str, kind: IssueKind) {
+        self.issues.push(ValidationIssue {
+            severity: kind.severity(),
+            path: path.to_string(),
+            message: kind.message(),
+            kind,
+        });
+    }
+
+    fn check_finite(&mut self, path: &str, values: &[f32]) {
+        if values.iter().any(|value| !value.is_finite()) {
+            self.push(path, IssueKind::NonFiniteValue);
+        }
+    }
+}
+
+/// See [`Timeline::validate`](super::timeline::Timeline::validate).
+pub fn validate_timeline(entities: &[AnimatedEntity], duration: f32) -> ValidationReport {
+    let mut report = ValidationReport::default();
+    let mut entity_ids = HashSet::new();
+
+    report.check_finite("duration", &[duration]);
+
+    for (index, entity) in entities.iter().enumerate() {
+        let mut validator = EntityValidator {
+            report: &mut report,
+            keyframe_ids: HashSet::new(),
+        };
+        let path = format!("entities[{index}]");
+
+        validator.entity(&path, entity, duration);
+
+        if !entity_ids.insert(entity_id(entity)) {
+            report.push(
+                &format!("{path}.id"),
+                IssueKind::DuplicateEntityId {
+                    id: entity_id(entity).to_string(),
+                },
+            );
+        }
+    }
+
+    report
+}
+
+fn entity_id(entity: &AnimatedEntity) -> &str {
+    match entity {
+        AnimatedEntity::Text(text) => &text.id,
+        AnimatedEntity::StaggeredText(staggered_text) => &staggered_text.id,
+        AnimatedEntity::Ellipse(ellipse) => &ellipse.id,
+        AnimatedEntity::Rect(rect) => &rect.id,
+        AnimatedEntity::Path(path) => &path.id,
+    }
+}
+
+/// Walks every animated value of a single entity.
+struct EntityValidator<'a> {
+    report: &'a mut ValidationReport,
+    keyframe_ids: HashSet<String>,
+}
+
+impl EntityValidator<'_> {
+    fn entity(&mut self, path: &str, entity: &AnimatedEntity, timeline_duration: f32) {
+        match entity {
+            AnimatedEntity::Text(text) => {
+                let animation_data = &text.animation_data;
+                self.animation_data(path, animation_data, timeline_duration);
+                self.vec2(&format!("{path}.origin"), &text.origin, animation_data);
+                self.paint(&format!("{path}.paint"), &text.paint.style, animation_data);
+                self.float(&format!("{path}.opacity"), &text.opacity, animation_data);
+                self.optional_transform(path, &text.transform, animation_data);
+            }
+            AnimatedEntity::StaggeredText(staggered_text) => {
+                let animation_data = &staggered_text.animation_data;
+                self.animation_data(path, animation_data, timeline_duration);
+                self.vec2(
+                    &format!("{path}.origin"),
+                    &staggered_text.origin,
+                    animation_data,
+                );
+                self.float(
+                    &format!("{path}.opacity"),
+                    &staggered_text.opacity,
+                    animation_data,
+                );
+                self.optional_transform(path, &staggered_text.transform, animation_data);
+                self.report
+                    .check_finite(&format!("{path}.stagger"), &[staggered_text.stagger]);
+
+                if let Some(transform) = &staggered_text.letter.transform {
+                    let letter_path = format!("{path}.letter.transform");
+                    self.transform(&letter_path, transform, animation_data);
+
+                    let end = stagger_end(staggered_text, transform);
+
+                    if let Some(end) = end.filter(|end| *end > timeline_duration) {
+                        self.report
+                            .push(&letter_path, IssueKind::StaggerOverrunsTimeline { end });
+                    }
+                }
+            }
+            AnimatedEntity::Ellipse(ellipse) => {
+                let animation_data = &ellipse.animation_data;
+                self.animation_data(path, animation_data, timeline_duration);
+                self.vec2(&format!("{path}.radius"), &ellipse.radius, animation_data);
+                self.vec2(&format!("{path}.origin"), &ellipse.origin, animation_data);
+                self.vec2(
+                    &format!("{path}.position"),
+                    &ellipse.position,
+                    animation_data,
+                );
+                self.paint(
+                    &format!("{path}.paint"),
+                    &ellipse.paint.style,
+                    animation_data,
+                );
+                self.float(&format!("{path}.opacity"), &ellipse.opacity, animation_data);
+                self.optional_transform(path, &ellipse.transform, animation_data);
+            }
+            AnimatedEntity::Rect(rect) => {
+                let animation_data = &rect.animation_data;
+                self.animation_data(path, animation_data, timeline_duration);
+                self.vec2(&format!("{path}.size"), &rect.size, animation_data);
+                self.vec2(&format!("{path}.origin"), &rect.origin, animation_data);
+                self.vec2(&format!("{path}.position"), &rect.position, animation_data);
+                self.paint(&format!("{path}.paint"), &rect.paint.style, animation_data);
+                self.float(&format!("{path}.opacity"), &rect.opacity, animation_data);
+                self.optional_transform(path, &rect.transform, animation_data);
+            }
+            AnimatedEntity::Path(shape) => {
+                let animation_data = &shape.animation_data;
+                self.animation_data(path, animation_data, timeline_duration);
+
+                for (index, command) in shape.path.iter().enumerate() {
+                    for (name, point) in command.points() {
+                        self.vec2(
+                            &format!("{path}.path[{index}].{name}"),
+                            point,
+                            animation_data,
+                        );
+                    }
+                }
+
+                self.vec2(&format!("{path}.origin"), &shape.origin, animation_data);
+                self.vec2(&format!("{path}.position"), &shape.position, animation_data);
+                self.paint(&format!("{path}.paint"), &shape.paint.style, animation_data);
+                self.float(&format!("{path}.opacity"), &shape.opacity, animation_data);
+                self.optional_transform(path, &shape.transform, animation_data);
+            }
+        }
+    }
+
+    fn animation_data(
+        &mut self,
+        path: &str,
+        animation_data: &AnimationData,
+        timeline_duration: f32,
+    ) {
+        let path = format!("{path}.animation_data");
+        let start = animation_data.offset;
+        let end = animation_data.offset + animation_data.duration;
+
+        self.report.check_finite(
+            &path,
+            &[
+                animation_data.offset,
+                animation_data.duration,
+                animation_data.speed,
+            ],
+        );
+
+        if start < 0.0 {
+            self.report
+                .push(&path, IssueKind::NegativeOffset { offset: start });
+        }
+
+        if end <= 0.0 || start >= timeline_duration {
+            self.report
+                .push(&path, IssueKind::EntityOutsideTimeline { start, end });
+        }
+
+        if let Some(time_remap) = &animation_data.time_remap {
+            // The remap is evaluated in local time of the entity
+            self.float(
+                &format!("{path}.time_remap"),
+                time_remap,
+                &AnimationData {
+                    duration: animation_data.duration,
+                    ..Default::default()
+                },
+            );
+        }
+    }
+
+    fn optional_transform(
+        &mut self,
+        path: &str,
+        transform: &Option<AnimatedTransform>,
+        animation_data: &AnimationData,
+    ) {
+        if let Some(transform) = transform {
+            self.transform(&format!("{path}.transform"), transform, animation_data);
+        }
+    }
+
+    fn transform(
+        &mut self,
+        path: &str,
+        transform: &AnimatedTransform,
+        animation_data: &AnimationData,
+    ) {
+        self.vec2(
+            &format!("{path}.translate"),
+            &transform.translate,
+            animation_data,
+        );
+        self.vec2(&format!("{path}.scale"), &transform.scale, animation_data);
+        self.vec2(&format!("{path}.skew"), &transform.skew, animation_data);
+        self.vec3(&format!("{path}.rotate"), &transform.rotate, animation_data);
+    }
+
+    fn paint(&mut self, path: &str, style: &AnimatedPaintStyle, animation_data: &AnimationData) {
+        match style {
+            AnimatedPaintStyle::Fill(fill) => {
+                self.color(&format!("{path}.fill.color"), &fill.color, animation_data)
+            }
+            AnimatedPaintStyle::Stroke(stroke) => {
+                self.stroke(&format!("{path}.stroke"), stroke, animation_data)
+            }
+            AnimatedPaintStyle::StrokeAndFill(stroke_and_fill) => {
+                self.stroke(
+                    &format!("{path}.stroke"),
+                    &stroke_and_fill.stroke,
+                    animation_data,
+                );
+                self.color(
+                    &format!("{path}.fill.color"),
+                    &stroke_and_fill.fill.color,
+                    animation_data,
+                );
+            }
+        }
+    }
+
+    fn stroke(&mut self, path: &str, stroke: &AnimatedStrokeStyle, animation_data: &AnimationData) {
+        self.color(&format!("{path}.color"), &stroke.color, animation_data);
+        self.float(&format!("{path}.width"), &stroke.width, animation_data);
+    }
+
+    fn color(&mut self, path: &str, color: &AnimatedColor, animation_data: &AnimationData) {
+        self.keyframes(path, &color.animated.keyframes, animation_data, |color| {
+            vec![color.value().3]
+        });
+    }
+
+    fn vec2(&mut self, path: &str, value: &AnimatedFloatVec2, animation_data: &AnimationData) {
+        let Some(motion_path) = &value.motion_path else {
+            self.float(&format!("{path}.x"), &value.keyframes.0, animation_data);
+            self.float(&format!("{path}.y"), &value.keyframes.1, animation_data);
+            return;
+        };
+
+        // The motion path replaces the keyframes of both axes, only their drivers apply
+        self.drivers(&format!("{path}.x"), &value.keyframes.0, animation_data);
+        self.drivers(&format!("{path}.y"), &value.keyframes.1, animation_data);
+
+        self.keyframes(
+            &format!("{path}.motion_path"),
+            &motion_path.keyframes,
+            animation_data,
+            |point| {
+                vec![
+                    point.point.0,
+                    point.point.1,
+                    point.in_tangent.0,
+                    point.in_tangent.1,
+                    point.out_tangent.0,
+                    point.out_tangent.1,
+                ]
+            },
+        );
+    }
+
+    fn vec3(&mut self, path: &str, value: &AnimatedFloatVec3, animation_data: &AnimationData) {
+        self.float(&format!("{path}.x"), &value.keyframes.0, animation_data);
+        self.float(&format!("{path}.y"), &value.keyframes.1, animation_data);
+        self.float(&format!("{path}.z"), &value.keyframes.2, animation_data);
+    }
+
+    fn float(&mut self, path: &str, value: &AnimatedFloat, animation_data: &AnimationData) {
+        self.keyframes(path, &value.keyframes, animation_data, |value| vec![*value]);
+        self.drivers(path, value, animation_data);
+    }
+
+    fn drivers(&mut self, path: &str, value: &AnimatedFloat, animation_data: &AnimationData) {
+        for (index, driver) in value.drivers.iter().enumerate() {
+            self.driver(&format!("{path}.drivers[{index}]"), driver, animation_data);
+        }
+    }
+
+    fn driver(&mut self, path: &str, driver: &Driver, animation_data: &AnimationData) {
+        match &driver.source {
+            DriverSource::Noise(noise) => {
+                self.float(
+                    &format!("{path}.frequency"),
+                    &noise.frequency,
+                    animation_data,
+                );
+                self.float(
+                    &format!("{path}.amplitude"),
+                    &noise.amplitude,
+                    animation_data,
+                );
+                self.float(&format!("{path}.octaves"), &noise.octaves, animation_data);
+            }
+            DriverSource::Oscillator(oscillator) => {
+                self.float(
+                    &format!("{path}.frequency"),
+                    &oscillator.frequency,
+                    animation_data,
+                );
+                self.float(&format!("{path}.phase"), &oscillator.phase, animation_data);
+                self.float(
+                    &format!("{path}.amplitude"),
+                    &oscillator.amplitude,
+                    animation_data,
+                );
+                self.float(
+                    &format!("{path}.offset"),
+                    &oscillator.offset,
+                    animation_data,
+                );
+            }
+            DriverSource::Audio(audio) => {
+                self.report
+                    .check_finite(&format!("{path}.values"), &audio.values);
+                self.float(
+                    &format!("{path}.amplitude"),
+                    &audio.amplitude,
+                    animation_data,
+                );
+                self.float(&format!("{path}.offset"), &audio.offset, animation_data);
+            }
+        }
+    }
+
+    fn keyframes<T>(
+        &mut self,
+        path: &str,
+        keyframes: &Keyframes<T>,
+        animation_data: &AnimationData,
+        values: impl Fn(&T) -> Vec<f32>,
+    ) {
+        if keyframes.values().is_empty() {
+            self.report
+                .push(&format!("{path}.keyframes"), IssueKind::EmptyKeyframes);
+        }
+
+        for (index, keyframe) in keyframes.values().iter().enumerate() {
+            let path = format!("{path}.keyframes[{index}]");
+
+            self.keyframe(
+                &path,
+                &keyframe.id,
+                keyframe.offset,
+                &keyframe.interpolation,
+                animation_data,
+            );
+            self.report.check_finite(&path, &values(&keyframe.value));
+        }
+    }
+
+    fn keyframe(
+        &mut self,
+        path: &str,
+        id: &str,
+        offset: f32,
+        interpolation: &Option<InterpolationType>,
+        animation_data: &AnimationData,
+    ) {
+        if !self.keyframe_ids.insert(id.to_string()) {
+            self.report
+                .push(path, IssueKind::DuplicateKeyframeId { id: id.to_string() });
+        }
+
+        self.report.check_finite(path, &[offset]);
+
+        if offset < 0.0 {
+            self.report.push(path, IssueKind::NegativeOffset { offset });
+        }
+
+        if offset > animation_data.duration {
+            self.report.push(
+                path,
+                IssueKind::KeyframeBeyondDuration {
+                    offset,
+                    duration: animation_data.duration,
+                },
+            );
+        }
+
+        match interpolation {
+            Some(InterpolationType::Spring(spring)) => {
+                self.report
+                    .check_finite(path, &[spring.mass, spring.damping, spring.stiffness]);
+
+                if spring.mass <= 0.0 {
+                    self.report
+                        .push(path, IssueKind::InvalidSpringMass { mass: spring.mass });
+                }
+            }
+            Some(InterpolationType::CubicBezier(curve)) => {
+                self.report
+                    .check_finite(path, &[curve.x1, curve.y1, curve.x2, curve.y2]);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Samples searched for the time a remapped entity finishes its letters.
+const STAGGER_SEARCH_SAMPLES: usize = 1000;
+
+/// Timeline time at which the last letter reaches its last keyframe, reversed the first letter
+/// its first keyframe. None when that never happens while the entity is visible.
+fn stagger_end(
+    staggered_text: &AnimatedStaggeredTextEntity,
+    transform: &AnimatedTransform,
+) -> Option<f32> {
+    let animation_data = &staggered_text.animation_data;
+    let letters = staggered_text.text.chars().count().max(1);
+    let (first, last) = keyframe_range(transform);
+
+    // Letters shift their keyframes in the time the entity is evaluated at
+    let target = if animation_data.reverse {
+        animation_data.offset + first
+    } else {
+        animation_data.offset + staggered_text.stagger * (letters - 1) as f32 + last
+    };
+    let reached = |time: f32| {
+        let source_time = animation_data.source_time(time);
+
+        if animation_data.reverse {
+            source_time <= target
+        } else {
+            source_time >= target
+        }
+    };
+
+    if animation_data.time_remap.is_none() {
+        let speed = animation_data.speed;
+
+        // A frozen entity never finishes its letters
+        if speed <= 0.0 || !target.is_finite() {
+            return None;
+        }
+
+        let local_time = (target - animation_data.offset) / speed;
+
+        return Some(if animation_data.reverse {
+            animation_data.offset + animation_data.duration - local_time
+        } else {
+            animation_data.offset + local_time
+        });
+    }
+
+    // A remap can run at any speed or even backwards, so the first sample that reaches the
+    // target is searched and then refined
+    let step = animation_data.duration / STAGGER_SEARCH_SAMPLES as f32;
+    let mut after = (0..=STAGGER_SEARCH_SAMPLES)
+        .map(|sample| animation_data.offset + step * sample as f32)
+        .find(|time| reached(*time))?;
+    let mut before = after - step;
+
+    if after == animation_data.offset {
+        return Some(after);
+    }
+
+    for _ in 0..20 {
+        let middle = (before + after) / 2.0;
+
+        if reached(middle) {
+            after = middle;
+        } else {
+            before = middle;
+        }
+    }
+
+    Some(after)
+}
+
+/// Offsets of the first and last keyframe of the transform, relative to the start of the entity.
+/// Both are infinite when there are no keyframes.
+fn keyframe_range(transform: &AnimatedTransform) -> (f32, f32) {
+    [
+        &transform.translate.keyframes.0,
+        &transform.translate.keyframes.1,
+        &transform.scale.keyframes.0,
+        &transform.scale.keyframes.1,
+        &transform.skew.keyframes.0,
+        &transform.skew.keyframes.1,
+        &transform.rotate.keyframes.0,
+        &transform.rotate.keyframes.1,
+        &transform.rotate.keyframes.2,
+    ]
+    .iter()
+    .flat_map(|value| value.keyframes.values().iter())
+    .fold(
+        (f32::INFINITY, f32::NEG_INFINITY),
+        |(first, last), keyframe| (first.min(keyframe.offset), last.max(keyframe.offset)),
+    )
+}
@@ -0,0 +1,62 @@
+//! Validates timeline JSON files, for example in CI before templates are rendered.
+//!
+//! Usage: `validate_timeline [--deny-warnings] <timeline.json>...`
+//!
+//! Exits with 1 when a file has errors, or warnings with `--deny-warnings`, and with 2 when a
+//! file can't be read or parsed.
+
+use std::process::ExitCode;
+
+use creator_rs::animation::{timeline::Timeline, validation::Severity};
+
+fn main() -> ExitCode {
+    let mut deny_warnings = false;
+    let mut paths = Vec::new();
+
+    for argument in std::env::args().skip(1) {
+        match argument.as_str() {
+            "--deny-warnings" => deny_warnings = true,
+            _ => paths.push(argument),
+        }
+    }
+
+    if paths.is_empty() {
+        eprintln!("Usage: validate_timeline [--deny-warnings] <timeline.json>...");
+        return ExitCode::from(2);
+    }
+
+    let mut exit_code = ExitCode::SUCCESS;
+
+    for path in paths {
+        let timeline = std::fs::read_to_string(&path)
+            .map_err(|error| error.to_string())
+            .and_then(|json| Timeline::from_json(&json).map_err(|error| error.to_string()));
+
+        let timeline = match timeline {
+            Ok(timeline) => timeline,
+            Err(error) => {
+                eprintln!("{path}: {error}");
+                return ExitCode::from(2);
+            }
+        };
+
+        let report = timeline.validate();
+
+        for issue in &report.issues {
+            let severity = match issue.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+            };
+
+            println!("{path}: {severity}: {}: {}", issue.path, issue.message);
+        }
+
+        let has_warnings = !report.issues.is_empty();
+
+        if report.has_errors() || (deny_warnings && has_warnings) {
+            exit_code = ExitCode::FAILURE;
+        }
+    }
+
+    exit_code
+}
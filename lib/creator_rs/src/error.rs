@@ -0,0 +1,101 @@
+use std::fmt;
+
+use serde::Serialize;
+use wasm_bindgen::JsValue;
+
+/// Everything that can go wrong in the core crate. Serializes with a `type` tag so the
+/// frontend can tell errors apart and show the details.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type")]
+pub enum CreatorError {
+    /// Input that isn't valid JSON or doesn't match the expected shape.
+    InvalidJson {
+        message: String,
+        line: usize,
+        column: usize,
+    },
+    /// A result that couldn't be turned into JSON.
+    Serialization {
+        message: String,
+    },
+    /// Keyframes without a single keyframe have no value.
+    EmptyKeyframes,
+    FontNotFound {
+        name: String,
+    },
+    FontLoad {
+        name: String,
+        message: String,
+    },
+    Io {
+        path: String,
+        message: String,
+    },
+    InvalidAudio {
+        message: String,
+    },
+    InvalidFrameRate {
+        fps: String,
+    },
+    InvalidTimecode {
+        timecode: String,
+        message: String,
+    },
+    /// SVG path data that can't be parsed, the position is the byte offset of the problem.
+    InvalidSvgPath {
+        message: String,
+        position: usize,
+    },
+}
+
+pub type CreatorResult<T> = Result<T, CreatorError>;
+
+impl fmt::Display for CreatorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            // The message of serde_json already contains the line and column
+            CreatorError::InvalidJson { message, .. } => write!(f, "Invalid JSON: {message}"),
+            CreatorError::Serialization { message } => {
+                write!(f, "Could not serialize the result: {message}")
+            }
+            CreatorError::EmptyKeyframes => write!(f, "There are no keyframes to evaluate"),
+            CreatorError::FontNotFound { name } => write!(f, "Font {name} was not found"),
+            CreatorError::FontLoad { name, message } => {
+                write!(f, "Could not load font {name}: {message}")
+            }
+            CreatorError::Io { path, message } => write!(f, "Could not read {path}: {message}"),
+            CreatorError::InvalidAudio { message } => write!(f, "Invalid audio file: {message}"),
+            CreatorError::InvalidFrameRate { fps } => {
+                write!(f, "Frame rate {fps} has to be greater than 0")
+            }
+            CreatorError::InvalidTimecode { timecode, message } => {
+                write!(f, "Invalid timecode {timecode}: {message}")
+            }
+            CreatorError::InvalidSvgPath { message, position } => {
+                write!(f, "Invalid SVG path at {position}: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CreatorError {}
+
+/// Parsing errors, serializing is mapped to [`CreatorError::Serialization`] where it happens.
+impl From<serde_json::Error> for CreatorError {
+    fn from(error: serde_json::Error) -> Self {
+        CreatorError::InvalidJson {
+            message: error.to_string(),
+            line: error.line(),
+            column: error.column(),
+        }
+    }
+}
+
+/// Thrown in JavaScript as the JSON of the error.
+impl From<CreatorError> for JsValue {
+    fn from(error: CreatorError) -> Self {
+        let json = serde_json::to_string(&error).unwrap_or_else(|_| error.to_string());
+
+        JsValue::from_str(&json)
+    }
+}
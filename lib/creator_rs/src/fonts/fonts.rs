@@ -1,5 +1,7 @@
 use font_kit::source::SystemSource;
 
+use crate::error::{CreatorError, CreatorResult};
+
 #[cfg_attr(feature = "tauri", tauri::command)]
 pub fn get_system_fonts() -> Option<Vec<String>> {
     let source = SystemSource::new();
@@ -10,12 +12,8 @@ pub fn get_system_fonts() -> Option<Vec<String>> {
         Ok(found_fonts) => {
             let font_names: Vec<String> = found_fonts
                 .iter()
-                .map(|f| f.load())
-                .filter(|f| f.is_ok())
-                .map(|f| f.unwrap())
-                .map(|f| f.postscript_name())
-                .filter(|f| f.is_some())
-                .map(|f| f.unwrap())
+                .filter_map(|f| f.load().ok())
+                .filter_map(|f| f.postscript_name())
                 .collect();
 
             Some(font_names)
@@ -34,25 +32,25 @@ pub fn get_system_families() -> Option<Vec<String>> {
 }
 
 #[cfg_attr(feature = "tauri", tauri::command)]
-pub fn get_system_font(font_name: String) -> Option<Vec<u8>> {
+pub fn get_system_font(font_name: String) -> CreatorResult<Vec<u8>> {
     let source = SystemSource::new();
 
-    let font = source.select_by_postscript_name(font_name.as_str());
-
-    match font {
-        Ok(font) => {
-            let font = font.load();
-
-            if let Ok(font) = font {
-                if let Some(font_data) = font.copy_font_data() {
-                    Some(font_data.as_slice().to_owned())
-                } else {
-                    None
-                }
-            } else {
-                None
-            }
-        }
-        Err(_) => panic!("Err"),
+    let font = source
+        .select_by_postscript_name(font_name.as_str())
+        .map_err(|_| CreatorError::FontNotFound {
+            name: font_name.clone(),
+        })?;
+
+    let font = font.load().map_err(|error| CreatorError::FontLoad {
+        name: font_name.clone(),
+        message: error.to_string(),
+    })?;
+
+    match font.copy_font_data() {
+        Some(font_data) => Ok(font_data.as_slice().to_owned()),
+        None => Err(CreatorError::FontLoad {
+            name: font_name,
+            message: "The font has no data".to_string(),
+        }),
     }
 }
@@ -1,3 +1,4 @@
 pub mod animation;
+pub mod error;
 #[cfg(feature = "fonts")]
 pub mod fonts;